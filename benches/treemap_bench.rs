@@ -3,13 +3,17 @@ use ratatui::Terminal;
 use ratatui::backend::TestBackend;
 use ratatui::layout::Rect;
 use std::hint::black_box;
-use treetop::system::process::{ProcessInfo, ProcessState, build_process_tree_from_flat};
+use treetop::format::format_bytes;
+use treetop::system::collector::{BackendKind, Collector};
+use treetop::system::process::{
+    MemoryDetail, ProcessInfo, ProcessState, build_process_tree_from_flat,
+};
 use treetop::treemap::algorithm::squarify_sorted;
 use treetop::treemap::node::{LayoutRect, TreemapItem};
 use treetop::ui::theme::{
     BorderStyle, ColorMode, ColorSupport, HeatOverrides, Theme, colorize_rects,
 };
-use treetop::ui::treemap_widget;
+use treetop::ui::treemap_widget::{self, TileMarks};
 
 fn make_items(n: usize) -> Vec<TreemapItem> {
     (0..n)
@@ -40,6 +44,13 @@ fn make_processes(n: usize) -> Vec<ProcessInfo> {
                 group_name: None,
                 priority: None,
                 io_stats: None,
+                memory_detail: MemoryDetail::default(),
+                container: None,
+                io_rate: None,
+                fd_count: None,
+                start_time: None,
+                start_ticks: None,
+                cpu_time: None,
             }
         })
         .collect()
@@ -149,6 +160,8 @@ fn bench_treemap_widget_render(c: &mut Criterion) {
                             2,
                             BorderStyle::Rounded,
                             &theme,
+                            format_bytes,
+                            &TileMarks::default(),
                         );
                     })
                     .expect("bench draw failed");
@@ -160,11 +173,33 @@ fn bench_treemap_widget_render(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_collector_refresh(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collector_refresh");
+    group.sample_size(20);
+
+    for kind in [BackendKind::Sysinfo, BackendKind::Native] {
+        let mut collector = Collector::with_backend(kind);
+        // Native falls back to sysinfo on platforms without one; don't bench it twice.
+        if kind == BackendKind::Native && collector.backend_name() == "sysinfo" {
+            continue;
+        }
+        group.bench_function(collector.backend_name(), |b| {
+            b.iter(|| {
+                let snapshot = collector.refresh();
+                black_box(snapshot);
+            })
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_squarify,
     bench_layout_pipeline,
     bench_process_tree_build,
-    bench_treemap_widget_render
+    bench_treemap_widget_render,
+    bench_collector_refresh
 );
 criterion_main!(benches);
@@ -23,8 +23,107 @@ pub enum Action {
     ToggleHelp,
     CycleSortMode,
     Refresh,
+    CycleMemoryMetric,
+    CycleGroupMode,
+    CycleView,
+    CycleSizeMode,
+    ToggleOpenFiles,
+    OpenSignalPicker(u32),
+    MoveSignalCursor(i32),
+    TypeSignalNumber(char),
+    SendSignal(u32, sysinfo::Signal),
+    KillTree(u32),
+    SendSignalTree(u32, sysinfo::Signal),
+    ToggleSignalScope,
+    CloseSignalPicker,
+    ConfirmSignal,
+    CancelSignal,
+    AdjustNice(u32, i32),
+    OpenNiceEntry(u32),
+    TypeNice(char),
+    BackspaceNice,
+    SetNice(u32, i32),
+    CloseNiceEntry,
+    CycleIoPriority(u32),
+    ToggleFreeze(u32),
+    ToggleFreezeTree(u32),
+    ToggleFrozenList,
+    MoveFrozenCursor(i32),
+    ThawFrozen(u32),
+    ThawAll,
+    ToggleMark(u32),
+    MarkMatching,
+    ClearMarks,
+    OpenMarkedSignalPicker,
+    SendSignalMarked(sysinfo::Signal),
+    AdjustNiceMarked(i32),
+    OpenMarkedNiceEntry,
+    SetNiceMarked(i32),
+    ExportMarked,
+    ExportImage,
+    ToggleRecording,
+    CycleCompare,
+    PinBaseline,
+    ReplayTogglePlay,
+    ReplayStep(isize),
+    ReplaySpeed(isize),
+    /// Jump to this share (0.0 to 1.0) of the recorded time.
+    ReplaySeek(f64),
+    ScrollOpenFiles(i32),
+    ToggleAuditLog,
+    ScrollAuditLog(i32),
     ZoomIn,
     ZoomOut,
     SelectAt(u16, u16),
     None,
 }
+
+impl Action {
+    /// Whether the action changes the state of another process (signals,
+    /// priorities), which read-only mode and the protected list forbid.
+    pub fn is_control(&self) -> bool {
+        matches!(
+            self,
+            Action::Kill(_)
+                | Action::ForceKill(_)
+                | Action::OpenSignalPicker(_)
+                | Action::SendSignal(..)
+                | Action::KillTree(_)
+                | Action::SendSignalTree(..)
+                | Action::ConfirmSignal
+                | Action::AdjustNice(..)
+                | Action::OpenNiceEntry(_)
+                | Action::SetNice(..)
+                | Action::CycleIoPriority(_)
+                | Action::ToggleFreeze(_)
+                | Action::ToggleFreezeTree(_)
+                | Action::ThawFrozen(_)
+                | Action::ThawAll
+                | Action::OpenMarkedSignalPicker
+                | Action::SendSignalMarked(_)
+                | Action::AdjustNiceMarked(_)
+                | Action::OpenMarkedNiceEntry
+                | Action::SetNiceMarked(_)
+        )
+    }
+
+    /// Process a control action is aimed at, when the action carries it.
+    pub fn target_pid(&self) -> Option<u32> {
+        match self {
+            Action::Kill(pid)
+            | Action::ForceKill(pid)
+            | Action::OpenSignalPicker(pid)
+            | Action::SendSignal(pid, _)
+            | Action::KillTree(pid)
+            | Action::SendSignalTree(pid, _)
+            | Action::AdjustNice(pid, _)
+            | Action::OpenNiceEntry(pid)
+            | Action::SetNice(pid, _)
+            | Action::CycleIoPriority(pid)
+            | Action::ToggleFreeze(pid)
+            | Action::ToggleFreezeTree(pid)
+            | Action::ThawFrozen(pid) => Some(*pid),
+            _ => None,
+        }
+    }
+}
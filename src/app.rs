@@ -1,26 +1,56 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
 
 use crate::action::{Action, Direction};
 use crate::config::{Config, parse_key};
-use crate::format::format_bytes;
-use crate::system::collector::Collector;
-use crate::system::history::HistoryStore;
-use crate::system::kill::{KillResult, kill_process};
+use crate::format::{format_bytes, format_rate, format_timestamp_utc};
+use crate::system::audit::{AuditEntry, AuditLog};
+use crate::system::cgroup::{CgroupNode, cgroup_tile_id, is_cgroup_tile, read_children};
+use crate::system::collector::{BackendKind, Collector};
+use crate::system::container::{group_tile_id, is_group_tile};
+use crate::system::diff::{Baseline, Change, ProcessDelta, SnapshotDiff};
+use crate::system::export::{ExportProcess, write_json};
+use crate::system::fds::OpenFile;
+use crate::system::history::{HistorySample, HistoryStore};
+use crate::system::kill::{
+    KillResult, SignalInfo, available_signals, kill_process, kill_tree, signal_from_name,
+    signal_name,
+};
+use crate::system::memmap::{MemoryMap, MemoryRegion, is_region_tile, region_tile_id};
+use crate::system::net::{ProcessSockets, parse_port_filter};
+use crate::system::platform;
+use crate::system::priority::{
+    IoClass, IoPriority, PriorityResult, SchedPolicy, renice, set_io_priority,
+};
+use crate::system::process::{
+    CpuTimes, MemoryMetric, ProcessIdentity, ProcessInfo, ProcessState, SizeMode,
+};
+use crate::system::recording::{Recorder, now_ms};
+use crate::system::replay::Replay;
+use crate::system::safety::SafetyPolicy;
 use crate::system::snapshot::SystemSnapshot;
 use crate::treemap::node::{LayoutRect, TreemapItem, TreemapRect};
+use crate::ui::svg;
 use crate::ui::theme::{
     BorderStyle, ColorMode, ColorSupport, HeatOverrides, Theme, resolve_color_support,
 };
-use std::collections::{HashMap, VecDeque};
+use crate::ui::treemap_widget::TileMarks;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputMode {
     Normal,
     Filter,
     Help,
+    OpenFiles,
+    SignalPicker,
+    ConfirmSignal,
+    AuditLog,
+    NiceEntry,
+    FrozenList,
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +67,37 @@ pub struct ResolvedKeybinds {
     pub help: KeyCode,
     pub cycle_sort: KeyCode,
     pub refresh: KeyCode,
+    pub cycle_memory: KeyCode,
+    pub cycle_grouping: KeyCode,
+    pub cycle_view: KeyCode,
+    pub cycle_size: KeyCode,
+    pub open_files: KeyCode,
+    pub signal_menu: KeyCode,
+    pub kill_tree: KeyCode,
+    pub confirm: KeyCode,
+    pub audit_log: KeyCode,
+    pub nice_up: KeyCode,
+    pub nice_down: KeyCode,
+    pub set_nice: KeyCode,
+    pub cycle_io_priority: KeyCode,
+    pub freeze: KeyCode,
+    pub freeze_tree: KeyCode,
+    pub frozen_list: KeyCode,
+    pub mark: KeyCode,
+    pub mark_matching: KeyCode,
+    pub clear_marks: KeyCode,
+    pub export: KeyCode,
+    pub export_image: KeyCode,
+    pub record: KeyCode,
+    pub cycle_compare: KeyCode,
+    pub pin_baseline: KeyCode,
+    pub replay_play: KeyCode,
+    pub replay_step_back: KeyCode,
+    pub replay_step_forward: KeyCode,
+    pub replay_slower: KeyCode,
+    pub replay_faster: KeyCode,
+    pub replay_seek_back: KeyCode,
+    pub replay_seek_forward: KeyCode,
 }
 
 impl ResolvedKeybinds {
@@ -54,6 +115,37 @@ impl ResolvedKeybinds {
             help: parse_key(&kb.help).unwrap_or(KeyCode::Char('?')),
             cycle_sort: parse_key(&kb.cycle_sort).unwrap_or(KeyCode::Char('s')),
             refresh: parse_key(&kb.refresh).unwrap_or(KeyCode::Char('r')),
+            cycle_memory: parse_key(&kb.cycle_memory).unwrap_or(KeyCode::Char('m')),
+            cycle_grouping: parse_key(&kb.cycle_grouping).unwrap_or(KeyCode::Char('g')),
+            cycle_view: parse_key(&kb.cycle_view).unwrap_or(KeyCode::Char('v')),
+            cycle_size: parse_key(&kb.cycle_size).unwrap_or(KeyCode::Char('i')),
+            open_files: parse_key(&kb.open_files).unwrap_or(KeyCode::Char('o')),
+            signal_menu: parse_key(&kb.signal_menu).unwrap_or(KeyCode::Char('x')),
+            kill_tree: parse_key(&kb.kill_tree).unwrap_or(KeyCode::Char('T')),
+            confirm: parse_key(&kb.confirm).unwrap_or(KeyCode::Char('y')),
+            audit_log: parse_key(&kb.audit_log).unwrap_or(KeyCode::Char('a')),
+            nice_up: parse_key(&kb.nice_up).unwrap_or(KeyCode::Char('+')),
+            nice_down: parse_key(&kb.nice_down).unwrap_or(KeyCode::Char('-')),
+            set_nice: parse_key(&kb.set_nice).unwrap_or(KeyCode::Char('n')),
+            cycle_io_priority: parse_key(&kb.cycle_io_priority).unwrap_or(KeyCode::Char('I')),
+            freeze: parse_key(&kb.freeze).unwrap_or(KeyCode::Char('f')),
+            freeze_tree: parse_key(&kb.freeze_tree).unwrap_or(KeyCode::Char('F')),
+            frozen_list: parse_key(&kb.frozen_list).unwrap_or(KeyCode::Char('u')),
+            mark: parse_key(&kb.mark).unwrap_or(KeyCode::Char(' ')),
+            mark_matching: parse_key(&kb.mark_matching).unwrap_or(KeyCode::Char('*')),
+            clear_marks: parse_key(&kb.clear_marks).unwrap_or(KeyCode::Char('U')),
+            export: parse_key(&kb.export).unwrap_or(KeyCode::Char('e')),
+            export_image: parse_key(&kb.export_image).unwrap_or(KeyCode::Char('E')),
+            record: parse_key(&kb.record).unwrap_or(KeyCode::Char('R')),
+            cycle_compare: parse_key(&kb.cycle_compare).unwrap_or(KeyCode::Char('D')),
+            pin_baseline: parse_key(&kb.pin_baseline).unwrap_or(KeyCode::Char('P')),
+            replay_play: parse_key(&kb.replay_play).unwrap_or(KeyCode::Char('p')),
+            replay_step_back: parse_key(&kb.replay_step_back).unwrap_or(KeyCode::Char(',')),
+            replay_step_forward: parse_key(&kb.replay_step_forward).unwrap_or(KeyCode::Char('.')),
+            replay_slower: parse_key(&kb.replay_slower).unwrap_or(KeyCode::Char('<')),
+            replay_faster: parse_key(&kb.replay_faster).unwrap_or(KeyCode::Char('>')),
+            replay_seek_back: parse_key(&kb.replay_seek_back).unwrap_or(KeyCode::Char('[')),
+            replay_seek_forward: parse_key(&kb.replay_seek_forward).unwrap_or(KeyCode::Char(']')),
         }
     }
 
@@ -72,11 +164,161 @@ impl ResolvedKeybinds {
             (key_label(self.help), "Toggle help"),
             (key_label(self.cycle_sort), "Cycle sort mode"),
             (key_label(self.refresh), "Refresh data"),
+            (key_label(self.cycle_memory), "Cycle memory metric"),
+            (key_label(self.cycle_grouping), "Group by process/container"),
+            (key_label(self.cycle_view), "Switch process/cgroup view"),
+            (key_label(self.cycle_size), "Size tiles by memory/I/O rate"),
+            (key_label(self.open_files), "List open files"),
+            (key_label(self.audit_log), "Show audit log"),
+            (key_label(self.signal_menu), "Send a signal..."),
+            (key_label(self.kill_tree), "Kill process and descendants"),
+            (key_label(self.mark), "Mark/unmark process"),
+            (
+                key_label(self.mark_matching),
+                "Mark all matching the filter",
+            ),
+            (key_label(self.clear_marks), "Unmark all"),
+            (
+                key_label(self.export),
+                "Export marked (or selected) to JSON",
+            ),
+            (
+                key_label(self.export_image),
+                "Export treemap as SVG and HTML",
+            ),
+            (key_label(self.record), "Start/stop recording snapshots"),
+            (
+                key_label(self.cycle_compare),
+                "Compare with startup/pin/off",
+            ),
+            (key_label(self.pin_baseline), "Pin snapshot to compare with"),
+            (key_label(self.freeze), "Freeze/thaw process"),
+            (
+                key_label(self.freeze_tree),
+                "Freeze/thaw process and descendants",
+            ),
+            (key_label(self.frozen_list), "Processes frozen by treetop"),
+            (key_label(self.nice_up), "Lower priority (nice +1)"),
+            (key_label(self.nice_down), "Raise priority (nice -1)"),
+            (key_label(self.set_nice), "Set nice value..."),
+            (
+                key_label(self.cycle_io_priority),
+                "Cycle I/O priority class",
+            ),
         ];
         entries.push(("↑↓←→".to_string(), "Navigate"));
         entries.push(("Ctrl+C".to_string(), "Quit (always)"));
         entries
     }
+
+    /// Playback keys, listed in the help only while replaying.
+    pub fn replay_help_entries(&self) -> Vec<(String, &'static str)> {
+        vec![
+            (key_label(self.replay_play), "Play/pause replay"),
+            (key_label(self.replay_step_back), "Previous frame"),
+            (key_label(self.replay_step_forward), "Next frame"),
+            (key_label(self.replay_slower), "Slower playback"),
+            (key_label(self.replay_faster), "Faster playback"),
+            (key_label(self.replay_seek_back), "Seek back 10%"),
+            (key_label(self.replay_seek_forward), "Seek forward 10%"),
+            ("Home/End".to_string(), "Seek to start/end"),
+        ]
+    }
+}
+
+/// PIDs listed per outcome in a tree-kill summary before the rest are counted.
+const SUMMARY_PIDS: usize = 6;
+
+/// One-line, per-PID outcome of signalling a tree, e.g.
+/// `Tree of 1234: sent SIGTERM to 1234 1235; gone 1238; failed 1240 (...)`.
+fn tree_kill_summary(root: u32, signal: &str, results: &[KillResult]) -> String {
+    signal_summary(
+        &format!("processes in tree of {root}"),
+        &format!("Tree of {root}"),
+        signal,
+        results,
+    )
+}
+
+/// Outcome of signalling several processes: `Sent SIGTERM to 3 {group}: ...`
+/// when all went through, else `{heading}: sent ...; gone ...; failed ...`.
+fn signal_summary(group: &str, heading: &str, signal: &str, results: &[KillResult]) -> String {
+    let mut sent = Vec::new();
+    let mut gone = Vec::new();
+    let mut reused = Vec::new();
+    let mut failed = Vec::new();
+    let mut refused = Vec::new();
+    for result in results {
+        match result {
+            KillResult::Success(pid, _) => sent.push(*pid),
+            KillResult::NotFound(pid) => gone.push(*pid),
+            KillResult::PidReused(pid) => reused.push(*pid),
+            KillResult::Failed { pid, reason, .. } => failed.push((*pid, reason.as_str())),
+            KillResult::Refused { pid, reason } => refused.push((*pid, reason.as_str())),
+        }
+    }
+    let list = |pids: &[u32]| {
+        let mut out: Vec<String> = pids.iter().take(SUMMARY_PIDS).map(u32::to_string).collect();
+        if pids.len() > SUMMARY_PIDS {
+            out.push(format!("+{}", pids.len() - SUMMARY_PIDS));
+        }
+        out.join(" ")
+    };
+
+    if sent.len() == results.len() {
+        return format!("Sent {signal} to {} {group}: {}", sent.len(), list(&sent));
+    }
+    let mut parts = Vec::new();
+    if !sent.is_empty() {
+        parts.push(format!("sent {signal} to {}", list(&sent)));
+    }
+    if !gone.is_empty() {
+        parts.push(format!("gone {}", list(&gone)));
+    }
+    if !reused.is_empty() {
+        parts.push(format!("PID reused {}", list(&reused)));
+    }
+    for (label, group) in [("refused", &refused), ("failed", &failed)] {
+        if let Some((_, reason)) = group.first() {
+            let pids: Vec<u32> = group.iter().map(|(pid, _)| *pid).collect();
+            parts.push(format!("{label} {} ({reason})", list(&pids)));
+        }
+    }
+    format!("{heading}: {}", parts.join("; "))
+}
+
+/// Whether `signal` stops the process it is sent to.
+fn stops(signal: sysinfo::Signal) -> bool {
+    matches!(
+        signal,
+        sysinfo::Signal::Stop
+            | sysinfo::Signal::TSTP
+            | sysinfo::Signal::TTIN
+            | sysinfo::Signal::TTOU
+    )
+}
+
+/// Status shown when stopping `pid` would stop treetop too.
+fn stop_refusal(pid: u32) -> String {
+    format!("Refused to stop PID {pid}: treetop itself would stop")
+}
+
+/// Rows moved by PageUp/PageDown in the open-files list, signal picker and
+/// audit log.
+const LIST_PAGE: usize = 10;
+
+/// How long a `port:` filter trusts its last scan of every process's
+/// sockets before looking again.
+const PORT_RESCAN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Share of a recording skipped by the replay seek keys.
+const REPLAY_SEEK_STEP: f64 = 0.1;
+
+/// Audit log entries loaded into the viewer.
+const AUDIT_LOG_ENTRIES: usize = 1000;
+
+fn container_key(process: &ProcessInfo) -> Option<String> {
+    process.container.as_ref().and_then(|c| c.group_key())
 }
 
 fn key_label(code: KeyCode) -> String {
@@ -97,6 +339,10 @@ pub enum SortMode {
     #[default]
     Memory,
     Cpu,
+    /// Cumulative user + system CPU time, largest first.
+    CpuTime,
+    /// Oldest process first.
+    Age,
     Name,
 }
 
@@ -104,7 +350,9 @@ impl SortMode {
     pub fn next(self) -> Self {
         match self {
             SortMode::Memory => SortMode::Cpu,
-            SortMode::Cpu => SortMode::Name,
+            SortMode::Cpu => SortMode::CpuTime,
+            SortMode::CpuTime => SortMode::Age,
+            SortMode::Age => SortMode::Name,
             SortMode::Name => SortMode::Memory,
         }
     }
@@ -114,6 +362,8 @@ impl SortMode {
         match self {
             SortMode::Memory => "Memory",
             SortMode::Cpu => "CPU",
+            SortMode::CpuTime => "CPU time",
+            SortMode::Age => "Age",
             SortMode::Name => "Name",
         }
     }
@@ -121,18 +371,271 @@ impl SortMode {
     pub fn from_str_config(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "cpu" => SortMode::Cpu,
+            "cpu_time" | "cputime" | "cpu-time" => SortMode::CpuTime,
+            "age" => SortMode::Age,
             "name" => SortMode::Name,
             _ => SortMode::Memory,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupMode {
+    #[default]
+    Process,
+    /// Processes of one container or pod share a single top-level tile.
+    Container,
+}
+
+impl GroupMode {
+    pub fn next(self) -> Self {
+        match self {
+            GroupMode::Process => GroupMode::Container,
+            GroupMode::Container => GroupMode::Process,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GroupMode::Process => "processes",
+            GroupMode::Container => "containers",
+        }
+    }
+
+    pub fn from_str_config(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "container" | "containers" => GroupMode::Container,
+            _ => GroupMode::Process,
+        }
+    }
+}
+
+/// Which hierarchy the treemap shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ViewMode {
+    #[default]
+    Processes,
+    /// cgroup v2 tree sized by `memory.current`.
+    Cgroups,
+}
+
+impl ViewMode {
+    pub fn next(self) -> Self {
+        match self {
+            ViewMode::Processes => ViewMode::Cgroups,
+            ViewMode::Cgroups => ViewMode::Processes,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ViewMode::Processes => "processes",
+            ViewMode::Cgroups => "cgroups",
+        }
+    }
+
+    pub fn from_str_config(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "cgroup" | "cgroups" => ViewMode::Cgroups,
+            _ => ViewMode::Processes,
+        }
+    }
+}
+
+/// What compare mode measures memory change against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompareMode {
+    #[default]
+    Off,
+    /// The first snapshot of the session.
+    Startup,
+    /// The snapshot pinned last, or the `--compare` baseline.
+    Pinned,
+}
+
+impl CompareMode {
+    pub fn next(self) -> Self {
+        match self {
+            CompareMode::Off => CompareMode::Startup,
+            CompareMode::Startup => CompareMode::Pinned,
+            CompareMode::Pinned => CompareMode::Off,
+        }
+    }
+}
+
+/// Per-process details only read for the process in the detail panel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DetailExtras {
+    pub pid: u32,
+    pub sockets: Option<ProcessSockets>,
+    /// Soft `RLIMIT_NOFILE`; `None` when unlimited or unreadable.
+    pub fd_limit: Option<u64>,
+    pub nice: Option<i32>,
+    pub sched_policy: Option<SchedPolicy>,
+    pub io_priority: Option<IoPriority>,
+}
+
+/// Scrollable list of a process's open descriptors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenFilesView {
+    pub pid: u32,
+    pub name: String,
+    pub files: Vec<OpenFile>,
+    /// First visible row; clamped to the last page when rendered.
+    pub scroll: usize,
+}
+
+/// Most recent audit log entries, newest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditLogView {
+    pub path: PathBuf,
+    pub entries: Vec<AuditEntry>,
+    /// First visible row; clamped to the last page when rendered.
+    pub scroll: usize,
+}
+
+/// `selected_index` when nothing is highlighted, e.g. after the selected
+/// process exited.
+pub const NO_SELECTION: usize = usize::MAX;
+
+/// The selected process, remembered so the highlight can follow it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SelectedProcess {
+    identity: ProcessIdentity,
+    name: String,
+}
+
+/// A process stopped with SIGSTOP from this treetop session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrozenProcess {
+    pub identity: ProcessIdentity,
+    pub name: String,
+}
+
+/// The "frozen by me" list, also shown before quitting while any process
+/// is still frozen.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrozenListView {
+    pub cursor: usize,
+    /// Opened by quit: `q` again quits and leaves the processes stopped.
+    pub quitting: bool,
+}
+
+/// Nice value being typed for one process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiceEntry {
+    pub pid: u32,
+    pub name: String,
+    /// Number of marked processes the value goes to; 0 for just `pid`.
+    pub marked: usize,
+    pub current: Option<i32>,
+    pub text: String,
+}
+
+/// Signal picker opened for one process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalPicker {
+    pub pid: u32,
+    pub name: String,
+    /// Signals this platform can send, with their numbers.
+    pub signals: Vec<(SignalInfo, Option<i32>)>,
+    pub selected: usize,
+    /// Send to the process and all of its descendants.
+    pub tree: bool,
+    /// Number of marked processes the signal goes to; 0 for just `pid`.
+    pub marked: usize,
+    /// Digits typed so far to jump to a signal by number.
+    typed: String,
+}
+
+impl SignalPicker {
+    /// Opens on `initial` when it is in the list, else on the first signal.
+    pub fn new(
+        pid: u32,
+        name: String,
+        signals: Vec<(SignalInfo, Option<i32>)>,
+        initial: sysinfo::Signal,
+    ) -> Self {
+        let selected = signals
+            .iter()
+            .position(|(info, _)| info.signal == initial)
+            .unwrap_or(0);
+        Self {
+            pid,
+            name,
+            signals,
+            selected,
+            tree: false,
+            marked: 0,
+            typed: String::new(),
+        }
+    }
+
+    pub fn selected_signal(&self) -> Option<sysinfo::Signal> {
+        self.signals.get(self.selected).map(|(info, _)| info.signal)
+    }
+
+    /// Selects the signal whose number matches the digits typed so far,
+    /// starting over from `digit` once the typed number matches nothing.
+    fn type_digit(&mut self, digit: char) {
+        self.typed.push(digit);
+        if self.typed.len() > 2 || self.index_of_number(&self.typed).is_none() {
+            self.typed = digit.to_string();
+        }
+        if let Some(index) = self.index_of_number(&self.typed) {
+            self.selected = index;
+        }
+    }
+
+    fn index_of_number(&self, typed: &str) -> Option<usize> {
+        let number: i32 = typed.parse().ok()?;
+        self.signals.iter().position(|(_, n)| *n == Some(number))
+    }
+}
+
+/// A signal held back until the user confirms it, with what it will hit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalConfirm {
+    pub pid: u32,
+    /// Whether `targets` are the marked processes rather than `pid` and,
+    /// with `tree`, its descendants.
+    pub marked: bool,
+    /// The processes previewed, as the snapshot showed them and parents
+    /// before children; confirming sends to exactly these.
+    pub targets: Vec<ProcessInfo>,
+    pub signal: sysinfo::Signal,
+    /// Whether the descendants are signalled too.
+    pub tree: bool,
+    pub name: String,
+    pub user: Option<String>,
+    pub command: String,
+    /// Memory of the process, or of the whole subtree when `tree` is set.
+    pub memory: u64,
+    /// Descendants in the snapshot: orphaned when only the process is
+    /// signalled, signalled along with it otherwise.
+    pub descendants: usize,
+    /// Label of the key that confirms.
+    pub confirm_key: String,
+}
+
+/// A container or pod tile in [`GroupMode::Container`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerGroup {
+    pub key: String,
+    pub label: String,
+    pub process_count: usize,
+    pub value: u64,
+}
+
 pub struct App {
     pub running: bool,
     pub collector: Collector,
     pub snapshot: SystemSnapshot,
     pub layout_rects: Vec<TreemapRect>,
     pub selected_index: usize,
+    /// Process the highlight follows across refreshes; `None` when a
+    /// non-process tile is selected.
+    selected: Option<SelectedProcess>,
     pub input_mode: InputMode,
     pub filter_text: String,
     pub show_detail_panel: bool,
@@ -145,6 +648,10 @@ pub struct App {
     pub min_rect_width: u16,
     pub min_rect_height: u16,
     pub zoom_stack: Vec<u32>,
+    /// Address-space view of the leaf process on top of `zoom_stack`.
+    pub memory_map: Option<MemoryMap>,
+    /// Container or pod zoomed into, below any process entries in `zoom_stack`.
+    pub zoom_group: Option<ContainerGroup>,
     pub history: HistoryStore,
     pub cpu_history: VecDeque<u64>,
     cpu_history_capacity: usize,
@@ -157,13 +664,74 @@ pub struct App {
     max_visible_procs: usize,
     needs_relayout: bool,
     pub sort_mode: SortMode,
+    pub memory_metric: MemoryMetric,
+    pub size_mode: SizeMode,
+    pub group_mode: GroupMode,
+    container_groups: Vec<ContainerGroup>,
+    pub view_mode: ViewMode,
+    /// Paths of the cgroups zoomed into in [`ViewMode::Cgroups`].
+    pub cgroup_zoom: Vec<String>,
+    /// Children of the current cgroup, refreshed every tick in cgroup view.
+    pub cgroup_nodes: Vec<CgroupNode>,
+    cgroup_root: Option<PathBuf>,
+    /// PIDs owning the port of an active `port:N` filter.
+    port_owners: HashSet<u32>,
+    /// Port and time of the scan behind `port_owners`.
+    port_scan: Option<(u16, Instant)>,
+    /// Extras of the process in the detail panel, dropped every refresh.
+    detail_extras: Option<DetailExtras>,
+    pub open_files: Option<OpenFilesView>,
+    pub audit_log: Option<AuditLogView>,
+    pub signal_picker: Option<SignalPicker>,
+    /// Signal the picker opens on, the last one sent through it.
+    last_signal: sysinfo::Signal,
+    pub signal_confirm: Option<SignalConfirm>,
+    /// System users, loaded the first time a uid is shown by name.
+    users: Option<sysinfo::Users>,
+    pub nice_entry: Option<NiceEntry>,
+    /// Processes this session froze and has not thawed, oldest first.
+    pub frozen: Vec<FrozenProcess>,
+    pub frozen_list: Option<FrozenListView>,
+    /// Processes marked for batch actions; an identity stops matching once
+    /// its PID is reused.
+    pub marked: HashSet<ProcessIdentity>,
+    /// Signals that need confirming before they are sent.
+    confirm_signals: Vec<sysinfo::Signal>,
+    pub safety: SafetyPolicy,
+    /// Writes every refreshed snapshot while recording.
+    recorder: Option<Recorder>,
+    /// File recording toggled on from the UI goes to; a new timestamped
+    /// file in the working directory when unset.
+    pub record_path: Option<PathBuf>,
+    /// Recording played back instead of live data.
+    pub replay: Option<Replay>,
+    /// Where the replay seek bar was last drawn, for mouse seeking.
+    pub replay_bar_area: Option<Rect>,
+    pub compare: CompareMode,
+    /// First snapshot of the session, or of the replay.
+    startup_baseline: Option<Baseline>,
+    pinned_baseline: Option<Baseline>,
+    /// Change from the compared baseline to the snapshot on screen.
+    pub diff: Option<SnapshotDiff>,
     pub keybinds: ResolvedKeybinds,
 }
 
 impl App {
     pub fn new(config: Config) -> Self {
-        let mut collector = Collector::new();
+        let memory_metric = MemoryMetric::from_str_config(&config.general.memory_metric);
+        let backend_kind = BackendKind::from_str_config(&config.general.collector);
+        let mut collector = Collector::with_backend(backend_kind);
+        collector.set_memory_metric(memory_metric);
+        let mut notices = Vec::new();
+        // Asking for the native reader where there is none falls back quietly
+        if backend_kind == BackendKind::Native && collector.backend_name() == "sysinfo" {
+            notices.push("No native collector on this platform; using sysinfo".to_string());
+        }
         let snapshot = collector.refresh();
+        let startup_baseline = Baseline {
+            label: "startup".to_string(),
+            tree: snapshot.process_tree.clone(),
+        };
 
         let show_detail_panel = config.general.show_detail_panel;
         let color_support = resolve_color_support(&config.general.color_support);
@@ -181,14 +749,34 @@ impl App {
         let sparkline_length = config.general.sparkline_length;
         let group_threshold = config.treemap.group_threshold;
         let sort_mode = SortMode::from_str_config(&config.general.default_sort);
+        let group_mode = GroupMode::from_str_config(&config.general.default_grouping);
+        let size_mode = SizeMode::from_str_config(&config.treemap.size_by);
         let keybinds = ResolvedKeybinds::from_config(&config.keybinds);
+        let mut safety = SafetyPolicy::new(config.safety.read_only, &config.safety.protected);
+        if config.safety.audit
+            && let Some(path) = config
+                .safety
+                .audit_log
+                .clone()
+                .or_else(AuditLog::default_path)
+        {
+            safety = safety.with_audit(AuditLog::new(path));
+        }
+        let unresolved = safety.unresolved_users();
+        if !unresolved.is_empty() {
+            notices.push(format!(
+                "Unknown user, nothing protected: {}",
+                unresolved.join(", ")
+            ));
+        }
 
-        App {
+        let mut app = App {
             running: true,
             collector,
             snapshot,
             layout_rects: Vec::new(),
             selected_index: 0,
+            selected: None,
             input_mode: InputMode::Normal,
             filter_text: String::new(),
             show_detail_panel,
@@ -196,11 +784,13 @@ impl App {
             theme,
             color_support,
             border_style,
-            status_message: None,
+            status_message: (!notices.is_empty()).then(|| (notices.join("; "), Instant::now())),
             treemap_area: None,
             min_rect_width,
             min_rect_height,
             zoom_stack: Vec::new(),
+            memory_map: None,
+            zoom_group: None,
             history: HistoryStore::new(sparkline_length),
             cpu_history: VecDeque::with_capacity(sparkline_length),
             cpu_history_capacity: sparkline_length,
@@ -213,13 +803,67 @@ impl App {
             max_visible_procs,
             needs_relayout: true,
             sort_mode,
+            memory_metric,
+            size_mode,
+            group_mode,
+            container_groups: Vec::new(),
+            view_mode: ViewMode::Processes,
+            cgroup_zoom: Vec::new(),
+            cgroup_nodes: Vec::new(),
+            cgroup_root: platform::cgroup_root(),
+            port_owners: HashSet::new(),
+            port_scan: None,
+            detail_extras: None,
+            open_files: None,
+            audit_log: None,
+            signal_picker: None,
+            last_signal: sysinfo::Signal::Term,
+            signal_confirm: None,
+            users: None,
+            nice_entry: None,
+            frozen: Vec::new(),
+            frozen_list: None,
+            marked: HashSet::new(),
+            confirm_signals: config
+                .safety
+                .confirm_signals
+                .iter()
+                .map(String::as_str)
+                .filter_map(signal_from_name)
+                .collect(),
+            safety,
+            recorder: None,
+            record_path: None,
+            replay: None,
+            replay_bar_area: None,
+            compare: CompareMode::Off,
+            startup_baseline: Some(startup_baseline),
+            pinned_baseline: None,
+            diff: None,
             keybinds,
+        };
+        if ViewMode::from_str_config(&config.general.default_view) == ViewMode::Cgroups {
+            app.dispatch(Action::CycleView);
         }
+        app
     }
 
     pub fn refresh_data(&mut self) {
-        self.snapshot = self.collector.refresh();
+        if self.replay.is_some() {
+            // Frames advance with playback, not with the refresh timer
+            self.expire_status_message();
+            return;
+        }
+        let snapshot = self.collector.refresh();
+        self.apply_snapshot(snapshot);
+    }
+
+    /// Makes `snapshot` the data on screen and updates everything derived
+    /// from it.
+    fn apply_snapshot(&mut self, snapshot: SystemSnapshot) {
+        self.snapshot = snapshot;
         self.needs_relayout = true;
+        self.record_snapshot();
 
         // Record system-level CPU history
         let cpu_val = (self.snapshot.cpu_usage_percent * 100.0) as u64;
@@ -229,11 +873,22 @@ impl App {
         self.cpu_history.push_back(cpu_val);
 
         // Recompute subtree sizes
-        self.subtree_sizes = self.snapshot.process_tree.all_subtree_sizes();
+        self.subtree_sizes = self
+            .snapshot
+            .process_tree
+            .all_subtree_sizes(self.size_mode, self.memory_metric);
+        self.update_diff();
 
         // Record history for all processes
         for p in self.snapshot.process_tree.processes.values() {
-            self.history.record(p.pid, p.memory_bytes, p.cpu_percent);
+            let sample = HistorySample {
+                memory: p.memory_bytes,
+                cpu: p.cpu_percent,
+                io: p.io_rate.map(|r| r.total()).unwrap_or(0),
+                fds: p.fd_count.map(u64::from).unwrap_or(0),
+                start_time: p.identity().start,
+            };
+            self.history.record(p.pid, sample);
         }
         let alive: std::collections::HashSet<u32> = self
             .snapshot
@@ -244,11 +899,51 @@ impl App {
             .collect();
         self.history.gc(&alive);
 
+        // Forget frozen processes that exited or were resumed elsewhere
+        let processes = &self.snapshot.process_tree.processes;
+        self.frozen.retain(|f| {
+            processes.get(&f.identity.pid).is_some_and(|p| {
+                f.identity.matches(p.identity().start) && p.status == ProcessState::Stopped
+            })
+        });
+
+        self.marked
+            .retain(|m| processes.get(&m.pid).is_some_and(|p| p.identity() == *m));
+
         // Validate zoom stack — remove PIDs that no longer exist
         self.zoom_stack
             .retain(|pid| self.snapshot.process_tree.processes.contains_key(pid));
+        if let Some(group) = &self.zoom_group
+            && !self
+                .snapshot
+                .process_tree
+                .processes
+                .values()
+                .any(|p| container_key(p).as_deref() == Some(group.key.as_str()))
+        {
+            self.zoom_group = None;
+        }
+        if let Some(map_pid) = self.memory_map.as_ref().map(|m| m.pid) {
+            let still_zoomed = self.zoom_pid() == Some(map_pid);
+            self.memory_map = still_zoomed
+                .then(|| platform::process_memory_map(map_pid))
+                .flatten();
+            if still_zoomed && self.memory_map.is_none() {
+                self.zoom_stack.pop();
+            }
+        }
+
+        if self.view_mode == ViewMode::Cgroups {
+            self.reload_cgroups();
+        }
+        self.reload_port_owners();
+        self.detail_extras = None;
+        self.load_detail_extras();
+        self.expire_status_message();
+    }
 
-        // Clear expired status messages (older than 3 seconds)
+    /// Clears the status message once it is 3 seconds old.
+    fn expire_status_message(&mut self) {
         if let Some((_, created)) = &self.status_message
             && created.elapsed().as_secs() >= 3
         {
@@ -256,6 +951,94 @@ impl App {
         }
     }
 
+    /// Switches to playing back `replay` from its first frame. Nothing can
+    /// be signalled or reniced while replaying, and views that read live
+    /// system state are unavailable.
+    pub fn start_replay(&mut self, replay: Replay) {
+        self.recorder = None;
+        self.view_mode = ViewMode::Processes;
+        self.cgroup_zoom.clear();
+        self.cgroup_nodes.clear();
+        self.memory_map = None;
+        self.status_message = Some((
+            format!(
+                "Replaying {} ({} frames)",
+                replay.path().display(),
+                replay.frame_count()
+            ),
+            Instant::now(),
+        ));
+        let snapshot = replay.snapshot();
+        self.replay = Some(replay);
+        self.startup_baseline = Some(Baseline {
+            label: "startup".to_string(),
+            tree: snapshot.process_tree.clone(),
+        });
+        self.history.clear();
+        self.cpu_history.clear();
+        self.apply_snapshot(snapshot);
+    }
+
+    /// Advances replay playback by `elapsed`. Returns whether a new frame
+    /// is on screen.
+    pub fn tick_replay(&mut self, elapsed: std::time::Duration) -> bool {
+        let Some(replay) = &mut self.replay else {
+            return false;
+        };
+        let changed = replay.advance(elapsed);
+        if changed {
+            self.show_replay_frame(false);
+        }
+        changed
+    }
+
+    /// Loads the replay's current frame. Sparkline history only runs
+    /// forward, so it restarts after a jump back in time.
+    fn show_replay_frame(&mut self, backwards: bool) {
+        let Some(replay) = &self.replay else {
+            return;
+        };
+        let snapshot = replay.snapshot();
+        if backwards {
+            self.history.clear();
+            self.cpu_history.clear();
+        }
+        self.apply_snapshot(snapshot);
+    }
+
+    /// Applies a replay control and loads the frame it lands on.
+    fn control_replay(&mut self, control: impl FnOnce(&mut Replay) -> bool) {
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+        let before = replay.position();
+        if control(replay) {
+            let backwards = replay.position() < before;
+            self.show_replay_frame(backwards);
+        }
+    }
+
+    /// Wall-clock time the data on screen describes, in seconds since the
+    /// Unix epoch: the frame time while replaying.
+    pub fn clock_secs(&self) -> u64 {
+        match &self.replay {
+            Some(replay) => replay.time_ms() / 1000,
+            None => now_ms() / 1000,
+        }
+    }
+
+    /// Whether `p` passes the current filter: a name or command substring,
+    /// or holding the port of a `port:N` filter.
+    fn matches_filter(&self, p: &ProcessInfo) -> bool {
+        if parse_port_filter(&self.filter_text).is_some() {
+            return self.port_owners.contains(&p.pid);
+        }
+        let filter = self.filter_text.to_lowercase();
+        filter.is_empty()
+            || p.name.to_lowercase().contains(&filter)
+            || p.command.to_lowercase().contains(&filter)
+    }
+
     pub fn compute_layout(&mut self, width: u16, height: u16) {
         if !self.needs_relayout {
             return;
@@ -271,6 +1054,7 @@ impl App {
         .entered();
 
         let filter_lower = self.filter_text.to_lowercase();
+        let process_matches = |p: &ProcessInfo| self.matches_filter(p);
 
         // If zoomed, show only the children of the zoom target
         let source_pids: Option<Vec<u32>> = self.zoom_pid().and_then(|zpid| {
@@ -282,45 +1066,148 @@ impl App {
         });
 
         let subtree = &self.subtree_sizes;
+        let metric = self.memory_metric;
+        let size = self.size_mode;
+        let mut container_groups = Vec::new();
 
         #[cfg(feature = "perf-tracing")]
         let _build_items_span = tracing::debug_span!("app.compute_layout.build_items").entered();
 
-        let mut items: Vec<TreemapItem> = if let Some(children) = &source_pids {
-            children
+        let mut items: Vec<TreemapItem> = if self.view_mode == ViewMode::Cgroups {
+            self.cgroup_nodes
                 .iter()
-                .filter_map(|pid| self.snapshot.process_tree.processes.get(pid))
-                .filter(|p| {
-                    let sz = subtree.get(&p.pid).copied().unwrap_or(p.memory_bytes);
-                    sz > 0
+                .enumerate()
+                .filter(|(_, n)| {
+                    n.memory_current > 0
                         && (filter_lower.is_empty()
-                            || p.name.to_lowercase().contains(&filter_lower)
-                            || p.command.to_lowercase().contains(&filter_lower))
+                            || n.name.to_lowercase().contains(&filter_lower))
                 })
-                .map(|p| TreemapItem {
-                    pid: p.pid,
-                    label: p.name.clone(),
-                    value: subtree.get(&p.pid).copied().unwrap_or(p.memory_bytes),
+                .map(|(i, n)| TreemapItem {
+                    pid: cgroup_tile_id(i),
+                    label: n.name.clone(),
+                    value: n.memory_current,
                 })
                 .collect()
-        } else {
-            self.snapshot
-                .process_tree
-                .processes
-                .values()
-                .filter(|p| {
-                    p.memory_bytes > 0
+        } else if let Some(map) = &self.memory_map {
+            map.regions
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| {
+                    r.value(metric) > 0
                         && (filter_lower.is_empty()
-                            || p.name.to_lowercase().contains(&filter_lower)
-                            || p.command.to_lowercase().contains(&filter_lower))
+                            || r.label.to_lowercase().contains(&filter_lower))
+                })
+                .map(|(i, r)| TreemapItem {
+                    pid: region_tile_id(i),
+                    label: r.label.clone(),
+                    value: r.value(metric),
+                })
+                .collect()
+        } else if let Some(diff) = self.diff.as_ref().filter(|_| self.comparing()) {
+            // Area is how much memory changed, whichever way
+            let pids: Vec<u32> = match self.zoom_pid() {
+                Some(zpid) => diff.children(zpid).to_vec(),
+                None => diff.deltas.keys().copied().collect(),
+            };
+            pids.into_iter()
+                .filter_map(|pid| {
+                    let delta = diff.deltas.get(&pid)?;
+                    let p = self
+                        .snapshot
+                        .process_tree
+                        .processes
+                        .get(&pid)
+                        .or_else(|| diff.exited.get(&pid))?;
+                    let value =
+                        u64::try_from(self.compare_delta(delta).unsigned_abs()).unwrap_or(u64::MAX);
+                    (value > 0 && process_matches(p)).then(|| TreemapItem {
+                        pid,
+                        label: p.name.clone(),
+                        value,
+                    })
+                })
+                .collect()
+        } else if let Some(children) = &source_pids {
+            children
+                .iter()
+                .filter_map(|pid| self.snapshot.process_tree.processes.get(pid))
+                .filter(|p| {
+                    let sz = subtree
+                        .get(&p.pid)
+                        .copied()
+                        .unwrap_or(p.size_for(size, metric));
+                    sz > 0 && process_matches(p)
                 })
                 .map(|p| TreemapItem {
                     pid: p.pid,
                     label: p.name.clone(),
-                    value: p.memory_bytes,
+                    value: subtree
+                        .get(&p.pid)
+                        .copied()
+                        .unwrap_or(p.size_for(size, metric)),
                 })
                 .collect()
+        } else {
+            let zoom_key = self.zoom_group.as_ref().map(|g| g.key.as_str());
+            let grouping = self.group_mode == GroupMode::Container && zoom_key.is_none();
+            let mut groups: HashMap<String, (ContainerGroup, bool)> = HashMap::new();
+            let mut items = Vec::new();
+
+            for p in self.snapshot.process_tree.processes.values() {
+                let key = container_key(p);
+                if zoom_key.is_some() && key.as_deref() != zoom_key {
+                    continue;
+                }
+                let value = p.size_for(size, metric);
+                if value == 0 {
+                    continue;
+                }
+                let matches = process_matches(p);
+
+                match key {
+                    Some(key) if grouping => {
+                        let (group, any_match) = groups.entry(key.clone()).or_insert_with(|| {
+                            let label = p
+                                .container
+                                .as_ref()
+                                .and_then(|c| c.display_name())
+                                .unwrap_or_else(|| key.clone());
+                            let group = ContainerGroup {
+                                key,
+                                label,
+                                process_count: 0,
+                                value: 0,
+                            };
+                            (group, false)
+                        });
+                        group.process_count += 1;
+                        group.value += value;
+                        *any_match |= matches;
+                    }
+                    _ if matches => items.push(TreemapItem {
+                        pid: p.pid,
+                        label: p.name.clone(),
+                        value,
+                    }),
+                    _ => {}
+                }
+            }
+
+            let mut groups: Vec<(ContainerGroup, bool)> = groups.into_values().collect();
+            groups.sort_by(|a, b| a.0.key.cmp(&b.0.key));
+            for (i, (group, any_match)) in groups.iter().enumerate() {
+                if *any_match || group.label.to_lowercase().contains(&filter_lower) {
+                    items.push(TreemapItem {
+                        pid: group_tile_id(i),
+                        label: group.label.clone(),
+                        value: group.value,
+                    });
+                }
+            }
+            container_groups = groups.into_iter().map(|(g, _)| g).collect();
+            items
         };
+        self.container_groups = container_groups;
 
         #[cfg(feature = "perf-tracing")]
         drop(_build_items_span);
@@ -331,6 +1218,8 @@ impl App {
         let total_value: u64 = items.iter().map(|i| i.value).sum();
         let mut other_count = 0usize;
         let mut other_value = 0u64;
+        // Tiles folded into "Other", so a selection there can be followed
+        let mut other_pids = HashSet::new();
 
         if total_value > 0 && self.group_threshold > 0.0 {
             let mut filtered = Vec::with_capacity(items.len());
@@ -339,6 +1228,7 @@ impl App {
                 if ratio < self.group_threshold {
                     other_count += 1;
                     other_value += item.value;
+                    other_pids.insert(item.pid);
                 } else {
                     filtered.push(item);
                 }
@@ -364,6 +1254,28 @@ impl App {
                     cb.partial_cmp(&ca).unwrap_or(std::cmp::Ordering::Equal)
                 });
             }
+            SortMode::CpuTime => {
+                let processes = &self.snapshot.process_tree.processes;
+                items.sort_by_key(|item| {
+                    std::cmp::Reverse(
+                        processes
+                            .get(&item.pid)
+                            .and_then(|p| p.cpu_time)
+                            .map(CpuTimes::total)
+                            .unwrap_or_default(),
+                    )
+                });
+            }
+            SortMode::Age => {
+                // Unknown start times sort after every known one
+                let processes = &self.snapshot.process_tree.processes;
+                items.sort_by_key(|item| {
+                    processes
+                        .get(&item.pid)
+                        .and_then(|p| p.start_time)
+                        .unwrap_or(u64::MAX)
+                });
+            }
             SortMode::Name => {
                 items.sort_by(|a, b| a.label.to_lowercase().cmp(&b.label.to_lowercase()));
             }
@@ -373,17 +1285,25 @@ impl App {
             let small_items = items.split_off(self.max_visible_procs);
             other_count += small_items.len();
             other_value += small_items.iter().map(|i| i.value).sum::<u64>();
+            other_pids.extend(small_items.iter().map(|i| i.pid));
         }
 
         if other_value > 0 {
             let max_visible_value = items.first().map(|i| i.value).unwrap_or(other_value);
             let capped_value = other_value.min(max_visible_value);
+            let noun = if self.view_mode == ViewMode::Cgroups {
+                "cgroups"
+            } else if self.memory_map.is_some() {
+                "regions"
+            } else {
+                "procs"
+            };
             items.push(TreemapItem {
                 pid: 0,
                 label: format!(
-                    "Other ({} procs, {})",
+                    "Other ({} {noun}, {})",
                     other_count,
-                    format_bytes(other_value)
+                    (self.value_format())(other_value)
                 ),
                 value: capped_value,
             });
@@ -409,11 +1329,10 @@ impl App {
         #[cfg(feature = "perf-tracing")]
         let _squarify_span = tracing::debug_span!("app.compute_layout.squarify").entered();
 
+        let previous_pid = self.layout_rects.get(self.selected_index).map(|r| r.pid);
         self.layout_rects = crate::treemap::algorithm::squarify_sorted(&items, &bounds);
 
-        if self.selected_index >= self.layout_rects.len() && !self.layout_rects.is_empty() {
-            self.selected_index = 0;
-        }
+        self.follow_selection(previous_pid, &other_pids);
         self.needs_relayout = false;
     }
 
@@ -427,6 +1346,12 @@ impl App {
             InputMode::Normal => self.map_key_normal(key),
             InputMode::Filter => self.map_key_filter(key),
             InputMode::Help => self.map_key_help(key),
+            InputMode::OpenFiles => self.map_key_open_files(key),
+            InputMode::AuditLog => self.map_key_audit_log(key),
+            InputMode::NiceEntry => self.map_key_nice_entry(key),
+            InputMode::FrozenList => self.map_key_frozen_list(key),
+            InputMode::SignalPicker => self.map_key_signal_picker(key),
+            InputMode::ConfirmSignal => self.map_key_confirm_signal(key),
         }
     }
 
@@ -454,6 +1379,60 @@ impl App {
         if code == kb.filter {
             return Action::EnterFilterMode;
         }
+        if code == kb.mark {
+            return self.selected_pid().map_or(Action::None, Action::ToggleMark);
+        }
+        if code == kb.mark_matching {
+            return Action::MarkMatching;
+        }
+        if code == kb.clear_marks {
+            return Action::ClearMarks;
+        }
+        if code == kb.export {
+            return Action::ExportMarked;
+        }
+        if code == kb.export_image {
+            return Action::ExportImage;
+        }
+        if code == kb.record {
+            return Action::ToggleRecording;
+        }
+        if code == kb.cycle_compare {
+            return Action::CycleCompare;
+        }
+        if code == kb.pin_baseline {
+            return Action::PinBaseline;
+        }
+        if let Some(replay) = &self.replay {
+            match code {
+                c if c == kb.replay_play => return Action::ReplayTogglePlay,
+                c if c == kb.replay_step_back => return Action::ReplayStep(-1),
+                c if c == kb.replay_step_forward => return Action::ReplayStep(1),
+                c if c == kb.replay_slower => return Action::ReplaySpeed(-1),
+                c if c == kb.replay_faster => return Action::ReplaySpeed(1),
+                c if c == kb.replay_seek_back => {
+                    return Action::ReplaySeek(replay.fraction() - REPLAY_SEEK_STEP);
+                }
+                c if c == kb.replay_seek_forward => {
+                    return Action::ReplaySeek(replay.fraction() + REPLAY_SEEK_STEP);
+                }
+                KeyCode::Home => return Action::ReplaySeek(0.0),
+                KeyCode::End => return Action::ReplaySeek(1.0),
+                _ => {}
+            }
+        }
+        // With processes marked, signals and renicing go to all of them
+        if !self.marked.is_empty() {
+            match code {
+                c if c == kb.kill => return Action::SendSignalMarked(sysinfo::Signal::Term),
+                c if c == kb.force_kill => return Action::SendSignalMarked(sysinfo::Signal::Kill),
+                c if c == kb.signal_menu => return Action::OpenMarkedSignalPicker,
+                c if c == kb.nice_up => return Action::AdjustNiceMarked(1),
+                c if c == kb.nice_down => return Action::AdjustNiceMarked(-1),
+                c if c == kb.set_nice => return Action::OpenMarkedNiceEntry,
+                _ => {}
+            }
+        }
         if code == kb.kill {
             return if let Some(pid) = self.selected_pid() {
                 Action::Kill(pid)
@@ -468,6 +1447,45 @@ impl App {
                 Action::None
             };
         }
+        if code == kb.kill_tree {
+            return if let Some(pid) = self.selected_pid() {
+                Action::KillTree(pid)
+            } else {
+                Action::None
+            };
+        }
+        if code == kb.freeze || code == kb.freeze_tree {
+            let Some(pid) = self.selected_pid() else {
+                return Action::None;
+            };
+            return if code == kb.freeze {
+                Action::ToggleFreeze(pid)
+            } else {
+                Action::ToggleFreezeTree(pid)
+            };
+        }
+        if code == kb.frozen_list {
+            return Action::ToggleFrozenList;
+        }
+        let priority_keys = [kb.nice_up, kb.nice_down, kb.set_nice, kb.cycle_io_priority];
+        if priority_keys.contains(&code) {
+            let Some(pid) = self.selected_pid() else {
+                return Action::None;
+            };
+            return match code {
+                c if c == kb.nice_up => Action::AdjustNice(pid, 1),
+                c if c == kb.nice_down => Action::AdjustNice(pid, -1),
+                c if c == kb.set_nice => Action::OpenNiceEntry(pid),
+                _ => Action::CycleIoPriority(pid),
+            };
+        }
+        if code == kb.signal_menu {
+            return if let Some(pid) = self.selected_pid() {
+                Action::OpenSignalPicker(pid)
+            } else {
+                Action::None
+            };
+        }
         if code == kb.cycle_color {
             return Action::CycleColorMode;
         }
@@ -492,6 +1510,24 @@ impl App {
         if code == kb.refresh {
             return Action::Refresh;
         }
+        if code == kb.cycle_memory {
+            return Action::CycleMemoryMetric;
+        }
+        if code == kb.cycle_grouping {
+            return Action::CycleGroupMode;
+        }
+        if code == kb.cycle_view {
+            return Action::CycleView;
+        }
+        if code == kb.cycle_size {
+            return Action::CycleSizeMode;
+        }
+        if code == kb.audit_log {
+            return Action::ToggleAuditLog;
+        }
+        if code == kb.open_files {
+            return Action::ToggleOpenFiles;
+        }
 
         Action::None
     }
@@ -505,6 +1541,115 @@ impl App {
         Action::None
     }
 
+    fn map_key_nice_entry(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() || c == '-' => Action::TypeNice(c),
+            KeyCode::Backspace => Action::BackspaceNice,
+            KeyCode::Enter => self
+                .nice_entry
+                .as_ref()
+                .and_then(|entry| {
+                    let nice = entry.text.parse().ok()?;
+                    Some(if entry.marked > 0 {
+                        Action::SetNiceMarked(nice)
+                    } else {
+                        Action::SetNice(entry.pid, nice)
+                    })
+                })
+                .unwrap_or(Action::None),
+            KeyCode::Esc => Action::CloseNiceEntry,
+            _ => Action::None,
+        }
+    }
+
+    fn map_key_frozen_list(&self, key: KeyEvent) -> Action {
+        let quitting = self.frozen_list.as_ref().is_some_and(|l| l.quitting);
+        match key.code {
+            KeyCode::Up => Action::MoveFrozenCursor(-1),
+            KeyCode::Down => Action::MoveFrozenCursor(1),
+            KeyCode::Enter => self
+                .frozen_list
+                .as_ref()
+                .and_then(|list| self.frozen.get(list.cursor))
+                .map_or(Action::None, |f| Action::ThawFrozen(f.identity.pid)),
+            KeyCode::Char('a') => Action::ThawAll,
+            code if quitting && code == self.keybinds.quit => Action::Quit,
+            code if code == KeyCode::Esc || code == self.keybinds.frozen_list => {
+                Action::ToggleFrozenList
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn map_key_audit_log(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Up => Action::ScrollAuditLog(-1),
+            KeyCode::Down => Action::ScrollAuditLog(1),
+            KeyCode::PageUp => Action::ScrollAuditLog(-(LIST_PAGE as i32)),
+            KeyCode::PageDown => Action::ScrollAuditLog(LIST_PAGE as i32),
+            KeyCode::Home => Action::ScrollAuditLog(i32::MIN),
+            KeyCode::End => Action::ScrollAuditLog(i32::MAX),
+            code if code == KeyCode::Esc || code == self.keybinds.audit_log => {
+                Action::ToggleAuditLog
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn map_key_open_files(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Up => Action::ScrollOpenFiles(-1),
+            KeyCode::Down => Action::ScrollOpenFiles(1),
+            KeyCode::PageUp => Action::ScrollOpenFiles(-(LIST_PAGE as i32)),
+            KeyCode::PageDown => Action::ScrollOpenFiles(LIST_PAGE as i32),
+            KeyCode::Home => Action::ScrollOpenFiles(i32::MIN),
+            KeyCode::End => Action::ScrollOpenFiles(i32::MAX),
+            code if code == KeyCode::Esc || code == self.keybinds.open_files => {
+                Action::ToggleOpenFiles
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn map_key_confirm_signal(&self, key: KeyEvent) -> Action {
+        match key.code {
+            code if code == self.keybinds.confirm => Action::ConfirmSignal,
+            KeyCode::Esc | KeyCode::Char('n') => Action::CancelSignal,
+            _ => Action::None,
+        }
+    }
+
+    fn map_key_signal_picker(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Up => Action::MoveSignalCursor(-1),
+            KeyCode::Down => Action::MoveSignalCursor(1),
+            KeyCode::PageUp => Action::MoveSignalCursor(-(LIST_PAGE as i32)),
+            KeyCode::PageDown => Action::MoveSignalCursor(LIST_PAGE as i32),
+            KeyCode::Home => Action::MoveSignalCursor(i32::MIN),
+            KeyCode::End => Action::MoveSignalCursor(i32::MAX),
+            KeyCode::Char(c) if c.is_ascii_digit() => Action::TypeSignalNumber(c),
+            KeyCode::Tab => Action::ToggleSignalScope,
+            KeyCode::Enter => self
+                .signal_picker
+                .as_ref()
+                .and_then(|p| {
+                    let signal = p.selected_signal()?;
+                    Some(if p.marked > 0 {
+                        Action::SendSignalMarked(signal)
+                    } else if p.tree {
+                        Action::SendSignalTree(p.pid, signal)
+                    } else {
+                        Action::SendSignal(p.pid, signal)
+                    })
+                })
+                .unwrap_or(Action::None),
+            code if code == KeyCode::Esc || code == self.keybinds.signal_menu => {
+                Action::CloseSignalPicker
+            }
+            _ => Action::None,
+        }
+    }
+
     fn map_key_filter(&self, key: KeyEvent) -> Action {
         match key.code {
             KeyCode::Esc => Action::ClearFilter,
@@ -524,8 +1669,29 @@ impl App {
     }
 
     pub fn dispatch(&mut self, action: Action) {
+        if action.is_control()
+            && let Some(msg) = self.control_refusal(&action)
+        {
+            if self.signal_confirm.take().is_some() {
+                self.input_mode = InputMode::Normal;
+            }
+            self.status_message = Some((msg, Instant::now()));
+            return;
+        }
         match action {
-            Action::Quit => self.running = false,
+            Action::Quit => {
+                let quitting = self.frozen_list.as_ref().is_some_and(|l| l.quitting);
+                if self.frozen.is_empty() || quitting {
+                    self.running = false;
+                } else {
+                    // Offer to thaw first rather than leave processes stopped
+                    self.frozen_list = Some(FrozenListView {
+                        cursor: 0,
+                        quitting: true,
+                    });
+                    self.input_mode = InputMode::FrozenList;
+                }
+            }
             Action::Navigate(dir) => self.navigate(dir),
             Action::EnterFilterMode => {
                 self.input_mode = InputMode::Filter;
@@ -535,11 +1701,17 @@ impl App {
             }
             Action::ClearFilter => {
                 self.filter_text.clear();
+                self.port_owners.clear();
+                self.port_scan = None;
                 self.input_mode = InputMode::Normal;
                 self.needs_relayout = true;
             }
             Action::UpdateFilter(text) => {
+                let old_port = parse_port_filter(&self.filter_text);
                 self.filter_text = text;
+                if parse_port_filter(&self.filter_text) != old_port {
+                    self.reload_port_owners();
+                }
                 self.needs_relayout = true;
             }
             Action::CycleColorMode => {
@@ -563,18 +1735,8 @@ impl App {
             Action::SelectAt(col, row) => {
                 self.select_at(col, row);
             }
-            Action::Kill(pid) => {
-                if pid != 0 {
-                    let result = kill_process(self.collector.system(), pid, sysinfo::Signal::Term);
-                    self.set_kill_status(result);
-                }
-            }
-            Action::ForceKill(pid) => {
-                if pid != 0 {
-                    let result = kill_process(self.collector.system(), pid, sysinfo::Signal::Kill);
-                    self.set_kill_status(result);
-                }
-            }
+            Action::Kill(pid) => self.request_signal(pid, sysinfo::Signal::Term, false),
+            Action::ForceKill(pid) => self.request_signal(pid, sysinfo::Signal::Kill, false),
             Action::ToggleHelp => {
                 self.input_mode = if self.input_mode == InputMode::Help {
                     InputMode::Normal
@@ -589,14 +1751,318 @@ impl App {
             Action::Refresh => {
                 self.refresh_data();
             }
+            Action::CycleGroupMode => {
+                self.group_mode = self.group_mode.next();
+                self.zoom_group = None;
+                self.zoom_stack.clear();
+                self.memory_map = None;
+                self.reset_selection();
+                self.needs_relayout = true;
+                self.status_message = Some((
+                    format!("Grouping by {}", self.group_mode.label()),
+                    Instant::now(),
+                ));
+            }
+            Action::CycleView => {
+                if self.replay.is_some() {
+                    self.status_message = Some((
+                        "cgroups are not part of recordings".to_string(),
+                        Instant::now(),
+                    ));
+                    return;
+                }
+                if self.view_mode == ViewMode::Processes && self.cgroup_root.is_none() {
+                    self.status_message = Some((
+                        "cgroup v2 hierarchy not available".to_string(),
+                        Instant::now(),
+                    ));
+                    return;
+                }
+                self.view_mode = self.view_mode.next();
+                self.cgroup_zoom.clear();
+                self.reload_cgroups();
+                self.reset_selection();
+                self.needs_relayout = true;
+                self.status_message = Some((
+                    format!("Viewing {}", self.view_mode.label()),
+                    Instant::now(),
+                ));
+            }
+            Action::CycleMemoryMetric => {
+                self.memory_metric = self.memory_metric.next();
+                self.collector.set_memory_metric(self.memory_metric);
+                if self.memory_metric.needs_smaps() && self.replay.is_none() {
+                    // The last tick skipped smaps_rollup; fetch real PSS/USS now
+                    self.refresh_data();
+                } else {
+                    self.subtree_sizes = self
+                        .snapshot
+                        .process_tree
+                        .all_subtree_sizes(self.size_mode, self.memory_metric);
+                    self.update_diff();
+                    self.needs_relayout = true;
+                }
+            }
+            Action::ToggleOpenFiles => self.toggle_open_files(),
+            Action::ToggleAuditLog => self.toggle_audit_log(),
+            Action::AdjustNice(pid, delta) => match platform::process_nice(pid) {
+                Some(current) => self.renice_process(pid, current + delta),
+                None => {
+                    self.status_message = Some((
+                        format!("Nice value of PID {pid} is not readable"),
+                        Instant::now(),
+                    ));
+                }
+            },
+            Action::OpenNiceEntry(pid) => self.open_nice_entry(pid),
+            Action::ToggleFreeze(pid) => self.toggle_freeze(pid, false),
+            Action::ToggleFreezeTree(pid) => self.toggle_freeze(pid, true),
+            Action::ToggleFrozenList => {
+                if self.frozen_list.take().is_some() {
+                    self.input_mode = InputMode::Normal;
+                } else {
+                    self.frozen_list = Some(FrozenListView::default());
+                    self.input_mode = InputMode::FrozenList;
+                }
+            }
+            Action::MoveFrozenCursor(delta) => {
+                if let Some(list) = &mut self.frozen_list {
+                    let last = self.frozen.len().saturating_sub(1);
+                    list.cursor = list.cursor.saturating_add_signed(delta as isize).min(last);
+                }
+            }
+            Action::ThawFrozen(pid) => self.thaw_frozen(pid),
+            Action::ThawAll => self.thaw_all(),
+            Action::ToggleMark(pid) => {
+                if let Some(identity) = self.process_identity(pid)
+                    && !self.marked.remove(&identity)
+                {
+                    self.marked.insert(identity);
+                }
+            }
+            Action::MarkMatching => self.mark_matching(),
+            Action::ClearMarks => self.marked.clear(),
+            Action::OpenMarkedSignalPicker => {
+                let marked = self.marked.len();
+                let mut picker = SignalPicker::new(
+                    0,
+                    format!("{marked} marked processes"),
+                    available_signals(),
+                    self.last_signal,
+                );
+                picker.marked = marked;
+                self.signal_picker = Some(picker);
+                self.input_mode = InputMode::SignalPicker;
+            }
+            Action::SendSignalMarked(signal) => {
+                if self.signal_picker.is_some() {
+                    self.close_signal_picker();
+                    self.last_signal = signal;
+                }
+                self.request_signal_marked(signal);
+            }
+            Action::AdjustNiceMarked(delta) => {
+                self.renice_marked(&format!("nice {delta:+}"), |current| current + delta)
+            }
+            Action::OpenMarkedNiceEntry => {
+                let marked = self.marked.len();
+                self.nice_entry = Some(NiceEntry {
+                    pid: 0,
+                    name: format!("{marked} marked processes"),
+                    marked,
+                    current: None,
+                    text: String::new(),
+                });
+                self.input_mode = InputMode::NiceEntry;
+            }
+            Action::SetNiceMarked(nice) => {
+                self.close_nice_entry();
+                self.renice_marked(&format!("nice {nice}"), |_| nice);
+            }
+            Action::ExportMarked => self.export_marked(),
+            Action::ExportImage => self.export_image(),
+            Action::ReplayTogglePlay => self.control_replay(|r| {
+                r.toggle_play();
+                true
+            }),
+            Action::ReplayStep(delta) => self.control_replay(|r| r.step(delta)),
+            Action::ReplaySpeed(delta) => {
+                if let Some(replay) = &mut self.replay {
+                    replay.change_speed(delta);
+                    self.status_message =
+                        Some((format!("Replay speed {}x", replay.speed()), Instant::now()));
+                }
+            }
+            Action::ReplaySeek(fraction) => self.control_replay(|r| r.seek_fraction(fraction)),
+            Action::CycleCompare => {
+                let mut next = self.compare.next();
+                if next == CompareMode::Pinned && self.pinned_baseline.is_none() {
+                    next = next.next();
+                }
+                self.set_compare(next);
+            }
+            Action::PinBaseline => {
+                let label = format!("pin at {}", format_timestamp_utc(self.clock_secs()));
+                self.pin_baseline(Baseline {
+                    label,
+                    tree: self.snapshot.process_tree.clone(),
+                });
+            }
+            Action::ToggleRecording if self.replay.is_some() => {
+                self.status_message =
+                    Some(("Cannot record while replaying".to_string(), Instant::now()));
+            }
+            Action::ToggleRecording => match self.recorder.take() {
+                Some(recorder) => {
+                    self.status_message = Some((
+                        format!(
+                            "Stopped recording: {} frames in {}",
+                            recorder.frames(),
+                            recorder.path().display()
+                        ),
+                        Instant::now(),
+                    ));
+                }
+                None => {
+                    let path = self.record_path.clone().unwrap_or_else(|| {
+                        PathBuf::from(format!("treetop-recording-{}.jsonl", now_ms() / 1000))
+                    });
+                    if let Err(err) = self.start_recording(path.clone()) {
+                        self.status_message = Some((
+                            format!("Cannot record to {}: {err}", path.display()),
+                            Instant::now(),
+                        ));
+                    }
+                }
+            },
+            Action::TypeNice(c) => {
+                if let Some(entry) = &mut self.nice_entry {
+                    // A sign only leads; three characters cover -20..19
+                    if (c != '-' || entry.text.is_empty()) && entry.text.len() < 3 {
+                        entry.text.push(c);
+                    }
+                }
+            }
+            Action::BackspaceNice => {
+                if let Some(entry) = &mut self.nice_entry {
+                    entry.text.pop();
+                }
+            }
+            Action::SetNice(pid, nice) => {
+                self.close_nice_entry();
+                self.renice_process(pid, nice);
+            }
+            Action::CloseNiceEntry => self.close_nice_entry(),
+            Action::CycleIoPriority(pid) => {
+                let current = platform::process_io_priority(pid).unwrap_or(IoPriority {
+                    class: IoClass::None,
+                    level: 0,
+                });
+                self.apply_priority(pid, |sys, target, safety| {
+                    set_io_priority(sys, target, current.next_class(), safety)
+                });
+            }
+            Action::ScrollAuditLog(delta) => {
+                if let Some(view) = &mut self.audit_log {
+                    let last = view.entries.len().saturating_sub(1);
+                    view.scroll = view.scroll.saturating_add_signed(delta as isize).min(last);
+                }
+            }
+            Action::OpenSignalPicker(pid) => self.open_signal_picker(pid),
+            Action::MoveSignalCursor(delta) => {
+                if let Some(picker) = &mut self.signal_picker {
+                    let last = picker.signals.len().saturating_sub(1);
+                    picker.selected = picker
+                        .selected
+                        .saturating_add_signed(delta as isize)
+                        .min(last);
+                    picker.typed.clear();
+                }
+            }
+            Action::TypeSignalNumber(digit) => {
+                if let Some(picker) = &mut self.signal_picker {
+                    picker.type_digit(digit);
+                }
+            }
+            Action::SendSignal(pid, signal) => {
+                self.close_signal_picker();
+                self.last_signal = signal;
+                self.request_signal(pid, signal, false);
+            }
+            Action::CloseSignalPicker => self.close_signal_picker(),
+            Action::KillTree(pid) => self.request_signal(pid, sysinfo::Signal::Term, true),
+            Action::SendSignalTree(pid, signal) => {
+                self.close_signal_picker();
+                self.last_signal = signal;
+                self.request_signal(pid, signal, true);
+            }
+            Action::ConfirmSignal => {
+                if let Some(confirm) = self.signal_confirm.take() {
+                    self.input_mode = InputMode::Normal;
+                    if confirm.marked {
+                        self.signal_marked(&confirm.targets, confirm.signal);
+                    } else {
+                        self.send_signal(
+                            confirm.pid,
+                            &confirm.targets,
+                            confirm.signal,
+                            confirm.tree,
+                        );
+                    }
+                }
+            }
+            Action::CancelSignal => {
+                if let Some(confirm) = self.signal_confirm.take() {
+                    self.input_mode = InputMode::Normal;
+                    let target = if confirm.marked {
+                        format!("{} marked processes", confirm.targets.len())
+                    } else {
+                        format!("PID {}", confirm.pid)
+                    };
+                    self.status_message = Some((
+                        format!("Cancelled {} to {target}", signal_name(confirm.signal)),
+                        Instant::now(),
+                    ));
+                }
+            }
+            Action::ToggleSignalScope => {
+                if let Some(picker) = &mut self.signal_picker {
+                    picker.tree = !picker.tree;
+                }
+            }
+            Action::ScrollOpenFiles(delta) => {
+                if let Some(view) = &mut self.open_files {
+                    let last = view.files.len().saturating_sub(1);
+                    view.scroll = view.scroll.saturating_add_signed(delta as isize).min(last);
+                }
+            }
+            Action::CycleSizeMode => {
+                self.size_mode = self.size_mode.next();
+                self.subtree_sizes = self
+                    .snapshot
+                    .process_tree
+                    .all_subtree_sizes(self.size_mode, self.memory_metric);
+                self.reset_selection();
+                self.needs_relayout = true;
+                self.status_message = Some((
+                    format!("Sizing tiles by {}", self.size_mode.label()),
+                    Instant::now(),
+                ));
+            }
             Action::None => {}
         }
+        self.load_detail_extras();
     }
 
     fn navigate(&mut self, direction: Direction) {
         if self.layout_rects.is_empty() {
             return;
         }
+        if self.selected_index >= self.layout_rects.len() {
+            self.selected_index = 0;
+            self.remember_selection();
+            return;
+        }
 
         let current = &self.layout_rects[self.selected_index].rect;
         let cx = current.x + current.width / 2.0;
@@ -634,9 +2100,83 @@ impl App {
             }
         }
         self.selected_index = best_index;
+        self.remember_selection();
+    }
+
+    /// Follows whatever process is now highlighted.
+    fn remember_selection(&mut self) {
+        self.selected = self.selected_process().map(|p| SelectedProcess {
+            identity: p.identity(),
+            name: p.name.clone(),
+        });
+    }
+
+    /// Moves the highlight to the first tile of a new view.
+    fn reset_selection(&mut self) {
+        self.selected_index = 0;
+        self.selected = None;
+    }
+
+    /// Puts the highlight back on the followed process after a relayout,
+    /// and says so when it moved, went into "Other" or exited.
+    /// `previous_pid` is the tile highlighted before the relayout.
+    fn follow_selection(&mut self, previous_pid: Option<u32>, other_pids: &HashSet<u32>) {
+        let Some(target) = self.selected.clone() else {
+            if self.selected_index == NO_SELECTION {
+                return;
+            }
+            if self.selected_index >= self.layout_rects.len() {
+                self.selected_index = 0;
+            }
+            self.remember_selection();
+            return;
+        };
+        let SelectedProcess { identity, name } = &target;
+        let pid = identity.pid;
+        let alive = self
+            .snapshot
+            .process_tree
+            .processes
+            .get(&pid)
+            .is_some_and(|p| identity.matches(p.identity().start));
+        let position = |pid: u32| self.layout_rects.iter().position(|r| r.pid == pid);
+
+        let msg = if !alive {
+            self.selected_index = NO_SELECTION;
+            self.selected = None;
+            format!("{name} (PID {pid}) exited; nothing is selected")
+        } else if let Some(index) = position(pid) {
+            let moved = previous_pid == Some(pid) && index != self.selected_index;
+            self.selected_index = index;
+            if !moved {
+                return;
+            }
+            format!("Selection followed {name} (PID {pid}) to its new tile")
+        } else if let Some(index) = other_pids.contains(&pid).then(|| position(0)).flatten() {
+            // Keep following it so the highlight returns when it grows again
+            self.selected_index = index;
+            if previous_pid == Some(0) {
+                return;
+            }
+            format!("{name} (PID {pid}) is now grouped into Other")
+        } else {
+            self.selected_index = 0;
+            self.remember_selection();
+            format!("{name} (PID {pid}) is not shown in this view")
+        };
+        self.status_message = Some((msg, Instant::now()));
     }
 
     fn select_at(&mut self, col: u16, row: u16) {
+        if let Some(bar) = self.replay_bar_area
+            && row == bar.y
+            && col >= bar.x
+            && col < bar.x + bar.width
+        {
+            let fraction = f64::from(col - bar.x) / f64::from(bar.width.saturating_sub(1).max(1));
+            self.dispatch(Action::ReplaySeek(fraction));
+            return;
+        }
         let area = match self.treemap_area {
             Some(a) => a,
             None => return,
@@ -657,113 +2197,1218 @@ impl App {
                 && local_row < r.rect.y + r.rect.height
             {
                 self.selected_index = i;
+                self.remember_selection();
                 return;
             }
         }
     }
 
     pub fn selected_pid(&self) -> Option<u32> {
-        self.layout_rects.get(self.selected_index).map(|r| r.pid)
+        self.layout_rects
+            .get(self.selected_index)
+            .map(|r| r.pid)
+            .filter(|&pid| !is_region_tile(pid) && !is_group_tile(pid) && !is_cgroup_tile(pid))
     }
 
-    pub fn selected_process(&self) -> Option<&crate::system::process::ProcessInfo> {
-        self.selected_pid()
-            .and_then(|pid| self.snapshot.process_tree.processes.get(&pid))
+    pub fn selected_cgroup(&self) -> Option<&CgroupNode> {
+        let id = self.layout_rects.get(self.selected_index)?.pid;
+        if !is_cgroup_tile(id) {
+            return None;
+        }
+        self.cgroup_nodes.get((id - cgroup_tile_id(0)) as usize)
     }
 
-    pub fn show_help(&self) -> bool {
-        self.input_mode == InputMode::Help
+    /// Re-reads the children of the current cgroup, backing out of any
+    /// zoomed cgroups that have since been removed.
+    fn reload_cgroups(&mut self) {
+        let Some(root) = &self.cgroup_root else {
+            self.cgroup_nodes.clear();
+            return;
+        };
+        if self.view_mode != ViewMode::Cgroups {
+            self.cgroup_nodes.clear();
+            return;
+        }
+        while let Some(path) = self.cgroup_zoom.last()
+            && !root.join(path.trim_start_matches('/')).is_dir()
+        {
+            self.cgroup_zoom.pop();
+        }
+        let path = self.cgroup_zoom.last().map(String::as_str).unwrap_or("/");
+        self.cgroup_nodes = read_children(root, path);
     }
 
-    pub fn help_entries(&self) -> Vec<(String, &'static str)> {
-        self.keybinds.help_entries()
+    pub fn selected_group(&self) -> Option<&ContainerGroup> {
+        let id = self.layout_rects.get(self.selected_index)?.pid;
+        if !is_group_tile(id) {
+            return None;
+        }
+        self.container_groups.get((id - group_tile_id(0)) as usize)
     }
 
-    fn set_kill_status(&mut self, result: KillResult) {
-        let msg = match result {
-            KillResult::Success(pid, signal) => format!("Sent {signal} to PID {pid}"),
-            KillResult::Failed(err) => err,
-            KillResult::NotFound(pid) => format!("Process {pid} not found"),
+    pub fn selected_region(&self) -> Option<&MemoryRegion> {
+        let map = self.memory_map.as_ref()?;
+        let id = self.layout_rects.get(self.selected_index)?.pid;
+        if !is_region_tile(id) {
+            return None;
+        }
+        map.regions.get((id - region_tile_id(0)) as usize)
+    }
+
+    /// Process shown in the detail panel: the selected tile, or the process
+    /// whose memory map is open.
+    pub fn detail_process(&self) -> Option<&crate::system::process::ProcessInfo> {
+        match &self.memory_map {
+            Some(map) => self.snapshot.process_tree.processes.get(&map.pid),
+            None => self.selected_process().or_else(|| self.selected_exited()),
+        }
+    }
+
+    /// The selected tile's process in compare mode when it has exited since
+    /// the baseline, as it was then.
+    pub fn selected_exited(&self) -> Option<&ProcessInfo> {
+        let diff = self.diff.as_ref().filter(|_| self.comparing())?;
+        diff.exited.get(&self.selected_pid()?)
+    }
+
+    /// Stopped and marked processes, and in compare mode how each tile
+    /// changed, for the treemap.
+    pub fn tile_marks(&self) -> TileMarks {
+        let mut marks = TileMarks {
+            stopped: self.stopped_pids(),
+            marked: self.marked.iter().map(|m| m.pid).collect(),
+            ..TileMarks::default()
         };
-        self.status_message = Some((msg, Instant::now()));
+        if let Some(diff) = self.diff.as_ref().filter(|_| self.comparing()) {
+            for (&pid, delta) in &diff.deltas {
+                if self.compare_delta(delta) < 0 {
+                    marks.shrunk.insert(pid);
+                } else {
+                    marks.grown.insert(pid);
+                }
+                match delta.change {
+                    Change::New => marks.new.insert(pid),
+                    Change::Exited => marks.exited.insert(pid),
+                    Change::Kept => false,
+                };
+            }
+        }
+        marks
     }
 
-    pub fn on_resize(&mut self) {
-        self.needs_relayout = true;
+    /// Processes shown as stopped: those the snapshot reports stopped, and
+    /// those frozen since it was taken.
+    pub fn stopped_pids(&self) -> HashSet<u32> {
+        self.snapshot
+            .process_tree
+            .processes
+            .values()
+            .filter(|p| p.status == ProcessState::Stopped)
+            .map(|p| p.pid)
+            .chain(self.frozen.iter().map(|f| f.identity.pid))
+            .collect()
     }
 
-    pub fn zoom_pid(&self) -> Option<u32> {
-        self.zoom_stack.last().copied()
+    pub fn selected_process(&self) -> Option<&crate::system::process::ProcessInfo> {
+        self.selected_pid()
+            .and_then(|pid| self.snapshot.process_tree.processes.get(&pid))
     }
 
-    pub fn is_zoomed(&self) -> bool {
-        !self.zoom_stack.is_empty()
+    fn open_signal_picker(&mut self, pid: u32) {
+        let Some(name) = self
+            .snapshot
+            .process_tree
+            .processes
+            .get(&pid)
+            .map(|p| p.name.clone())
+        else {
+            self.set_kill_status(KillResult::NotFound(pid));
+            return;
+        };
+        self.signal_picker = Some(SignalPicker::new(
+            pid,
+            name,
+            available_signals(),
+            self.last_signal,
+        ));
+        self.input_mode = InputMode::SignalPicker;
     }
 
-    fn zoom_in(&mut self) {
-        let pid = match self.selected_pid() {
-            Some(pid) if pid != 0 => pid,
-            _ => return,
+    fn close_signal_picker(&mut self) {
+        self.signal_picker = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    fn toggle_open_files(&mut self) {
+        if self.open_files.take().is_some() {
+            self.input_mode = InputMode::Normal;
+            return;
+        }
+        let Some((pid, name)) = self.detail_process().map(|p| (p.pid, p.name.clone())) else {
+            return;
         };
-        // Only zoom if the process has children
-        if let Some(process) = self.snapshot.process_tree.processes.get(&pid)
-            && !process.children.is_empty()
-        {
-            self.zoom_stack.push(pid);
-            self.selected_index = 0;
-            self.needs_relayout = true;
+        if self.replay.is_some() {
+            self.status_message = Some((
+                "Open files are not part of recordings".to_string(),
+                Instant::now(),
+            ));
+            return;
+        }
+        match platform::process_open_files(pid) {
+            Some(files) => {
+                self.open_files = Some(OpenFilesView {
+                    pid,
+                    name,
+                    files,
+                    scroll: 0,
+                });
+                self.input_mode = InputMode::OpenFiles;
+            }
+            None => {
+                self.status_message = Some((
+                    format!("Open files of PID {pid} are not readable"),
+                    Instant::now(),
+                ));
+            }
         }
     }
 
-    fn zoom_out(&mut self) {
-        if self.zoom_stack.pop().is_some() {
-            self.selected_index = 0;
-            self.needs_relayout = true;
+    /// Finds the owners of the `port:` filter's port, scanning every
+    /// process's sockets only when the port changed or the last scan is
+    /// older than [`PORT_RESCAN_INTERVAL`].
+    fn reload_port_owners(&mut self) {
+        // Sockets are not recorded; today's port owners say nothing about a
+        // replayed process
+        let port = parse_port_filter(&self.filter_text).filter(|_| self.replay.is_none());
+        let Some(port) = port else {
+            self.port_owners.clear();
+            self.port_scan = None;
+            return;
+        };
+        let fresh = self
+            .port_scan
+            .is_some_and(|(scanned, at)| scanned == port && at.elapsed() < PORT_RESCAN_INTERVAL);
+        if !fresh {
+            self.port_owners = platform::port_owners(port);
+            self.port_scan = Some((port, Instant::now()));
         }
     }
 
-    pub fn zoom_breadcrumbs(&self) -> Vec<(u32, String)> {
-        self.zoom_stack
-            .iter()
-            .filter_map(|&pid| {
-                self.snapshot
-                    .process_tree
-                    .processes
-                    .get(&pid)
-                    .map(|p| (pid, p.name.clone()))
-            })
-            .collect()
+    /// Reads the extras of the process in the detail panel, at most once
+    /// per refresh and selection. Runs after each refresh and action; a
+    /// selection that only settles in the next layout gets its extras with
+    /// the following refresh or key press.
+    fn load_detail_extras(&mut self) {
+        if self.replay.is_some() || !self.show_detail_panel {
+            return;
+        }
+        let Some(pid) = self.detail_process().map(|p| p.pid) else {
+            return;
+        };
+        if self.detail_extras.as_ref().is_none_or(|e| e.pid != pid) {
+            self.detail_extras = Some(DetailExtras {
+                pid,
+                sockets: platform::process_sockets(pid),
+                fd_limit: platform::process_fd_limit(pid),
+                nice: platform::process_nice(pid),
+                sched_policy: platform::process_sched_policy(pid),
+                io_priority: platform::process_io_priority(pid),
+            });
+        }
     }
 
-    pub fn is_animating(&self) -> bool {
-        self.animation_frame > 0 && self.animation_frame <= self.anim_frames
+    pub fn detail_extras(&self) -> Option<&DetailExtras> {
+        let pid = self.detail_process()?.pid;
+        self.detail_extras.as_ref().filter(|e| e.pid == pid)
     }
 
-    pub fn tick_animation(&mut self) {
-        if self.is_animating() {
-            self.animation_frame += 1;
-            if self.animation_frame > self.anim_frames {
-                self.animation_frame = 0;
-                self.prev_layout_rects.clear();
-            }
-        }
+    /// Whether tiles are currently sized by I/O rate. Memory maps and cgroups
+    /// have no per-tile I/O figures and stay sized by memory.
+    pub fn sizing_by_io(&self) -> bool {
+        self.size_mode == SizeMode::IoRate
+            && self.view_mode == ViewMode::Processes
+            && self.memory_map.is_none()
+            && !self.comparing()
     }
 
-    pub fn display_rects(&self) -> Vec<TreemapRect> {
-        if !self.is_animating() || self.prev_layout_rects.is_empty() {
-            return self.layout_rects.clone();
+    /// Whether tiles show memory change from a baseline: compare mode is on
+    /// and the view lists processes.
+    pub fn comparing(&self) -> bool {
+        self.diff.is_some() && self.view_mode == ViewMode::Processes && self.memory_map.is_none()
+    }
+
+    /// The snapshot compare mode measures against.
+    pub fn baseline(&self) -> Option<&Baseline> {
+        match self.compare {
+            CompareMode::Off => None,
+            CompareMode::Startup => self.startup_baseline.as_ref(),
+            CompareMode::Pinned => self.pinned_baseline.as_ref(),
         }
+    }
 
-        let t = self.animation_frame as f64 / self.anim_frames as f64;
+    /// Makes `baseline` the pinned snapshot and compares with it.
+    pub fn pin_baseline(&mut self, baseline: Baseline) {
+        self.pinned_baseline = Some(baseline);
+        self.set_compare(CompareMode::Pinned);
+    }
 
-        self.layout_rects
-            .iter()
-            .map(|new_rect| {
-                // Find matching old rect by pid
-                let old = self
-                    .prev_layout_rects
-                    .iter()
-                    .find(|old| old.pid == new_rect.pid);
+    fn set_compare(&mut self, mode: CompareMode) {
+        self.compare = mode;
+        self.zoom_group = None;
+        self.update_diff();
+        self.reset_selection();
+        let msg = match (self.baseline(), &self.diff) {
+            (Some(baseline), Some(diff)) => {
+                let (grown, shrunk) = diff.totals();
+                format!(
+                    "Comparing with {}: {} grown, {} shrunk",
+                    baseline.label,
+                    format_bytes(grown),
+                    format_bytes(shrunk)
+                )
+            }
+            _ => "Compare mode off".to_string(),
+        };
+        self.status_message = Some((msg, Instant::now()));
+    }
+
+    /// Recomputes the change from the compared baseline.
+    fn update_diff(&mut self) {
+        self.diff = self.baseline().map(|baseline| {
+            SnapshotDiff::between(
+                &baseline.tree,
+                &self.snapshot.process_tree,
+                self.memory_metric,
+            )
+        });
+        self.needs_relayout = true;
+    }
+
+    /// The change a compare tile shows: the process alone at the top level,
+    /// the process with its descendants once zoomed into a parent.
+    fn compare_delta(&self, delta: &ProcessDelta) -> i128 {
+        if self.zoom_stack.is_empty() {
+            delta.own()
+        } else {
+            delta.subtree()
+        }
+    }
+
+    /// Formatter for tile values under the active sizing.
+    pub fn value_format(&self) -> fn(u64) -> String {
+        if self.sizing_by_io() {
+            format_rate
+        } else {
+            format_bytes
+        }
+    }
+
+    /// What tile area represents, for the header.
+    pub fn size_label(&self) -> String {
+        if let Some(baseline) = self.baseline().filter(|_| self.comparing()) {
+            return format!(
+                "{} change since {}",
+                self.memory_metric.label(),
+                baseline.label
+            );
+        }
+        if self.sizing_by_io() {
+            return SizeMode::IoRate.label().to_string();
+        }
+        let metric = self.memory_metric;
+        let fallbacks = self
+            .snapshot
+            .process_tree
+            .processes
+            .values()
+            .filter(|p| p.falls_back_to_rss(metric))
+            .count();
+        if fallbacks > 0 {
+            format!("{} (RSS for {fallbacks})", metric.label())
+        } else {
+            metric.label().to_string()
+        }
+    }
+
+    /// Denominator for the size heatmap under the active metric.
+    pub fn heat_total(&self) -> u64 {
+        if self.sizing_by_io() {
+            return self
+                .snapshot
+                .process_tree
+                .processes
+                .values()
+                .map(|p| p.size_for(SizeMode::IoRate, self.memory_metric))
+                .sum();
+        }
+        if self.view_mode == ViewMode::Cgroups {
+            return self.snapshot.memory_total;
+        }
+        if let Some(map) = &self.memory_map {
+            return map.total(self.memory_metric);
+        }
+        match self.memory_metric {
+            MemoryMetric::Rss | MemoryMetric::Pss | MemoryMetric::Uss => self.snapshot.memory_total,
+            MemoryMetric::Swap => self.snapshot.swap_total,
+            MemoryMetric::Virtual => self.snapshot.memory_total + self.snapshot.swap_total,
+        }
+    }
+
+    pub fn show_help(&self) -> bool {
+        self.input_mode == InputMode::Help
+    }
+
+    pub fn help_entries(&self) -> Vec<(String, &'static str)> {
+        let mut entries = self.keybinds.help_entries();
+        if self.replay.is_some() {
+            entries.splice(0..0, self.keybinds.replay_help_entries());
+        }
+        entries
+    }
+
+    /// Why a control action may not run: read-only mode, or a protected
+    /// target. `kill_process` checks every process again before sending.
+    fn control_refusal(&mut self, action: &Action) -> Option<String> {
+        let priority = matches!(
+            action,
+            Action::AdjustNice(..)
+                | Action::OpenNiceEntry(_)
+                | Action::SetNice(..)
+                | Action::CycleIoPriority(_)
+                | Action::AdjustNiceMarked(_)
+                | Action::OpenMarkedNiceEntry
+                | Action::SetNiceMarked(_)
+        );
+        let what = if priority {
+            "priority changes"
+        } else {
+            "signals"
+        };
+        if self.replay.is_some() {
+            return Some(format!("Replaying a recording: {what} are disabled"));
+        }
+        if self.safety.read_only {
+            return Some(format!("Read-only mode: {what} are disabled"));
+        }
+        let pid = action.target_pid().or_else(|| {
+            // Marked processes are checked one by one as they are signalled
+            self.signal_confirm
+                .as_ref()
+                .filter(|c| !c.marked)
+                .map(|c| c.pid)
+        })?;
+        let sys = self.collector.system_for_pids(&[pid]);
+        let reason = self.safety.refusal_in(sys, pid)?;
+        let verb = if priority { "change" } else { "signal" };
+        Some(format!("Refused to {verb} PID {pid}: {reason}"))
+    }
+
+    /// Sends `signal`, first asking for confirmation when the config
+    /// requires it for this signal.
+    fn request_signal(&mut self, pid: u32, signal: sysinfo::Signal, tree: bool) {
+        if pid == 0 {
+            return;
+        }
+        if stops(signal) && self.holds_treetop(pid, tree) {
+            // Nothing would be left running to send the SIGCONT
+            self.status_message = Some((stop_refusal(pid), Instant::now()));
+            return;
+        }
+        if !self.confirm_signals.contains(&signal) {
+            self.deliver_signal(pid, signal, tree);
+            return;
+        }
+        let Some(process) = self.snapshot.process_tree.processes.get(&pid) else {
+            self.set_kill_status(KillResult::NotFound(pid));
+            return;
+        };
+        let (name, command) = (process.name.clone(), process.command.clone());
+        let user = process.user_id.clone().map(|id| self.user_name(&id));
+        let targets = self.signal_targets(pid, tree);
+        let processes = &self.snapshot.process_tree.processes;
+        let memory = targets
+            .iter()
+            .filter_map(|t| processes.get(&t.pid))
+            .map(|p| p.memory_for(self.memory_metric))
+            .sum();
+        self.signal_confirm = Some(SignalConfirm {
+            pid,
+            marked: false,
+            signal,
+            tree,
+            name,
+            user,
+            command,
+            memory,
+            descendants: self
+                .snapshot
+                .process_tree
+                .subtree(pid)
+                .len()
+                .saturating_sub(1),
+            targets,
+            confirm_key: key_label(self.keybinds.confirm),
+        });
+        self.input_mode = InputMode::ConfirmSignal;
+    }
+
+    fn deliver_signal(&mut self, pid: u32, signal: sysinfo::Signal, tree: bool) {
+        let targets = self.signal_targets(pid, tree);
+        self.send_signal(pid, &targets, signal, tree);
+    }
+
+    /// The processes shown for `pid` in the current snapshot, never
+    /// whichever process may have been handed a PID since: `pid` alone, or
+    /// its whole subtree parents first when `tree` is set.
+    fn signal_targets(&self, pid: u32, tree: bool) -> Vec<ProcessInfo> {
+        let process_tree = &self.snapshot.process_tree;
+        let pids = if tree {
+            process_tree.subtree(pid)
+        } else {
+            vec![pid]
+        };
+        pids.iter()
+            .filter_map(|p| process_tree.processes.get(p))
+            .cloned()
+            .collect()
+    }
+
+    /// Signals exactly `targets`, aimed at `pid`, and reports the outcome:
+    /// per PID for a tree.
+    fn send_signal(
+        &mut self,
+        pid: u32,
+        targets: &[ProcessInfo],
+        signal: sysinfo::Signal,
+        tree: bool,
+    ) {
+        if targets.is_empty() {
+            self.set_kill_status(KillResult::NotFound(pid));
+            return;
+        }
+        let pids: Vec<u32> = targets.iter().map(|t| t.pid).collect();
+        let identities: Vec<ProcessIdentity> = targets.iter().map(ProcessInfo::identity).collect();
+        let sys = self.collector.system_for_pids(&pids);
+        if tree {
+            let results = kill_tree(sys, targets, signal, &self.safety);
+            self.track_frozen(signal, &identities, &results);
+            self.set_control_status(tree_kill_summary(pid, signal_name(signal), &results));
+        } else {
+            let result = kill_process(sys, &targets[0], signal, &self.safety);
+            self.track_frozen(signal, &identities, std::slice::from_ref(&result));
+            self.set_kill_status(result);
+        }
+    }
+
+    /// Name of the user `user_id` stands for, or the id itself when no
+    /// user has it.
+    fn user_name(&mut self, user_id: &str) -> String {
+        let users = self
+            .users
+            .get_or_insert_with(sysinfo::Users::new_with_refreshed_list);
+        users
+            .iter()
+            .find(|u| format!("{:?}", u.id()) == user_id)
+            .map_or_else(|| user_id.to_string(), |u| u.name().to_string())
+    }
+
+    fn set_kill_status(&mut self, result: KillResult) {
+        let msg = match result {
+            KillResult::Success(pid, signal) => format!("Sent {signal} to PID {pid}"),
+            KillResult::Failed {
+                pid,
+                signal,
+                reason,
+            } => format!("Failed to send {signal} to PID {pid}: {reason}"),
+            KillResult::NotFound(pid) => format!("Process {pid} not found"),
+            KillResult::PidReused(pid) => {
+                format!("PID {pid} now belongs to another process; nothing sent")
+            }
+            KillResult::Refused { pid, reason } => {
+                format!("Refused to signal PID {pid}: {reason}")
+            }
+        };
+        self.set_control_status(msg);
+    }
+
+    /// Shows the outcome of a control action, led by any audit log write
+    /// failure so it is never missed.
+    fn set_control_status(&mut self, msg: String) {
+        let msg = match self.safety.audit().and_then(AuditLog::take_error) {
+            Some(err) => format!("Audit log not written ({err}); {msg}"),
+            None => msg,
+        };
+        self.status_message = Some((msg, Instant::now()));
+    }
+
+    fn process_identity(&self, pid: u32) -> Option<ProcessIdentity> {
+        self.snapshot
+            .process_tree
+            .processes
+            .get(&pid)
+            .map(ProcessInfo::identity)
+    }
+
+    /// Marked processes as the snapshot shows them, in PID order.
+    fn marked_targets(&self) -> Vec<ProcessInfo> {
+        let processes = &self.snapshot.process_tree.processes;
+        let mut targets: Vec<ProcessInfo> = self
+            .marked
+            .iter()
+            .filter_map(|m| processes.get(&m.pid).filter(|p| p.identity() == *m))
+            .cloned()
+            .collect();
+        targets.sort_by_key(|t| t.pid);
+        targets
+    }
+
+    /// Marks every process matching the filter, or unmarks them when they
+    /// all are marked already.
+    fn mark_matching(&mut self) {
+        if self.filter_text.is_empty() {
+            self.status_message = Some((
+                "Filter first to mark the matching processes".to_string(),
+                Instant::now(),
+            ));
+            return;
+        }
+        let matching: Vec<ProcessIdentity> = self
+            .snapshot
+            .process_tree
+            .processes
+            .values()
+            .filter(|p| self.matches_filter(p))
+            .map(ProcessInfo::identity)
+            .collect();
+        let msg = if matching.iter().all(|m| self.marked.contains(m)) {
+            for m in &matching {
+                self.marked.remove(m);
+            }
+            format!(
+                "Unmarked {} processes matching \"{}\"",
+                matching.len(),
+                self.filter_text
+            )
+        } else {
+            self.marked.extend(matching.iter().copied());
+            format!(
+                "Marked {} processes matching \"{}\"",
+                matching.len(),
+                self.filter_text
+            )
+        };
+        self.status_message = Some((msg, Instant::now()));
+    }
+
+    /// Count, memory and CPU of the marked processes.
+    pub fn marked_totals(&self) -> Option<(usize, u64, f32)> {
+        if self.marked.is_empty() {
+            return None;
+        }
+        let processes = &self.snapshot.process_tree.processes;
+        let (memory, cpu) = self
+            .marked
+            .iter()
+            .filter_map(|m| processes.get(&m.pid))
+            .fold((0, 0.0), |(memory, cpu), p| {
+                (
+                    memory + p.memory_for(self.memory_metric),
+                    cpu + p.cpu_percent,
+                )
+            });
+        Some((self.marked.len(), memory, cpu))
+    }
+
+    /// Asks to confirm sending `signal` to every marked process. A batch
+    /// always asks, whatever `confirm_signals` says, since the keys that
+    /// send it act on the single selected process when nothing is marked.
+    fn request_signal_marked(&mut self, signal: sysinfo::Signal) {
+        let targets = self.marked_targets();
+        if self.refuse_stopping_treetop(&targets, signal) {
+            return;
+        }
+        let user_ids: Vec<String> = targets.iter().filter_map(|p| p.user_id.clone()).collect();
+        let command = targets
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let memory = targets
+            .iter()
+            .map(|p| p.memory_for(self.memory_metric))
+            .sum();
+        let mut users: Vec<String> = user_ids.iter().map(|id| self.user_name(id)).collect();
+        users.sort();
+        users.dedup();
+        self.signal_confirm = Some(SignalConfirm {
+            pid: targets.first().map_or(0, |t| t.pid),
+            marked: true,
+            signal,
+            tree: false,
+            name: format!("{} marked processes", targets.len()),
+            user: (!users.is_empty()).then(|| users.join(", ")),
+            command,
+            memory,
+            descendants: 0,
+            targets,
+            confirm_key: key_label(self.keybinds.confirm),
+        });
+        self.input_mode = InputMode::ConfirmSignal;
+    }
+
+    /// Refuses, with a status message, a stop signal whose `targets`
+    /// include treetop itself.
+    fn refuse_stopping_treetop(
+        &mut self,
+        targets: &[ProcessInfo],
+        signal: sysinfo::Signal,
+    ) -> bool {
+        let own_pid = std::process::id();
+        if !stops(signal) || !targets.iter().any(|t| t.pid == own_pid) {
+            return false;
+        }
+        self.status_message = Some((stop_refusal(own_pid), Instant::now()));
+        true
+    }
+
+    /// Signals the marked processes listed when the batch was confirmed.
+    fn signal_marked(&mut self, targets: &[ProcessInfo], signal: sysinfo::Signal) {
+        if self.refuse_stopping_treetop(targets, signal) {
+            return;
+        }
+        let pids: Vec<u32> = targets.iter().map(|t| t.pid).collect();
+        let sys = self.collector.system_for_pids(&pids);
+        let results: Vec<KillResult> = targets
+            .iter()
+            .map(|target| kill_process(sys, target, signal, &self.safety))
+            .collect();
+        let identities: Vec<ProcessIdentity> = targets.iter().map(ProcessInfo::identity).collect();
+        self.track_frozen(signal, &identities, &results);
+        self.set_control_status(signal_summary(
+            "marked processes",
+            "Marked",
+            signal_name(signal),
+            &results,
+        ));
+    }
+
+    /// Renices every marked process to `nice(current)`; `change` describes
+    /// it for the status bar, e.g. `nice +1`.
+    fn renice_marked(&mut self, change: &str, nice: impl Fn(i32) -> i32) {
+        let targets = self.marked_targets();
+        let pids: Vec<u32> = targets.iter().map(|t| t.pid).collect();
+        let sys = self.collector.system_for_pids(&pids);
+        let mut applied = 0;
+        let mut problems = Vec::new();
+        for target in &targets {
+            let Some(current) = platform::process_nice(target.pid) else {
+                problems.push(format!("{} (nice not readable)", target.pid));
+                continue;
+            };
+            match renice(sys, target, nice(current), &self.safety) {
+                PriorityResult::Success { .. } => applied += 1,
+                PriorityResult::Failed { pid, reason, .. }
+                | PriorityResult::Refused { pid, reason } => {
+                    problems.push(format!("{pid} ({reason})"))
+                }
+                PriorityResult::NotFound(pid) => problems.push(format!("{pid} (gone)")),
+                PriorityResult::PidReused(pid) => problems.push(format!("{pid} (PID reused)")),
+            }
+        }
+        self.detail_extras = None;
+        let msg = if problems.is_empty() {
+            format!("Applied {change} to {applied} marked processes")
+        } else {
+            format!(
+                "Applied {change} to {applied} of {} marked processes; not changed: {}",
+                targets.len(),
+                problems.join(", ")
+            )
+        };
+        self.set_control_status(msg);
+    }
+
+    /// Writes the marked processes, or the selected one when nothing is
+    /// marked, to a JSON file in the working directory.
+    fn export_marked(&mut self) {
+        let records: Vec<ExportProcess> = if self.marked.is_empty() {
+            self.selected_process()
+                .into_iter()
+                .map(ExportProcess::from)
+                .collect()
+        } else {
+            self.marked_targets()
+                .iter()
+                .map(ExportProcess::from)
+                .collect()
+        };
+        if records.is_empty() {
+            self.status_message = Some(("Nothing to export".to_string(), Instant::now()));
+            return;
+        }
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let path = PathBuf::from(format!("treetop-export-{now}.json"));
+        let msg = match write_json(&path, &records) {
+            Ok(()) => format!("Exported {} processes to {}", records.len(), path.display()),
+            Err(err) => format!("Export to {} failed: {err}", path.display()),
+        };
+        self.status_message = Some((msg, Instant::now()));
+    }
+
+    /// Writes the treemap as it is laid out now to an SVG image and an HTML
+    /// page with tooltips, both in the working directory.
+    fn export_image(&mut self) {
+        if self.layout_rects.is_empty() {
+            self.status_message = Some(("Nothing to export".to_string(), Instant::now()));
+            return;
+        }
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let svg_path = PathBuf::from(format!("treetop-treemap-{now}.svg"));
+        let html_path = svg_path.with_extension("html");
+        let (width, height) = (svg::DEFAULT_WIDTH, svg::DEFAULT_HEIGHT);
+        let written = std::fs::write(&svg_path, svg::svg(self, width, height))
+            .and_then(|()| std::fs::write(&html_path, svg::html(self, width, height)));
+        let msg = match written {
+            Ok(()) => format!(
+                "Exported treemap to {} and {}",
+                svg_path.display(),
+                html_path.display()
+            ),
+            Err(err) => format!("Treemap export failed: {err}"),
+        };
+        self.status_message = Some((msg, Instant::now()));
+    }
+
+    /// Starts appending every refreshed snapshot to `path`, beginning with
+    /// the current one.
+    pub fn start_recording(&mut self, path: PathBuf) -> std::io::Result<()> {
+        self.recorder = Some(Recorder::create(path.clone())?);
+        self.status_message = Some((
+            format!("Recording snapshots to {}", path.display()),
+            Instant::now(),
+        ));
+        self.record_snapshot();
+        Ok(())
+    }
+
+    /// Data source shown at the start of the header.
+    pub fn header_badge(&self) -> &'static str {
+        if self.replay.is_some() {
+            "replay"
+        } else if self.recorder.is_some() {
+            "\u{25cf} rec"
+        } else {
+            "live"
+        }
+    }
+
+    /// Writes the current snapshot to the active recording, stopping it on
+    /// the first write error.
+    fn record_snapshot(&mut self) {
+        let Some(recorder) = &mut self.recorder else {
+            return;
+        };
+        if let Err(err) = recorder.record(&self.snapshot, now_ms()) {
+            self.status_message = Some((
+                format!(
+                    "Writing {} failed, recording stopped: {err}",
+                    recorder.path().display()
+                ),
+                Instant::now(),
+            ));
+            self.recorder = None;
+        }
+    }
+
+    /// Freezes `pid` (and its subtree when `tree`), or thaws it when it is
+    /// already stopped.
+    fn toggle_freeze(&mut self, pid: u32, tree: bool) {
+        let stopped = self.is_frozen(pid)
+            || self
+                .snapshot
+                .process_tree
+                .processes
+                .get(&pid)
+                .is_some_and(|p| p.status == ProcessState::Stopped);
+        let signal = if stopped {
+            sysinfo::Signal::Continue
+        } else {
+            sysinfo::Signal::Stop
+        };
+        self.request_signal(pid, signal, tree);
+    }
+
+    /// Whether treetop is `pid`, or among its descendants when `tree` is
+    /// set.
+    fn holds_treetop(&self, pid: u32, tree: bool) -> bool {
+        let own_pid = std::process::id();
+        if tree {
+            self.snapshot.process_tree.subtree(pid).contains(&own_pid)
+        } else {
+            pid == own_pid
+        }
+    }
+
+    /// Resumes the process the frozen list holds for `pid`: the one that was
+    /// stopped, never another that has since been handed the PID.
+    fn thaw_frozen(&mut self, pid: u32) {
+        let Some(identity) = self
+            .frozen
+            .iter()
+            .find(|f| f.identity.pid == pid)
+            .map(|f| f.identity)
+        else {
+            return;
+        };
+        match self.snapshot.process_tree.processes.get(&pid) {
+            Some(process) if process.identity() == identity => {
+                let target = process.clone();
+                self.send_signal(pid, &[target], sysinfo::Signal::Continue, false);
+            }
+            found => {
+                // No longer ours to thaw
+                self.frozen.retain(|f| f.identity != identity);
+                if let Some(list) = &mut self.frozen_list {
+                    list.cursor = list.cursor.min(self.frozen.len().saturating_sub(1));
+                }
+                self.set_kill_status(if found.is_some() {
+                    KillResult::PidReused(pid)
+                } else {
+                    KillResult::NotFound(pid)
+                });
+            }
+        }
+    }
+
+    fn is_frozen(&self, pid: u32) -> bool {
+        self.frozen.iter().any(|f| f.identity.pid == pid)
+    }
+
+    /// Keeps the frozen list in step with SIGSTOP and SIGCONT delivered to
+    /// `targets`, whichever way they were sent.
+    fn track_frozen(
+        &mut self,
+        signal: sysinfo::Signal,
+        targets: &[ProcessIdentity],
+        results: &[KillResult],
+    ) {
+        for (target, result) in targets.iter().zip(results) {
+            let KillResult::Success(..) = result else {
+                continue;
+            };
+            match signal {
+                sysinfo::Signal::Stop if !self.is_frozen(target.pid) => {
+                    let name = self
+                        .snapshot
+                        .process_tree
+                        .processes
+                        .get(&target.pid)
+                        .map(|p| p.name.clone())
+                        .unwrap_or_default();
+                    self.frozen.push(FrozenProcess {
+                        identity: *target,
+                        name,
+                    });
+                }
+                sysinfo::Signal::Continue => self.frozen.retain(|f| f.identity.pid != target.pid),
+                _ => {}
+            }
+        }
+        if let Some(list) = &mut self.frozen_list {
+            list.cursor = list.cursor.min(self.frozen.len().saturating_sub(1));
+        }
+    }
+
+    /// Sends SIGCONT to every process in the frozen list. When quitting,
+    /// quits once nothing is left frozen.
+    fn thaw_all(&mut self) {
+        let targets: Vec<ProcessIdentity> = self.frozen.iter().map(|f| f.identity).collect();
+        if targets.is_empty() {
+            self.status_message = Some(("Nothing is frozen".to_string(), Instant::now()));
+            return;
+        }
+        let pids: Vec<u32> = targets.iter().map(|t| t.pid).collect();
+        let processes = &self.snapshot.process_tree.processes;
+        let sys = self.collector.system_for_pids(&pids);
+        let results: Vec<KillResult> = targets
+            .iter()
+            .map(|target| match processes.get(&target.pid) {
+                None => KillResult::NotFound(target.pid),
+                Some(p) if p.identity() != *target => KillResult::PidReused(target.pid),
+                Some(p) => kill_process(sys, p, sysinfo::Signal::Continue, &self.safety),
+            })
+            .collect();
+        self.track_frozen(sysinfo::Signal::Continue, &targets, &results);
+        // A process that is gone, or whose PID was reused, is no longer ours
+        // to thaw
+        self.frozen.retain(|f| {
+            !results.iter().any(|r| {
+                matches!(r, KillResult::NotFound(pid) | KillResult::PidReused(pid)
+                    if *pid == f.identity.pid)
+            })
+        });
+
+        let thawed = results
+            .iter()
+            .filter(|r| matches!(r, KillResult::Success(..)))
+            .count();
+        let msg = if self.frozen.is_empty() {
+            format!("Sent SIGCONT to {thawed} frozen processes")
+        } else {
+            let failures: Vec<String> = results
+                .iter()
+                .filter_map(|r| match r {
+                    KillResult::Failed { pid, reason, .. }
+                    | KillResult::Refused { pid, reason } => Some(format!("{pid} ({reason})")),
+                    _ => None,
+                })
+                .collect();
+            format!("Still frozen: {}", failures.join(", "))
+        };
+        self.set_control_status(msg);
+
+        let quitting = self.frozen_list.as_ref().is_some_and(|l| l.quitting);
+        if self.frozen.is_empty() {
+            self.frozen_list = None;
+            self.input_mode = InputMode::Normal;
+            if quitting {
+                self.running = false;
+            }
+        }
+    }
+
+    fn open_nice_entry(&mut self, pid: u32) {
+        let Some(name) = self
+            .snapshot
+            .process_tree
+            .processes
+            .get(&pid)
+            .map(|p| p.name.clone())
+        else {
+            self.set_kill_status(KillResult::NotFound(pid));
+            return;
+        };
+        self.nice_entry = Some(NiceEntry {
+            pid,
+            name,
+            marked: 0,
+            current: platform::process_nice(pid),
+            text: String::new(),
+        });
+        self.input_mode = InputMode::NiceEntry;
+    }
+
+    fn close_nice_entry(&mut self) {
+        self.nice_entry = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    fn renice_process(&mut self, pid: u32, nice: i32) {
+        self.apply_priority(pid, |sys, target, safety| renice(sys, target, nice, safety));
+    }
+
+    /// Runs a priority change against the process shown for `pid` in the
+    /// current snapshot and reports the outcome.
+    fn apply_priority(
+        &mut self,
+        pid: u32,
+        change: impl FnOnce(&sysinfo::System, &ProcessInfo, &SafetyPolicy) -> PriorityResult,
+    ) {
+        let Some(target) = self.snapshot.process_tree.processes.get(&pid) else {
+            self.set_kill_status(KillResult::NotFound(pid));
+            return;
+        };
+        let result = change(self.collector.system_for_pids(&[pid]), target, &self.safety);
+        // Show the new values in the detail panel
+        self.detail_extras = None;
+        let msg = match result {
+            PriorityResult::Success { pid, setting } => format!("Applied {setting} to PID {pid}"),
+            PriorityResult::Failed {
+                pid,
+                setting,
+                reason,
+            } => format!("Failed to apply {setting} to PID {pid}: {reason}"),
+            PriorityResult::NotFound(pid) => format!("Process {pid} not found"),
+            PriorityResult::PidReused(pid) => {
+                format!("PID {pid} now belongs to another process; nothing changed")
+            }
+            PriorityResult::Refused { pid, reason } => {
+                format!("Refused to change PID {pid}: {reason}")
+            }
+        };
+        self.set_control_status(msg);
+    }
+
+    fn toggle_audit_log(&mut self) {
+        if self.audit_log.take().is_some() {
+            self.input_mode = InputMode::Normal;
+            return;
+        }
+        let Some(log) = self.safety.audit() else {
+            self.status_message = Some(("Audit log is disabled".to_string(), Instant::now()));
+            return;
+        };
+        match log.read_recent(AUDIT_LOG_ENTRIES) {
+            Ok(entries) => {
+                self.audit_log = Some(AuditLogView {
+                    path: log.path().to_path_buf(),
+                    entries,
+                    scroll: 0,
+                });
+                self.input_mode = InputMode::AuditLog;
+            }
+            Err(err) => {
+                self.status_message = Some((
+                    format!("Could not read {}: {err}", log.path().display()),
+                    Instant::now(),
+                ));
+            }
+        }
+    }
+
+    pub fn on_resize(&mut self) {
+        self.needs_relayout = true;
+    }
+
+    pub fn zoom_pid(&self) -> Option<u32> {
+        self.zoom_stack.last().copied()
+    }
+
+    pub fn is_zoomed(&self) -> bool {
+        match self.view_mode {
+            ViewMode::Cgroups => !self.cgroup_zoom.is_empty(),
+            ViewMode::Processes => !self.zoom_stack.is_empty() || self.zoom_group.is_some(),
+        }
+    }
+
+    fn zoom_in(&mut self) {
+        if self.view_mode == ViewMode::Cgroups {
+            if let Some(node) = self.selected_cgroup()
+                && node.has_children
+            {
+                self.cgroup_zoom.push(node.path.clone());
+                self.reload_cgroups();
+                self.reset_selection();
+                self.needs_relayout = true;
+            }
+            return;
+        }
+        if let Some(group) = self.selected_group() {
+            self.zoom_group = Some(group.clone());
+            self.reset_selection();
+            self.needs_relayout = true;
+            return;
+        }
+        let pid = match self.selected_pid() {
+            Some(pid) if pid != 0 => pid,
+            _ => return,
+        };
+        let Some(process) = self.snapshot.process_tree.processes.get(&pid) else {
+            return;
+        };
+        let has_children = match self.diff.as_ref().filter(|_| self.comparing()) {
+            Some(diff) => !diff.children(pid).is_empty(),
+            None => !process.children.is_empty(),
+        };
+        // Leaves open their address space instead of a child list
+        if !has_children {
+            if self.replay.is_some() {
+                self.status_message = Some((
+                    "Memory maps are not part of recordings".to_string(),
+                    Instant::now(),
+                ));
+                return;
+            }
+            match platform::process_memory_map(pid) {
+                Some(map) => self.memory_map = Some(map),
+                None => {
+                    self.status_message = Some((
+                        format!("Memory map of PID {pid} is not readable"),
+                        Instant::now(),
+                    ));
+                    return;
+                }
+            }
+        }
+        self.zoom_stack.push(pid);
+        self.reset_selection();
+        self.needs_relayout = true;
+    }
+
+    fn zoom_out(&mut self) {
+        if self.view_mode == ViewMode::Cgroups {
+            if self.cgroup_zoom.pop().is_some() {
+                self.reload_cgroups();
+                self.reset_selection();
+                self.needs_relayout = true;
+            }
+            return;
+        }
+        self.memory_map = None;
+        if self.zoom_stack.pop().is_some() || self.zoom_group.take().is_some() {
+            self.reset_selection();
+            self.needs_relayout = true;
+        }
+    }
+
+    pub fn zoom_breadcrumbs(&self) -> Vec<(u32, String)> {
+        if self.view_mode == ViewMode::Cgroups {
+            return self
+                .cgroup_zoom
+                .iter()
+                .map(|path| (0, path.rsplit('/').next().unwrap_or(path).to_string()))
+                .collect();
+        }
+        let group_crumb = self.zoom_group.as_ref().map(|g| (0, g.label.clone()));
+        let mut crumbs: Vec<(u32, String)> = group_crumb
+            .into_iter()
+            .chain(self.zoom_stack.iter().filter_map(|&pid| {
+                self.snapshot
+                    .process_tree
+                    .processes
+                    .get(&pid)
+                    .map(|p| (pid, p.name.clone()))
+            }))
+            .collect();
+        if let Some(map) = &self.memory_map {
+            crumbs.push((map.pid, "memory map".to_string()));
+        }
+        crumbs
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.animation_frame > 0 && self.animation_frame <= self.anim_frames
+    }
+
+    pub fn tick_animation(&mut self) {
+        if self.is_animating() {
+            self.animation_frame += 1;
+            if self.animation_frame > self.anim_frames {
+                self.animation_frame = 0;
+                self.prev_layout_rects.clear();
+            }
+        }
+    }
+
+    pub fn display_rects(&self) -> Vec<TreemapRect> {
+        if !self.is_animating() || self.prev_layout_rects.is_empty() {
+            return self.layout_rects.clone();
+        }
+
+        let t = self.animation_frame as f64 / self.anim_frames as f64;
+
+        self.layout_rects
+            .iter()
+            .map(|new_rect| {
+                // Find matching old rect by pid
+                let old = self
+                    .prev_layout_rects
+                    .iter()
+                    .find(|old| old.pid == new_rect.pid);
 
                 match old {
                     Some(old_rect) => TreemapRect {
@@ -782,9 +3427,10 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::system::process::{ProcessInfo, ProcessState, ProcessTree};
+    use crate::system::process::{IoRate, MemoryDetail, ProcessInfo, ProcessState, ProcessTree};
     use crate::system::snapshot::SystemSnapshot;
     use std::collections::HashMap;
+    use std::time::Duration;
 
     fn make_test_process(pid: u32, name: &str, memory: u64, cpu: f32) -> ProcessInfo {
         ProcessInfo {
@@ -801,6 +3447,13 @@ mod tests {
             group_name: None,
             priority: None,
             io_stats: None,
+            memory_detail: MemoryDetail::default(),
+            container: None,
+            io_rate: None,
+            fd_count: None,
+            start_time: None,
+            start_ticks: None,
+            cpu_time: None,
         }
     }
 
@@ -826,6 +3479,7 @@ mod tests {
             snapshot,
             layout_rects: Vec::new(),
             selected_index: 0,
+            selected: None,
             input_mode: InputMode::Normal,
             filter_text: String::new(),
             show_detail_panel: false,
@@ -846,6 +3500,8 @@ mod tests {
             min_rect_width: 4,
             min_rect_height: 2,
             zoom_stack: Vec::new(),
+            memory_map: None,
+            zoom_group: None,
             history: HistoryStore::new(20),
             cpu_history: VecDeque::new(),
             cpu_history_capacity: 20,
@@ -862,6 +3518,37 @@ mod tests {
             max_visible_procs: 0,
             needs_relayout: true,
             sort_mode,
+            memory_metric: MemoryMetric::Rss,
+            size_mode: SizeMode::Memory,
+            group_mode: GroupMode::Process,
+            container_groups: Vec::new(),
+            view_mode: ViewMode::Processes,
+            cgroup_zoom: Vec::new(),
+            cgroup_nodes: Vec::new(),
+            cgroup_root: None,
+            port_owners: HashSet::new(),
+            port_scan: None,
+            detail_extras: None,
+            open_files: None,
+            audit_log: None,
+            signal_picker: None,
+            last_signal: sysinfo::Signal::Term,
+            signal_confirm: None,
+            users: None,
+            nice_entry: None,
+            frozen: Vec::new(),
+            frozen_list: None,
+            marked: HashSet::new(),
+            confirm_signals: vec![sysinfo::Signal::Kill],
+            safety: SafetyPolicy::default(),
+            recorder: None,
+            record_path: None,
+            replay: None,
+            replay_bar_area: None,
+            compare: CompareMode::Off,
+            startup_baseline: None,
+            pinned_baseline: None,
+            diff: None,
             keybinds: ResolvedKeybinds::from_config(&crate::config::KeybindsConfig::default()),
         };
         app.compute_layout(100, 50);
@@ -869,54 +3556,302 @@ mod tests {
     }
 
     #[test]
-    fn sort_mode_cycles_through_all_variants() {
-        let mode = SortMode::Memory;
-        assert_eq!(mode.next(), SortMode::Cpu);
-        assert_eq!(mode.next().next(), SortMode::Name);
-        assert_eq!(mode.next().next().next(), SortMode::Memory);
+    fn sort_mode_cycles_through_all_variants() {
+        let mode = SortMode::Memory;
+        assert_eq!(mode.next(), SortMode::Cpu);
+        assert_eq!(mode.next().next(), SortMode::CpuTime);
+        assert_eq!(mode.next().next().next(), SortMode::Age);
+        assert_eq!(mode.next().next().next().next(), SortMode::Name);
+        assert_eq!(mode.next().next().next().next().next(), SortMode::Memory);
+    }
+
+    #[test]
+    fn compute_layout_cpu_sort_orders_by_cpu_descending() {
+        // Process with less memory but higher CPU should come first in CPU sort
+        let procs = vec![
+            make_test_process(1, "low_cpu", 500_000_000, 5.0),
+            make_test_process(2, "high_cpu", 100_000_000, 90.0),
+            make_test_process(3, "mid_cpu", 300_000_000, 50.0),
+        ];
+        let app = make_test_app_with_processes(procs, SortMode::Cpu);
+
+        assert!(!app.layout_rects.is_empty());
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["high_cpu", "mid_cpu", "low_cpu"]);
+    }
+
+    #[test]
+    fn compute_layout_name_sort_orders_alphabetically() {
+        let procs = vec![
+            make_test_process(1, "Zebra", 100_000, 1.0),
+            make_test_process(2, "alpha", 200_000, 2.0),
+            make_test_process(3, "Beta", 300_000, 3.0),
+        ];
+        let app = make_test_app_with_processes(procs, SortMode::Name);
+
+        assert!(!app.layout_rects.is_empty());
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["alpha", "Beta", "Zebra"]);
+    }
+
+    #[test]
+    fn compute_layout_sorts_by_cpu_time_and_age() {
+        let timed = |pid, name, secs, start| {
+            let mut p = make_test_process(pid, name, 100_000 * u64::from(pid), 1.0);
+            p.cpu_time = Some(CpuTimes {
+                user: Duration::from_secs(secs),
+                ..CpuTimes::default()
+            });
+            p.start_time = start;
+            p
+        };
+        let procs = vec![
+            timed(1, "busy_young", 600, Some(1_700_000_900)),
+            timed(2, "unknown", 5, None),
+            timed(3, "idle_old", 30, Some(1_700_000_000)),
+        ];
+        let mut app = make_test_app_with_processes(procs, SortMode::CpuTime);
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["busy_young", "idle_old", "unknown"]);
+
+        app.dispatch(Action::CycleSortMode);
+        assert_eq!(app.sort_mode, SortMode::Age);
+        app.compute_layout(100, 50);
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["idle_old", "busy_young", "unknown"]);
+    }
+
+    #[test]
+    fn dispatch_cycle_sort_advances_mode() {
+        let procs = vec![make_test_process(1, "test", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+
+        assert_eq!(app.sort_mode, SortMode::Memory);
+        app.dispatch(Action::CycleSortMode);
+        assert_eq!(app.sort_mode, SortMode::Cpu);
+        app.dispatch(Action::CycleSortMode);
+        assert_eq!(app.sort_mode, SortMode::CpuTime);
+        app.dispatch(Action::CycleSortMode);
+        assert_eq!(app.sort_mode, SortMode::Age);
+        app.dispatch(Action::CycleSortMode);
+        assert_eq!(app.sort_mode, SortMode::Name);
+        app.dispatch(Action::CycleSortMode);
+        assert_eq!(app.sort_mode, SortMode::Memory);
+    }
+
+    #[test]
+    fn layout_sizes_tiles_by_memory_metric() {
+        let mut swapped = make_test_process(1, "swapped", 100_000, 1.0);
+        swapped.memory_detail.swap = Some(400_000);
+        let procs = vec![swapped, make_test_process(2, "resident", 300_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        assert_eq!(app.layout_rects[0].label, "resident");
+
+        app.memory_metric = MemoryMetric::Pss;
+        app.snapshot
+            .process_tree
+            .processes
+            .get_mut(&1)
+            .unwrap()
+            .memory_detail
+            .pss = Some(50_000);
+        // The header owns up to tiles that are RSS for lack of smaps access
+        assert_eq!(app.size_label(), "PSS (RSS for 1)");
+
+        app.memory_metric = MemoryMetric::Uss;
+        app.dispatch(Action::CycleMemoryMetric);
+        assert_eq!(app.memory_metric, MemoryMetric::Swap);
+        app.compute_layout(100, 50);
+
+        // Processes with nothing swapped out drop out of the swap view
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["swapped"]);
+        assert_eq!(app.layout_rects[0].value, 400_000);
+    }
+
+    #[test]
+    fn layout_sizes_tiles_by_io_rate() {
+        let mut busy = make_test_process(1, "busy", 100_000, 1.0);
+        busy.io_rate = Some(IoRate {
+            read_per_sec: 3_000_000,
+            write_per_sec: 1_000_000,
+        });
+        let mut quiet = make_test_process(2, "quiet", 300_000, 1.0);
+        quiet.io_rate = Some(IoRate::default());
+        let procs = vec![busy, quiet, make_test_process(3, "fresh", 200_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        assert_eq!(app.size_label(), "RSS");
+
+        app.dispatch(Action::CycleSizeMode);
+        app.compute_layout(100, 50);
+
+        // Idle processes and ones without a second sample take no area
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["busy"]);
+        assert_eq!(app.layout_rects[0].value, 4_000_000);
+        assert_eq!(app.heat_total(), 4_000_000);
+        assert_eq!(app.size_label(), "I/O rate");
+        assert_eq!((app.value_format())(2048), "2 KB/s");
+    }
+
+    #[test]
+    fn port_filter_keeps_only_port_owners() {
+        let procs = vec![
+            make_test_process(1, "nginx", 300_000, 1.0),
+            make_test_process(2, "postgres", 500_000, 1.0),
+            make_test_process(3, "port-scanner", 100_000, 1.0),
+        ];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.filter_text = "port:8080".to_string();
+        app.port_owners = HashSet::from([1]);
+        app.needs_relayout = true;
+        app.compute_layout(100, 50);
+
+        // Names containing "port" don't match a port filter
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["nginx"]);
+
+        app.dispatch(Action::ClearFilter);
+        app.compute_layout(100, 50);
+        assert_eq!(app.layout_rects.len(), 3);
+    }
+
+    #[test]
+    fn port_filter_reuses_a_recent_scan_of_the_same_port() {
+        let procs = vec![make_test_process(1, "nginx", 300_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.filter_text = "port:1".to_string();
+        app.port_owners = HashSet::from([1]);
+        app.port_scan = Some((1, Instant::now()));
+
+        app.reload_port_owners();
+        assert_eq!(app.port_owners, HashSet::from([1]));
+
+        app.filter_text.clear();
+        app.reload_port_owners();
+        assert!(app.port_owners.is_empty());
+        assert!(app.port_scan.is_none());
+    }
+
+    fn make_test_memory_map(pid: u32) -> MemoryMap {
+        crate::system::memmap::parse_smaps(
+            pid,
+            "00400000-00500000 r-xp 00000000 08:01 1 /usr/bin/test\n\
+             Size: 1024 kB\nRss: 512 kB\nPss: 512 kB\n\
+             01000000-02000000 rw-p 00000000 00:00 0 [heap]\n\
+             Size: 16384 kB\nRss: 4096 kB\nPss: 4096 kB\n",
+        )
+    }
+
+    #[test]
+    fn memory_map_view_lays_out_regions() {
+        let procs = vec![make_test_process(1, "test", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.zoom_stack.push(1);
+        app.memory_map = Some(make_test_memory_map(1));
+        app.needs_relayout = true;
+        app.compute_layout(100, 50);
+
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["[heap]", "test"]);
+        assert_eq!(app.selected_region().unwrap().label, "[heap]");
+        // Region tiles must never be treated as signal targets
+        assert_eq!(app.selected_pid(), None);
+        assert_eq!(app.detail_process().unwrap().pid, 1);
+        assert_eq!(app.heat_total(), (512 + 4096) * 1024);
+
+        app.memory_metric = MemoryMetric::Virtual;
+        app.needs_relayout = true;
+        app.compute_layout(100, 50);
+        assert_eq!(app.layout_rects[0].value, 16384 * 1024);
     }
 
     #[test]
-    fn compute_layout_cpu_sort_orders_by_cpu_descending() {
-        // Process with less memory but higher CPU should come first in CPU sort
-        let procs = vec![
-            make_test_process(1, "low_cpu", 500_000_000, 5.0),
-            make_test_process(2, "high_cpu", 100_000_000, 90.0),
-            make_test_process(3, "mid_cpu", 300_000_000, 50.0),
-        ];
-        let app = make_test_app_with_processes(procs, SortMode::Cpu);
+    fn zoom_out_closes_memory_map() {
+        let procs = vec![make_test_process(1, "test", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.zoom_stack.push(1);
+        app.memory_map = Some(make_test_memory_map(1));
+        assert_eq!(app.zoom_breadcrumbs().len(), 2);
 
-        assert!(!app.layout_rects.is_empty());
-        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
-        assert_eq!(labels, vec!["high_cpu", "mid_cpu", "low_cpu"]);
+        app.dispatch(Action::ZoomOut);
+        assert!(app.memory_map.is_none());
+        assert!(!app.is_zoomed());
+        app.compute_layout(100, 50);
+        assert_eq!(app.selected_pid(), Some(1));
+    }
+
+    fn make_containerized_process(pid: u32, name: &str, memory: u64, cgroup: &str) -> ProcessInfo {
+        let mut process = make_test_process(pid, name, memory, 1.0);
+        process.container = Some(crate::system::container::parse_cgroup_path(cgroup));
+        process
     }
 
     #[test]
-    fn compute_layout_name_sort_orders_alphabetically() {
+    fn container_grouping_folds_pods_into_one_tile() {
+        let pod = "/kubepods/burstable/pod8d4e7c2a-1b3f-4a5c-9d6e-0f1a2b3c4d5e";
         let procs = vec![
-            make_test_process(1, "Zebra", 100_000, 1.0),
-            make_test_process(2, "alpha", 200_000, 2.0),
-            make_test_process(3, "Beta", 300_000, 3.0),
+            make_containerized_process(1, "envoy", 200_000, &format!("{pod}/{}", "a".repeat(64))),
+            make_containerized_process(2, "app", 300_000, &format!("{pod}/{}", "b".repeat(64))),
+            make_containerized_process(3, "sshd", 100_000, "/system.slice/sshd.service"),
         ];
-        let app = make_test_app_with_processes(procs, SortMode::Name);
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.dispatch(Action::CycleGroupMode);
+        assert_eq!(app.group_mode, GroupMode::Container);
+        app.compute_layout(100, 50);
 
-        assert!(!app.layout_rects.is_empty());
         let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
-        assert_eq!(labels, vec!["alpha", "Beta", "Zebra"]);
+        assert_eq!(labels, vec!["pod 8d4e7c2a (burstable)", "sshd"]);
+        let group = app.selected_group().unwrap();
+        assert_eq!(group.process_count, 2);
+        assert_eq!(group.value, 500_000);
+        assert_eq!(app.selected_pid(), None);
+
+        app.dispatch(Action::ZoomIn);
+        assert!(app.is_zoomed());
+        app.compute_layout(100, 50);
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["app", "envoy"]);
+        assert_eq!(app.zoom_breadcrumbs()[0].1, "pod 8d4e7c2a (burstable)");
+
+        app.dispatch(Action::ZoomOut);
+        assert!(!app.is_zoomed());
     }
 
     #[test]
-    fn dispatch_cycle_sort_advances_mode() {
+    fn cgroup_view_zooms_through_hierarchy() {
         let procs = vec![make_test_process(1, "test", 100_000, 1.0)];
         let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.dispatch(Action::CycleView);
+        assert_eq!(
+            app.view_mode,
+            ViewMode::Processes,
+            "no cgroup root configured"
+        );
 
-        assert_eq!(app.sort_mode, SortMode::Memory);
-        app.dispatch(Action::CycleSortMode);
-        assert_eq!(app.sort_mode, SortMode::Cpu);
-        app.dispatch(Action::CycleSortMode);
-        assert_eq!(app.sort_mode, SortMode::Name);
-        app.dispatch(Action::CycleSortMode);
-        assert_eq!(app.sort_mode, SortMode::Memory);
+        app.cgroup_root =
+            Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/cgroupfs"));
+        app.dispatch(Action::CycleView);
+        assert_eq!(app.view_mode, ViewMode::Cgroups);
+        app.compute_layout(100, 50);
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["system.slice", "user.slice", "init.scope"]);
+        assert_eq!(app.selected_pid(), None);
+
+        app.dispatch(Action::ZoomIn);
+        app.compute_layout(100, 50);
+        let labels: Vec<&str> = app.layout_rects.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["postgresql.service", "nginx.service"]);
+        assert_eq!(app.zoom_breadcrumbs()[0].1, "system.slice");
+
+        // Leaf cgroups have nothing to zoom into
+        app.dispatch(Action::ZoomIn);
+        assert_eq!(app.cgroup_zoom.len(), 1);
+
+        app.dispatch(Action::ZoomOut);
+        assert!(!app.is_zoomed());
+        app.dispatch(Action::CycleView);
+        assert!(app.cgroup_nodes.is_empty());
     }
 
     #[test]
@@ -936,6 +3871,10 @@ mod tests {
         let key = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE);
         assert_eq!(app.map_key(key), Action::CycleSortMode);
 
+        // Default 'm' should map to CycleMemoryMetric
+        let key = KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert_eq!(app.map_key(key), Action::CycleMemoryMetric);
+
         // Default '?' should map to ToggleHelp
         let key = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE);
         assert_eq!(app.map_key(key), Action::ToggleHelp);
@@ -999,4 +3938,754 @@ mod tests {
         assert_eq!(app.input_mode, InputMode::Normal);
         assert!(!app.show_help());
     }
+
+    #[test]
+    fn signal_picker_remembers_last_signal_and_jumps_by_number() {
+        let procs = vec![make_test_process(4242, "worker", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        app.dispatch(Action::OpenSignalPicker(4242));
+        assert_eq!(app.input_mode, InputMode::SignalPicker);
+        let picker = app.signal_picker.as_ref().unwrap();
+        assert_eq!(picker.selected_signal(), Some(sysinfo::Signal::Term));
+
+        // Never dispatched here: SendSignal would signal a real PID
+        let enter = app.map_key(key(KeyCode::Enter));
+        assert_eq!(
+            enter,
+            Action::SendSignal(4242, sysinfo::Signal::Term),
+            "Enter sends the highlighted signal"
+        );
+        app.dispatch(Action::CloseSignalPicker);
+        assert!(app.signal_picker.is_none());
+        assert_eq!(app.input_mode, InputMode::Normal);
+
+        app.last_signal = sysinfo::Signal::Hangup;
+        app.dispatch(Action::OpenSignalPicker(4242));
+        let picker = app.signal_picker.as_ref().unwrap();
+        assert_eq!(picker.selected_signal(), Some(sysinfo::Signal::Hangup));
+
+        if cfg!(unix) {
+            for digit in ['1', '5'] {
+                let action = app.map_key(key(KeyCode::Char(digit)));
+                app.dispatch(action);
+            }
+            let picker = app.signal_picker.as_ref().unwrap();
+            assert_eq!(picker.selected_signal(), Some(sysinfo::Signal::Term));
+            app.dispatch(app.map_key(key(KeyCode::Char('9'))));
+            let picker = app.signal_picker.as_ref().unwrap();
+            assert_eq!(picker.selected_signal(), Some(sysinfo::Signal::Kill));
+        }
+
+        assert_eq!(app.map_key(key(KeyCode::Esc)), Action::CloseSignalPicker);
+    }
+
+    #[test]
+    fn signal_picker_for_vanished_process_reports_not_found() {
+        let procs = vec![make_test_process(1, "test", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.dispatch(Action::OpenSignalPicker(777));
+        assert!(app.signal_picker.is_none());
+        assert_eq!(app.input_mode, InputMode::Normal);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "Process 777 not found");
+    }
+
+    #[test]
+    fn force_kill_waits_for_confirmation() {
+        let mut parent = make_test_process(4242, "worker", 100_000, 1.0);
+        parent.children = vec![4243];
+        let mut child = make_test_process(4243, "helper", 50_000, 1.0);
+        child.ppid = 4242;
+        let mut app = make_test_app_with_processes(vec![parent, child], SortMode::Memory);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        app.dispatch(Action::ForceKill(4242));
+        assert_eq!(app.input_mode, InputMode::ConfirmSignal);
+        let confirm = app.signal_confirm.as_ref().unwrap();
+        assert_eq!(confirm.signal, sysinfo::Signal::Kill);
+        assert_eq!(confirm.descendants, 1);
+        assert!(!confirm.tree);
+
+        // Never dispatched here: ConfirmSignal would signal a real PID
+        assert_eq!(app.map_key(key(KeyCode::Char('y'))), Action::ConfirmSignal);
+        assert_eq!(app.map_key(key(KeyCode::Enter)), Action::None);
+
+        app.dispatch(app.map_key(key(KeyCode::Esc)));
+        assert!(app.signal_confirm.is_none());
+        assert_eq!(app.input_mode, InputMode::Normal);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "Cancelled SIGKILL to PID 4242");
+
+        app.confirm_signals = vec![sysinfo::Signal::Term];
+        app.dispatch(Action::KillTree(4242));
+        let confirm = app.signal_confirm.as_ref().unwrap();
+        assert!(confirm.tree);
+        assert_eq!(confirm.memory, 150_000);
+        let pids: Vec<u32> = confirm.targets.iter().map(|t| t.pid).collect();
+        assert_eq!(pids, vec![4242, 4243]);
+    }
+
+    #[test]
+    fn confirmed_tree_signal_goes_to_the_previewed_processes() {
+        // PIDs above any pid_max, so nothing real is signalled
+        let (root, child, late) = (4_000_000_000, 4_000_000_001, 4_000_000_002);
+        let mut parent = make_test_process(root, "worker", 100_000, 1.0);
+        parent.children = vec![child];
+        let mut helper = make_test_process(child, "helper", 50_000, 1.0);
+        helper.ppid = root;
+        let mut app = make_test_app_with_processes(vec![parent, helper], SortMode::Memory);
+        app.confirm_signals = vec![sysinfo::Signal::Term];
+        app.dispatch(Action::KillTree(root));
+
+        // A child forked while the dialog is open was never previewed
+        let mut forked = make_test_process(late, "forked", 10_000, 1.0);
+        forked.ppid = root;
+        let tree = &mut app.snapshot.process_tree;
+        tree.processes.insert(late, forked);
+        tree.processes.get_mut(&root).unwrap().children.push(late);
+
+        app.dispatch(Action::ConfirmSignal);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert!(msg.contains(&child.to_string()), "unexpected status: {msg}");
+        assert!(!msg.contains(&late.to_string()), "unexpected status: {msg}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn user_ids_show_as_names() {
+        let mut app = make_test_app_with_processes(Vec::new(), SortMode::Memory);
+        assert_eq!(app.user_name("Uid(0)"), "root");
+        assert_eq!(app.user_name("user"), "user");
+    }
+
+    #[test]
+    fn read_only_and_protected_processes_refuse_signals() {
+        let procs = vec![
+            make_test_process(1, "init", 100_000, 1.0),
+            make_test_process(4242, "worker", 100_000, 1.0),
+        ];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+
+        app.safety = SafetyPolicy::new(false, &["pid:1".to_string()]);
+        app.dispatch(Action::ForceKill(1));
+        assert!(app.signal_confirm.is_none());
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert!(
+            msg.starts_with("Refused to signal PID 1:"),
+            "unexpected status: {msg}"
+        );
+
+        app.safety = SafetyPolicy::new(true, &[]);
+        for action in [
+            Action::ForceKill(4242),
+            Action::OpenSignalPicker(4242),
+            Action::KillTree(4242),
+        ] {
+            app.status_message = None;
+            app.dispatch(action);
+            assert!(app.signal_confirm.is_none());
+            assert!(app.signal_picker.is_none());
+            let (msg, _) = app.status_message.as_ref().unwrap();
+            assert_eq!(msg, "Read-only mode: signals are disabled");
+        }
+    }
+
+    #[test]
+    fn freezing_treetop_or_its_ancestors_is_refused() {
+        let own_pid = std::process::id();
+        let parent_pid = 4_000_000_000;
+        let mut parent = make_test_process(parent_pid, "shell", 100_000, 1.0);
+        parent.children = vec![own_pid];
+        let mut own = make_test_process(own_pid, "treetop", 50_000, 1.0);
+        own.ppid = parent_pid;
+        let mut app = make_test_app_with_processes(vec![parent, own], SortMode::Memory);
+
+        for action in [
+            Action::ToggleFreeze(own_pid),
+            Action::ToggleFreezeTree(parent_pid),
+        ] {
+            app.status_message = None;
+            app.dispatch(action);
+            let (msg, _) = app.status_message.as_ref().unwrap();
+            assert!(msg.starts_with("Refused to stop PID"), "unexpected: {msg}");
+            assert!(app.frozen.is_empty());
+        }
+    }
+
+    #[test]
+    fn thawing_checks_the_identity_that_was_frozen() {
+        let pid = 4_000_000_000;
+        let mut app = make_test_app_with_processes(
+            vec![make_test_process(pid, "worker", 100_000, 1.0)],
+            SortMode::Memory,
+        );
+        // The process frozen earlier had another start than the one now on
+        // the PID
+        app.frozen.push(FrozenProcess {
+            identity: ProcessIdentity {
+                pid,
+                start: Some(1),
+            },
+            name: "worker".to_string(),
+        });
+
+        app.dispatch(Action::ThawFrozen(pid));
+        assert!(app.frozen.is_empty());
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(
+            msg,
+            &format!("PID {pid} now belongs to another process; nothing sent")
+        );
+    }
+
+    #[test]
+    fn quitting_with_frozen_processes_offers_to_thaw_them() {
+        let procs = vec![make_test_process(4242, "worker", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let target = ProcessIdentity {
+            pid: 4242,
+            start: None,
+        };
+
+        app.track_frozen(
+            sysinfo::Signal::Stop,
+            &[target],
+            &[KillResult::Success(4242, "SIGSTOP")],
+        );
+        assert_eq!(app.frozen.len(), 1);
+        assert_eq!(app.frozen[0].name, "worker");
+        assert!(app.stopped_pids().contains(&4242));
+
+        app.dispatch(app.map_key(key(KeyCode::Char('q'))));
+        assert!(app.running);
+        assert_eq!(app.input_mode, InputMode::FrozenList);
+        assert!(app.frozen_list.as_ref().unwrap().quitting);
+        assert_eq!(app.map_key(key(KeyCode::Enter)), Action::ThawFrozen(4242));
+        assert_eq!(app.map_key(key(KeyCode::Char('a'))), Action::ThawAll);
+
+        app.dispatch(app.map_key(key(KeyCode::Esc)));
+        assert!(app.running);
+        assert_eq!(app.input_mode, InputMode::Normal);
+
+        app.track_frozen(
+            sysinfo::Signal::Continue,
+            &[target],
+            &[KillResult::Success(4242, "SIGCONT")],
+        );
+        assert!(app.frozen.is_empty());
+        app.dispatch(app.map_key(key(KeyCode::Char('q'))));
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn freeze_keys_target_the_selected_process() {
+        let procs = vec![make_test_process(4242, "worker", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.compute_layout(80, 24);
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let shift = KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT);
+
+        assert_eq!(app.map_key(key('f')), Action::ToggleFreeze(4242));
+        assert_eq!(app.map_key(shift), Action::ToggleFreezeTree(4242));
+        assert_eq!(app.map_key(key('u')), Action::ToggleFrozenList);
+    }
+
+    #[test]
+    fn selection_follows_the_process_and_says_where_it_went() {
+        let procs = vec![
+            make_test_process(1, "low_cpu", 500_000_000, 5.0),
+            make_test_process(2, "high_cpu", 100_000_000, 90.0),
+            make_test_process(3, "mid_cpu", 300_000_000, 50.0),
+        ];
+        let mut app = make_test_app_with_processes(procs, SortMode::Cpu);
+        let relayout = |app: &mut App| {
+            app.needs_relayout = true;
+            app.compute_layout(100, 50);
+        };
+        assert_eq!(app.selected_pid(), Some(2));
+
+        // Dropping to the least CPU moves its tile to the end
+        app.snapshot
+            .process_tree
+            .processes
+            .get_mut(&2)
+            .unwrap()
+            .cpu_percent = 1.0;
+        relayout(&mut app);
+        assert_eq!(app.selected_pid(), Some(2));
+        assert_eq!(app.selected_index, 2);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "Selection followed high_cpu (PID 2) to its new tile");
+
+        // Squeezed out of the visible tiles, it is highlighted inside Other
+        app.max_visible_procs = 2;
+        relayout(&mut app);
+        assert_eq!(app.selected_pid(), Some(0));
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "high_cpu (PID 2) is now grouped into Other");
+
+        // ...and gets the highlight back once there is room again
+        app.max_visible_procs = 0;
+        relayout(&mut app);
+        assert_eq!(app.selected_pid(), Some(2));
+
+        app.snapshot.process_tree.processes.remove(&2);
+        relayout(&mut app);
+        assert_eq!(app.selected_index, NO_SELECTION);
+        assert_eq!(app.selected_pid(), None);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "high_cpu (PID 2) exited; nothing is selected");
+
+        // Arrow keys pick a tile again
+        app.dispatch(Action::Navigate(Direction::Right));
+        assert_eq!(app.selected_index, 0);
+        assert_eq!(app.selected_pid(), Some(3));
+    }
+
+    #[test]
+    fn marked_processes_receive_batch_actions() {
+        let procs = vec![
+            make_test_process(4242, "worker", 100_000, 1.0),
+            make_test_process(4243, "worker", 300_000, 2.5),
+            make_test_process(5000, "editor", 50_000, 0.5),
+        ];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        assert_eq!(app.map_key(key('k')), Action::Kill(4243));
+        app.dispatch(Action::MarkMatching);
+        assert!(app.marked.is_empty());
+
+        app.filter_text = "work".to_string();
+        app.dispatch(Action::MarkMatching);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "Marked 2 processes matching \"work\"");
+        assert_eq!(app.marked_totals(), Some((2, 400_000, 3.5)));
+
+        assert_eq!(
+            app.map_key(key('k')),
+            Action::SendSignalMarked(sysinfo::Signal::Term)
+        );
+        assert_eq!(app.map_key(key('+')), Action::AdjustNiceMarked(1));
+        assert_eq!(app.map_key(key('x')), Action::OpenMarkedSignalPicker);
+
+        // SIGKILL asks first, listing every marked PID
+        app.dispatch(Action::SendSignalMarked(sysinfo::Signal::Kill));
+        assert_eq!(app.input_mode, InputMode::ConfirmSignal);
+        let confirm = app.signal_confirm.as_ref().unwrap();
+        let pids: Vec<u32> = confirm.targets.iter().map(|t| t.pid).collect();
+        assert_eq!(pids, vec![4242, 4243]);
+        app.dispatch(Action::CancelSignal);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "Cancelled SIGKILL to 2 marked processes");
+
+        app.dispatch(Action::ToggleMark(4242));
+        app.dispatch(Action::ToggleMark(5000));
+        let mut pids: Vec<u32> = app.marked.iter().map(|m| m.pid).collect();
+        pids.sort();
+        assert_eq!(pids, vec![4243, 5000]);
+
+        app.dispatch(Action::ClearMarks);
+        assert!(app.marked_totals().is_none());
+    }
+
+    #[test]
+    fn stopping_a_batch_holding_treetop_is_refused() {
+        let own_pid = std::process::id();
+        let procs = vec![
+            make_test_process(own_pid, "treetop", 100_000, 1.0),
+            make_test_process(4_000_000_000, "worker", 300_000, 2.5),
+        ];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.dispatch(Action::ToggleMark(own_pid));
+        app.dispatch(Action::ToggleMark(4_000_000_000));
+
+        app.dispatch(Action::SendSignalMarked(sysinfo::Signal::Stop));
+        assert!(app.signal_confirm.is_none());
+        assert_eq!(app.input_mode, InputMode::Normal);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(
+            msg,
+            &format!("Refused to stop PID {own_pid}: treetop itself would stop")
+        );
+
+        // Nor when the targets were confirmed before the check could run
+        let targets = app.marked_targets();
+        app.status_message = None;
+        app.signal_marked(&targets, sysinfo::Signal::TSTP);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert!(msg.starts_with("Refused to stop"), "{msg}");
+    }
+
+    #[test]
+    fn batch_signals_always_ask_and_go_to_the_previewed_processes() {
+        // PIDs above any pid_max, so nothing real is signalled
+        let procs = vec![
+            make_test_process(4_000_000_000, "worker", 100_000, 1.0),
+            make_test_process(4_000_000_001, "worker", 300_000, 2.5),
+            make_test_process(4_000_000_002, "editor", 50_000, 0.5),
+        ];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.confirm_signals = Vec::new();
+        app.dispatch(Action::ToggleMark(4_000_000_000));
+        app.dispatch(Action::ToggleMark(4_000_000_001));
+
+        app.dispatch(Action::SendSignalMarked(sysinfo::Signal::Term));
+        assert_eq!(app.input_mode, InputMode::ConfirmSignal);
+        assert!(app.signal_confirm.as_ref().unwrap().marked);
+
+        // Marks changing behind the dialog do not change what it sends to
+        app.dispatch(Action::ClearMarks);
+        app.dispatch(Action::ToggleMark(4_000_000_002));
+        app.dispatch(Action::ConfirmSignal);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert!(msg.contains("4000000000"), "{msg}");
+        assert!(msg.contains("4000000001"), "{msg}");
+        assert!(!msg.contains("4000000002"), "{msg}");
+    }
+
+    #[test]
+    fn nice_entry_accepts_a_signed_value() {
+        let procs = vec![make_test_process(4242, "worker", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        app.dispatch(Action::OpenNiceEntry(4242));
+        assert_eq!(app.input_mode, InputMode::NiceEntry);
+        assert_eq!(app.map_key(key(KeyCode::Enter)), Action::None);
+        for c in ['-', '1', '-', '5', '7'] {
+            app.dispatch(app.map_key(key(KeyCode::Char(c))));
+        }
+        assert_eq!(app.nice_entry.as_ref().unwrap().text, "-15");
+        assert_eq!(app.map_key(key(KeyCode::Enter)), Action::SetNice(4242, -15));
+        app.dispatch(app.map_key(key(KeyCode::Backspace)));
+        assert_eq!(app.map_key(key(KeyCode::Enter)), Action::SetNice(4242, -1));
+
+        app.dispatch(app.map_key(key(KeyCode::Esc)));
+        assert!(app.nice_entry.is_none());
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn read_only_refuses_priority_changes() {
+        let procs = vec![make_test_process(4242, "worker", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.safety = SafetyPolicy::new(true, &[]);
+
+        for action in [
+            Action::AdjustNice(4242, 1),
+            Action::OpenNiceEntry(4242),
+            Action::CycleIoPriority(4242),
+        ] {
+            app.status_message = None;
+            app.dispatch(action);
+            assert!(app.nice_entry.is_none());
+            let (msg, _) = app.status_message.as_ref().unwrap();
+            assert_eq!(msg, "Read-only mode: priority changes are disabled");
+        }
+    }
+
+    #[test]
+    fn signal_picker_tab_switches_to_whole_tree() {
+        let procs = vec![make_test_process(4242, "worker", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        app.dispatch(Action::OpenSignalPicker(4242));
+        app.dispatch(app.map_key(key(KeyCode::Tab)));
+        assert!(app.signal_picker.as_ref().unwrap().tree);
+        assert_eq!(
+            app.map_key(key(KeyCode::Enter)),
+            Action::SendSignalTree(4242, sysinfo::Signal::Term)
+        );
+    }
+
+    #[test]
+    fn tree_kill_summary_groups_outcomes_by_pid() {
+        let all_sent: Vec<KillResult> = (10..19)
+            .map(|pid| KillResult::Success(pid, "SIGTERM"))
+            .collect();
+        assert_eq!(
+            tree_kill_summary(10, "SIGTERM", &all_sent),
+            "Sent SIGTERM to 9 processes in tree of 10: 10 11 12 13 14 15 +3"
+        );
+
+        let mixed = vec![
+            KillResult::Success(10, "SIGTERM"),
+            KillResult::Success(11, "SIGTERM"),
+            KillResult::NotFound(12),
+            KillResult::Failed {
+                pid: 13,
+                signal: "SIGTERM",
+                reason: "Operation not permitted".to_string(),
+            },
+        ];
+        assert_eq!(
+            tree_kill_summary(10, "SIGTERM", &mixed),
+            "Tree of 10: sent SIGTERM to 10 11; gone 12; failed 13 (Operation not permitted)"
+        );
+    }
+
+    #[test]
+    fn audit_log_viewer_lists_newest_first() {
+        let procs = vec![make_test_process(1, "test", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        app.dispatch(Action::ToggleAuditLog);
+        assert!(app.audit_log.is_none());
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "Audit log is disabled");
+
+        let path =
+            std::env::temp_dir().join(format!("treetop_app_audit_{}.jsonl", std::process::id()));
+        let log = AuditLog::new(path.clone());
+        for pid in [10, 11, 12] {
+            let mut entry = log.entry("signal");
+            entry.pid = pid;
+            log.append(&entry);
+        }
+        app.safety = SafetyPolicy::default().with_audit(log);
+
+        app.dispatch(app.map_key(key(KeyCode::Char('a'))));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(app.input_mode, InputMode::AuditLog);
+        let view = app.audit_log.as_ref().unwrap();
+        let pids: Vec<u32> = view.entries.iter().map(|e| e.pid).collect();
+        assert_eq!(pids, vec![12, 11, 10]);
+
+        app.dispatch(app.map_key(key(KeyCode::End)));
+        assert_eq!(app.audit_log.as_ref().unwrap().scroll, 2);
+        app.dispatch(app.map_key(key(KeyCode::Esc)));
+        assert!(app.audit_log.is_none());
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn recording_key_toggles_recording_of_each_snapshot() {
+        let procs = vec![make_test_process(1, "test", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        let path = std::env::temp_dir().join(format!(
+            "treetop_app_recording_{}.jsonl",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        app.record_path = Some(path.clone());
+
+        let key = KeyEvent::new(KeyCode::Char('R'), KeyModifiers::NONE);
+        assert_eq!(app.map_key(key), Action::ToggleRecording);
+        app.dispatch(Action::ToggleRecording);
+        assert_eq!(app.header_badge(), "\u{25cf} rec");
+        app.snapshot
+            .process_tree
+            .processes
+            .get_mut(&1)
+            .unwrap()
+            .memory_bytes = 200_000;
+        app.record_snapshot();
+        app.dispatch(Action::ToggleRecording);
+        assert_eq!(app.header_badge(), "live");
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert!(msg.starts_with("Stopped recording: 2 frames"), "{msg}");
+
+        let recording = crate::system::recording::Recording::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(recording.frames.len(), 2);
+        let last = recording.snapshot_at(1).unwrap();
+        assert_eq!(last.process_tree.processes[&1].memory_bytes, 200_000);
+    }
+
+    #[test]
+    fn replay_drives_the_view_from_recorded_frames() {
+        let path =
+            std::env::temp_dir().join(format!("treetop_app_replay_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut recorder = Recorder::create(path.clone()).unwrap();
+        let mut app = make_test_app_with_processes(
+            vec![
+                make_test_process(1, "init", 100_000, 1.0),
+                make_test_process(2, "worker", 50_000, 1.0),
+            ],
+            SortMode::Memory,
+        );
+        recorder.record(&app.snapshot, 1_000_000).unwrap();
+        app.snapshot.process_tree.processes.remove(&2);
+        recorder.record(&app.snapshot, 1_002_000).unwrap();
+        drop(recorder);
+        let replay = Replay::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        app.start_replay(replay);
+        app.compute_layout(100, 50);
+        assert_eq!(app.header_badge(), "replay");
+        assert_eq!(app.clock_secs(), 1_000);
+        assert_eq!(app.snapshot.process_tree.processes.len(), 2);
+        assert!(app.help_entries()[0].1.contains("replay"));
+
+        // Playback moves on with recorded time
+        assert!(!app.tick_replay(Duration::from_millis(1_500)));
+        assert!(app.tick_replay(Duration::from_millis(500)));
+        assert_eq!(app.snapshot.process_tree.processes.len(), 1);
+        assert!(!app.replay.as_ref().unwrap().is_playing());
+
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        app.dispatch(app.map_key(key(',')));
+        assert_eq!(app.snapshot.process_tree.processes.len(), 2);
+        assert_eq!(app.map_key(key('>')), Action::ReplaySpeed(1));
+        assert_eq!(
+            app.map_key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE)),
+            Action::ReplaySeek(1.0)
+        );
+
+        app.replay_bar_area = Some(Rect::new(10, 60, 11, 1));
+        app.dispatch(Action::SelectAt(20, 60));
+        assert_eq!(app.replay.as_ref().unwrap().position(), 1);
+
+        app.dispatch(Action::Kill(1));
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "Replaying a recording: signals are disabled");
+        app.dispatch(Action::ToggleRecording);
+        assert!(app.recorder.is_none());
+    }
+
+    #[test]
+    fn compare_mode_sizes_tiles_by_change_since_the_pin() {
+        let mut app = make_test_app_with_processes(
+            vec![
+                make_test_process(1, "steady", 100_000, 1.0),
+                make_test_process(2, "grower", 100_000, 1.0),
+                make_test_process(3, "leaver", 50_000, 1.0),
+            ],
+            SortMode::Memory,
+        );
+        assert_eq!(
+            app.map_key(KeyEvent::new(KeyCode::Char('D'), KeyModifiers::NONE)),
+            Action::CycleCompare
+        );
+        // Nothing pinned yet: cycling goes from startup back to off
+        app.dispatch(Action::CycleCompare);
+        assert_eq!(app.compare, CompareMode::Startup);
+        app.dispatch(Action::CycleCompare);
+        assert_eq!(app.compare, CompareMode::Off);
+        app.dispatch(Action::PinBaseline);
+        assert_eq!(app.compare, CompareMode::Pinned);
+
+        let processes = &mut app.snapshot.process_tree.processes;
+        processes.get_mut(&2).unwrap().memory_bytes = 400_000;
+        processes.remove(&3);
+        processes.insert(4, make_test_process(4, "newcomer", 20_000, 0.0));
+        let snapshot = std::mem::replace(
+            &mut app.snapshot,
+            SystemSnapshot {
+                cpu_usage_percent: 0.0,
+                memory_total: 0,
+                memory_used: 0,
+                swap_total: 0,
+                swap_used: 0,
+                cpu_per_core: Vec::new(),
+                load_average: [0.0; 3],
+                process_tree: ProcessTree {
+                    processes: HashMap::new(),
+                },
+            },
+        );
+        app.apply_snapshot(snapshot);
+        app.compute_layout(100, 50);
+
+        let tiles: Vec<(&str, u64)> = app
+            .layout_rects
+            .iter()
+            .map(|r| (r.label.as_str(), r.value))
+            .collect();
+        assert_eq!(
+            tiles,
+            [
+                ("grower", 300_000),
+                ("leaver", 50_000),
+                ("newcomer", 20_000)
+            ]
+        );
+        assert!(app.size_label().starts_with("RSS change since pin at"));
+        let marks = app.tile_marks();
+        assert!(marks.grown.contains(&2) && marks.shrunk.contains(&3));
+        assert!(marks.new.contains(&4) && marks.exited.contains(&3));
+
+        // The exited process still has details, as it was at the pin
+        app.selected_index = 1;
+        assert_eq!(app.detail_process().unwrap().name, "leaver");
+
+        app.dispatch(Action::CycleCompare);
+        assert_eq!(app.compare, CompareMode::Off);
+        assert!(app.diff.is_none());
+    }
+
+    #[test]
+    fn open_files_list_scrolls_and_closes() {
+        let procs = vec![make_test_process(1, "test", 100_000, 1.0)];
+        let mut app = make_test_app_with_processes(procs, SortMode::Memory);
+        app.open_files = Some(OpenFilesView {
+            pid: 1,
+            name: "test".to_string(),
+            files: (0..4)
+                .map(|fd| OpenFile::new(fd, format!("/tmp/file{fd}")))
+                .collect(),
+            scroll: 0,
+        });
+        app.input_mode = InputMode::OpenFiles;
+
+        let key = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(app.map_key(key), Action::None);
+        let key = KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE);
+        let action = app.map_key(key);
+        assert_eq!(action, Action::ScrollOpenFiles(10));
+
+        app.dispatch(action);
+        assert_eq!(app.open_files.as_ref().unwrap().scroll, 3);
+        app.dispatch(Action::ScrollOpenFiles(-1));
+        assert_eq!(app.open_files.as_ref().unwrap().scroll, 2);
+        app.dispatch(Action::ScrollOpenFiles(i32::MIN));
+        assert_eq!(app.open_files.as_ref().unwrap().scroll, 0);
+
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.map_key(key), Action::ToggleOpenFiles);
+        app.dispatch(Action::ToggleOpenFiles);
+        assert!(app.open_files.is_none());
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn treemap_export_lays_tiles_out_at_image_size_with_tooltips() {
+        let mut app = make_test_app_with_processes(
+            vec![
+                make_test_process(1, "big", 3_000_000, 12.5),
+                make_test_process(2, "a<b", 1_000_000, 0.0),
+            ],
+            SortMode::Memory,
+        );
+        app.compute_layout(80, 24);
+        assert_eq!(
+            app.map_key(KeyEvent::new(KeyCode::Char('E'), KeyModifiers::NONE)),
+            Action::ExportImage
+        );
+
+        let image = svg::svg(&app, 400, 300);
+        assert!(image.starts_with("<svg "));
+        assert!(image.contains("viewBox=\"0 0 400 300\""));
+        // Laid out for 400x300, not stretched from 80x24 cells
+        assert!(image.contains("width=\"300.0\" height=\"300.0\""));
+        assert!(
+            image.contains(
+                "<title>PID 1&#10;big --flag&#10;Memory: 2.9 MB RSS&#10;CPU: 12.5%</title>"
+            )
+        );
+        assert!(image.contains(">a&lt;b</text>"));
+
+        let page = svg::html(&app, 400, 300);
+        assert!(page.contains("<g data-tip=\"PID 2&#10;a&lt;b --flag"));
+        assert!(page.contains("<script>"));
+        assert!(!page.contains("<title>PID"));
+    }
 }
@@ -10,6 +10,7 @@ pub struct Config {
     pub treemap: TreemapConfig,
     pub colors: ColorsConfig,
     pub keybinds: KeybindsConfig,
+    pub safety: SafetyConfig,
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,6 +22,10 @@ pub struct GeneralConfig {
     pub sparkline_length: usize,
     pub color_support: String,
     pub default_sort: String,
+    pub collector: String,
+    pub memory_metric: String,
+    pub default_grouping: String,
+    pub default_view: String,
 }
 
 impl Default for GeneralConfig {
@@ -32,6 +37,10 @@ impl Default for GeneralConfig {
             sparkline_length: 60,
             color_support: "auto".to_string(),
             default_sort: "memory".to_string(),
+            collector: "auto".to_string(),
+            memory_metric: "rss".to_string(),
+            default_grouping: "process".to_string(),
+            default_view: "processes".to_string(),
         }
     }
 }
@@ -45,6 +54,7 @@ pub struct TreemapConfig {
     pub max_visible_procs: usize,
     pub border_style: String,
     pub animation_frames: u8,
+    pub size_by: String,
 }
 
 impl Default for TreemapConfig {
@@ -56,6 +66,7 @@ impl Default for TreemapConfig {
             max_visible_procs: 25,
             border_style: "thin".to_string(),
             animation_frames: 5,
+            size_by: "memory".to_string(),
         }
     }
 }
@@ -80,6 +91,35 @@ impl Default for ColorsConfig {
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct SafetyConfig {
+    /// Disables every action that changes system state.
+    pub read_only: bool,
+    /// Processes that can never be signalled from the UI: `pid:N`,
+    /// `user:NAME` or a process name, where `*` is a wildcard.
+    pub protected: Vec<String>,
+    /// Record every process-control action in a JSON-lines audit log.
+    pub audit: bool,
+    /// Where the audit log goes; the local data directory when unset.
+    pub audit_log: Option<PathBuf>,
+    /// Signals that open a confirmation dialog before they are sent, by
+    /// name (`"SIGKILL"` or `"kill"`).
+    pub confirm_signals: Vec<String>,
+}
+
+impl Default for SafetyConfig {
+    fn default() -> Self {
+        SafetyConfig {
+            read_only: false,
+            protected: vec!["pid:1".to_string()],
+            audit: true,
+            audit_log: None,
+            confirm_signals: vec!["SIGKILL".to_string()],
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(default)]
 pub struct KeybindsConfig {
@@ -95,6 +135,37 @@ pub struct KeybindsConfig {
     pub help: String,
     pub cycle_sort: String,
     pub refresh: String,
+    pub cycle_memory: String,
+    pub cycle_grouping: String,
+    pub cycle_view: String,
+    pub cycle_size: String,
+    pub open_files: String,
+    pub signal_menu: String,
+    pub kill_tree: String,
+    pub confirm: String,
+    pub audit_log: String,
+    pub nice_up: String,
+    pub nice_down: String,
+    pub set_nice: String,
+    pub cycle_io_priority: String,
+    pub freeze: String,
+    pub freeze_tree: String,
+    pub frozen_list: String,
+    pub mark: String,
+    pub mark_matching: String,
+    pub clear_marks: String,
+    pub export: String,
+    pub export_image: String,
+    pub record: String,
+    pub cycle_compare: String,
+    pub pin_baseline: String,
+    pub replay_play: String,
+    pub replay_step_back: String,
+    pub replay_step_forward: String,
+    pub replay_slower: String,
+    pub replay_faster: String,
+    pub replay_seek_back: String,
+    pub replay_seek_forward: String,
 }
 
 impl Default for KeybindsConfig {
@@ -112,6 +183,37 @@ impl Default for KeybindsConfig {
             help: "?".to_string(),
             cycle_sort: "s".to_string(),
             refresh: "r".to_string(),
+            cycle_memory: "m".to_string(),
+            cycle_grouping: "g".to_string(),
+            cycle_view: "v".to_string(),
+            cycle_size: "i".to_string(),
+            open_files: "o".to_string(),
+            signal_menu: "x".to_string(),
+            kill_tree: "T".to_string(),
+            confirm: "y".to_string(),
+            audit_log: "a".to_string(),
+            nice_up: "+".to_string(),
+            nice_down: "-".to_string(),
+            set_nice: "n".to_string(),
+            cycle_io_priority: "I".to_string(),
+            freeze: "f".to_string(),
+            freeze_tree: "F".to_string(),
+            frozen_list: "u".to_string(),
+            mark: "Space".to_string(),
+            mark_matching: "*".to_string(),
+            clear_marks: "U".to_string(),
+            export: "e".to_string(),
+            export_image: "E".to_string(),
+            record: "R".to_string(),
+            cycle_compare: "D".to_string(),
+            pin_baseline: "P".to_string(),
+            replay_play: "p".to_string(),
+            replay_step_back: ",".to_string(),
+            replay_step_forward: ".".to_string(),
+            replay_slower: "<".to_string(),
+            replay_faster: ">".to_string(),
+            replay_seek_back: "[".to_string(),
+            replay_seek_forward: "]".to_string(),
         }
     }
 }
@@ -173,6 +275,7 @@ mod tests {
         assert_eq!(config.treemap.min_rect_width, 6);
         assert_eq!(config.colors.theme, "vivid");
         assert_eq!(config.general.color_support, "auto");
+        assert_eq!(config.safety.confirm_signals, vec!["SIGKILL"]);
     }
 
     #[test]
@@ -282,4 +385,58 @@ default_sort = "cpu"
         let config: Config = toml::from_str(toml_str).unwrap();
         assert_eq!(config.general.default_sort, "cpu");
     }
+
+    #[test]
+    fn collector_backend_config() {
+        assert_eq!(Config::default().general.collector, "auto");
+
+        let toml_str = r#"
+[general]
+collector = "sysinfo"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.general.collector, "sysinfo");
+    }
+
+    #[test]
+    fn memory_metric_config() {
+        let config = Config::default();
+        assert_eq!(config.general.memory_metric, "rss");
+        assert_eq!(config.keybinds.cycle_memory, "m");
+
+        let toml_str = r#"
+[general]
+memory_metric = "pss"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.general.memory_metric, "pss");
+    }
+
+    #[test]
+    fn grouping_config() {
+        let config = Config::default();
+        assert_eq!(config.general.default_grouping, "process");
+        assert_eq!(config.keybinds.cycle_grouping, "g");
+
+        let toml_str = r#"
+[general]
+default_grouping = "container"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.general.default_grouping, "container");
+    }
+
+    #[test]
+    fn size_by_config() {
+        let config = Config::default();
+        assert_eq!(config.treemap.size_by, "memory");
+        assert_eq!(config.keybinds.cycle_size, "i");
+
+        let toml_str = r#"
+[treemap]
+size_by = "io"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.treemap.size_by, "io");
+    }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub fn truncate_unicode(s: &str, max_width: usize) -> String {
@@ -18,6 +20,59 @@ pub fn truncate_unicode(s: &str, max_width: usize) -> String {
     result
 }
 
+pub fn format_rate(bytes_per_sec: u64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec))
+}
+
+/// Compact duration with its two largest units: `4.2s`, `3m 07s`, `5h 12m`, `2d 03h`.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, mins) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}h")
+    } else if secs >= 3_600 {
+        format!("{}h {mins:02}m", secs / 3_600)
+    } else if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+/// UTC wall-clock time of a Unix timestamp, as `2025-02-22 14:03 UTC`.
+pub fn format_timestamp_utc(epoch_secs: u64) -> String {
+    let (year, month, day) = civil_date(epoch_secs);
+    let (hour, minute) = (epoch_secs / 3_600 % 24, epoch_secs / 60 % 60);
+    format!("{year}-{month:02}-{day:02} {hour:02}:{minute:02} UTC")
+}
+
+/// RFC 3339 UTC timestamp with seconds, as `2025-02-22T14:03:20Z`.
+pub fn format_timestamp_rfc3339(epoch_secs: u64) -> String {
+    let (year, month, day) = civil_date(epoch_secs);
+    let (hour, minute, second) = (
+        epoch_secs / 3_600 % 24,
+        epoch_secs / 60 % 60,
+        epoch_secs % 60,
+    );
+    format!("{year}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
+}
+
+/// Year, month and day of a Unix timestamp in UTC.
+fn civil_date(epoch_secs: u64) -> (i64, i64, i64) {
+    let days = (epoch_secs / 86_400) as i64;
+    // Howard Hinnant's civil_from_days, shifted to a March-based year
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
 pub fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = 1024 * 1024;
@@ -33,3 +88,33 @@ pub fn format_bytes(bytes: u64) -> String {
         format!("{} B", bytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_keep_two_units() {
+        assert_eq!(format_duration(Duration::from_millis(4_250)), "4.2s");
+        assert_eq!(format_duration(Duration::from_secs(187)), "3m 07s");
+        assert_eq!(
+            format_duration(Duration::from_secs(5 * 3_600 + 720)),
+            "5h 12m"
+        );
+        assert_eq!(
+            format_duration(Duration::from_secs(2 * 86_400 + 3 * 3_600)),
+            "2d 03h"
+        );
+    }
+
+    #[test]
+    fn timestamps_render_as_utc_dates() {
+        assert_eq!(format_timestamp_utc(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp_utc(951_782_400), "2000-02-29 00:00 UTC");
+        assert_eq!(format_timestamp_utc(1_740_233_000), "2025-02-22 14:03 UTC");
+        assert_eq!(
+            format_timestamp_rfc3339(1_740_233_000),
+            "2025-02-22T14:03:20Z"
+        );
+    }
+}
@@ -9,18 +9,24 @@ mod system;
 mod treemap;
 mod ui;
 
-use std::io::stdout;
-use std::path::PathBuf;
-use std::time::Duration;
+use std::io::{IsTerminal, Write, stdout};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use app::App;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use color_eyre::Result;
 use color_eyre::eyre::eyre;
 use config::{load_config, load_config_from_path};
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture, MouseEventKind};
 use crossterm::execute;
 use event::{Event, EventHandler};
+use system::collector::{BackendKind, Collector};
+use system::diff::Baseline;
+use system::export::{SnapshotRecord, write_csv};
+use system::process::MemoryMetric;
+use system::recording::now_ms;
+use system::replay::Replay;
 
 #[derive(Parser)]
 #[command(
@@ -28,12 +34,15 @@ use event::{Event, EventHandler};
     about = "TUI system monitor with treemap visualization"
 )]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to config file
-    #[arg(long)]
+    #[arg(long, global = true)]
     config: Option<PathBuf>,
 
     /// Refresh rate in milliseconds
-    #[arg(long)]
+    #[arg(long, global = true)]
     refresh_rate: Option<u64>,
 
     /// Color mode: name, memory, cpu, user, group, mono
@@ -44,6 +53,53 @@ struct Cli {
     #[arg(long)]
     color: Option<String>,
 
+    /// Disable every action that changes system state (signals, renice, ...)
+    #[arg(long, default_value_t = false)]
+    read_only: bool,
+
+    /// Append every refreshed snapshot to this recording file
+    #[arg(long, value_name = "FILE")]
+    record: Option<PathBuf>,
+
+    /// Play back a recording instead of showing live data
+    #[arg(long, value_name = "FILE", conflicts_with = "record")]
+    replay: Option<PathBuf>,
+
+    /// Show how memory changed from the end of one recording to the end of
+    /// another
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["BEFORE", "AFTER"],
+        conflicts_with_all = ["record", "replay"]
+    )]
+    compare: Vec<PathBuf>,
+
+    /// Print the header, treemap and largest processes to stdout instead of
+    /// starting the UI
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["record", "replay", "compare"]
+    )]
+    batch: bool,
+
+    /// Pages printed in batch mode; 0 prints until interrupted
+    #[arg(long, default_value_t = 1, requires = "batch")]
+    iterations: usize,
+
+    /// Processes listed under the treemap in batch mode
+    #[arg(long, default_value_t = 10, requires = "batch")]
+    top: usize,
+
+    /// Batch page width; the terminal's, or 120 without one
+    #[arg(long, requires = "batch")]
+    batch_width: Option<u16>,
+
+    /// Batch page height; the terminal's, or 40 without one
+    #[arg(long, requires = "batch")]
+    batch_height: Option<u16>,
+
     /// Run headless performance capture without interactive terminal.
     #[arg(long, default_value_t = false)]
     perf_capture: bool,
@@ -65,6 +121,50 @@ struct Cli {
     perf_output: PathBuf,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    /// Print the process tree with subtree totals and system figures, then
+    /// exit
+    Snapshot {
+        #[arg(long, value_enum, default_value_t = SnapshotFormat::Json)]
+        format: SnapshotFormat,
+
+        /// Print one NDJSON line per refresh interval until interrupted
+        #[arg(long, default_value_t = false)]
+        stream: bool,
+    },
+    /// Write the treemap as an SVG image or an HTML page with tooltips,
+    /// then exit
+    Treemap {
+        #[arg(long, value_enum, default_value_t = TreemapFormat::Svg)]
+        format: TreemapFormat,
+
+        /// Image width in pixels
+        #[arg(long, default_value_t = ui::svg::DEFAULT_WIDTH)]
+        width: u32,
+
+        /// Image height in pixels
+        #[arg(long, default_value_t = ui::svg::DEFAULT_HEIGHT)]
+        height: u32,
+
+        /// File to write; stdout when omitted
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SnapshotFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TreemapFormat {
+    Svg,
+    Html,
+}
+
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<()> {
     color_eyre::install()?;
@@ -75,6 +175,34 @@ async fn main() -> Result<()> {
     if cli.perf_capture {
         return run_perf_capture(config, &cli);
     }
+    if cli.batch {
+        return run_batch(config, &cli).await;
+    }
+    match cli.command {
+        Some(Command::Snapshot { format, stream }) => {
+            return run_snapshot(config, format, stream).await;
+        }
+        Some(Command::Treemap {
+            format,
+            width,
+            height,
+            output,
+        }) => return run_treemap(config, format, (width, height), output).await,
+        None => {}
+    }
+
+    // Fail before taking over the terminal when a recording is unusable
+    let (replay, baseline) = match (cli.compare.as_slice(), &cli.replay) {
+        ([before, after], _) => {
+            let baseline = final_frame(before)?;
+            let mut after = open_replay(after)?;
+            after.seek(after.frame_count() - 1);
+            after.pause();
+            (Some(after), Some(baseline))
+        }
+        (_, Some(path)) => (Some(open_replay(path)?), None),
+        _ => (None, None),
+    };
 
     let mut terminal = ratatui::init();
     execute!(stdout(), EnableMouseCapture)?;
@@ -86,7 +214,7 @@ async fn main() -> Result<()> {
         original_hook(panic_info);
     }));
 
-    let result = run(&mut terminal, config).await;
+    let result = run(&mut terminal, config, cli.record, replay, baseline).await;
 
     execute!(stdout(), DisableMouseCapture)?;
     ratatui::restore();
@@ -94,12 +222,49 @@ async fn main() -> Result<()> {
     result
 }
 
-async fn run(terminal: &mut ratatui::DefaultTerminal, config: config::Config) -> Result<()> {
+fn open_replay(path: &Path) -> Result<Replay> {
+    Replay::open(path).map_err(|err| eyre!("cannot replay {}: {err}", path.display()))
+}
+
+/// The last frame of the recording at `path`, as a compare baseline named
+/// after the file.
+fn final_frame(path: &Path) -> Result<Baseline> {
+    let mut replay = open_replay(path)?;
+    replay.seek(replay.frame_count() - 1);
+    let label = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    Ok(Baseline {
+        label,
+        tree: replay.snapshot().process_tree,
+    })
+}
+
+async fn run(
+    terminal: &mut ratatui::DefaultTerminal,
+    config: config::Config,
+    record: Option<PathBuf>,
+    replay: Option<Replay>,
+    baseline: Option<Baseline>,
+) -> Result<()> {
     let tick_rate = Duration::from_millis(config.general.refresh_rate_ms);
     let mut app = App::new(config);
+    if let Some(replay) = replay {
+        app.start_replay(replay);
+    }
+    if let Some(baseline) = baseline {
+        app.pin_baseline(baseline);
+    }
+    if let Some(path) = record {
+        app.record_path = Some(path.clone());
+        app.start_recording(path.clone())
+            .map_err(|err| eyre!("cannot record to {}: {err}", path.display()))?;
+    }
     let mut events = EventHandler::new(tick_rate);
 
     terminal.draw(|frame| ui::draw(frame, &mut app))?;
+    let mut last_animate = Instant::now();
 
     while app.running {
         if let Some(event) = events.next().await {
@@ -124,6 +289,11 @@ async fn run(terminal: &mut ratatui::DefaultTerminal, config: config::Config) ->
                     should_draw = true;
                 }
                 Event::Animate => {
+                    let now = Instant::now();
+                    if app.tick_replay(now.duration_since(last_animate)) {
+                        should_draw = true;
+                    }
+                    last_animate = now;
                     if app.is_animating() {
                         app.tick_animation();
                         should_draw = true;
@@ -158,10 +328,131 @@ fn load_config_for_cli(cli: &Cli) -> config::Config {
     if let Some(ref support) = cli.color {
         config.general.color_support = support.clone();
     }
+    if cli.read_only {
+        config.safety.read_only = true;
+    }
 
     config
 }
 
+/// Prints snapshots to stdout for scripts: one document, or an NDJSON line
+/// per refresh with `stream`. A closed pipe ends the stream quietly.
+async fn run_snapshot(config: config::Config, format: SnapshotFormat, stream: bool) -> Result<()> {
+    if stream && format == SnapshotFormat::Csv {
+        return Err(eyre!("--stream prints NDJSON; drop --format csv"));
+    }
+    let mut collector =
+        Collector::with_backend(BackendKind::from_str_config(&config.general.collector));
+    let metric = MemoryMetric::from_str_config(&config.general.memory_metric);
+    collector.set_memory_metric(metric);
+    // CPU usage and I/O rates need a previous sample
+    collector.refresh();
+    tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+
+    let mut ticks = tokio::time::interval(Duration::from_millis(config.general.refresh_rate_ms));
+    let mut out = std::io::stdout().lock();
+    loop {
+        ticks.tick().await;
+        let record = SnapshotRecord::new(&collector.refresh(), now_ms(), metric);
+        let written = match (stream, format) {
+            (true, _) => serde_json::to_writer(&mut out, &record)
+                .map_err(std::io::Error::from)
+                .and_then(|()| writeln!(out)),
+            (false, SnapshotFormat::Json) => serde_json::to_writer_pretty(&mut out, &record)
+                .map_err(std::io::Error::from)
+                .and_then(|()| writeln!(out)),
+            (false, SnapshotFormat::Csv) => write_csv(&mut out, &record),
+        };
+        match written.and_then(|()| out.flush()) {
+            Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result?,
+        }
+        if !stream {
+            return Ok(());
+        }
+    }
+}
+
+/// Prints batch pages to stdout through the same headless backend as perf
+/// capture, without raw mode or the alternate screen. Pages are ANSI when
+/// stdout is a terminal or `--color` asks for colors, plain text otherwise.
+async fn run_batch(mut config: config::Config, cli: &Cli) -> Result<()> {
+    // Every page shows the settled layout, not a frame of the transition
+    config.treemap.animation_frames = 0;
+    let (columns, rows) = crossterm::terminal::size().unwrap_or((120, 40));
+    let width = cli.batch_width.unwrap_or(columns);
+    let height = cli.batch_height.unwrap_or(rows);
+    if width == 0 || height == 0 {
+        return Err(eyre!(
+            "--batch-width and --batch-height must be greater than 0"
+        ));
+    }
+    let ansi = match cli.color.as_deref() {
+        Some("mono") => false,
+        None | Some("auto") => stdout().is_terminal(),
+        Some(_) => true,
+    };
+
+    let mut ticks = tokio::time::interval(Duration::from_millis(config.general.refresh_rate_ms));
+    let mut app = App::new(config);
+    let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height))?;
+    // CPU usage needs a previous sample
+    tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+
+    let mut out = stdout().lock();
+    let mut page = 0;
+    while cli.iterations == 0 || page < cli.iterations {
+        ticks.tick().await;
+        app.refresh_data();
+        terminal.draw(|frame| ui::batch::draw(frame, &mut app, cli.top))?;
+        let text = ui::batch::buffer_to_text(terminal.backend().buffer(), ansi);
+        // Pages after the first are set apart by a blank line
+        let separator = if page > 0 { "\n" } else { "" };
+        match write!(out, "{separator}{text}").and_then(|()| out.flush()) {
+            Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result?,
+        }
+        page += 1;
+    }
+    Ok(())
+}
+
+/// Lays out one live snapshot for an image of `size` pixels and writes it
+/// to `output` or stdout.
+async fn run_treemap(
+    config: config::Config,
+    format: TreemapFormat,
+    (width, height): (u32, u32),
+    output: Option<PathBuf>,
+) -> Result<()> {
+    if width == 0 || height == 0 {
+        return Err(eyre!("--width and --height must be greater than 0"));
+    }
+    let mut app = App::new(config);
+    // CPU usage needs a previous sample
+    tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+    app.refresh_data();
+    // Fold away the tiles a terminal of about the same size could not show,
+    // taking a cell as 8x16 pixels
+    let columns = u16::try_from(width / 8).unwrap_or(u16::MAX).max(1);
+    let rows = u16::try_from(height / 16).unwrap_or(u16::MAX).max(1);
+    app.compute_layout(columns, rows);
+
+    let document = match format {
+        TreemapFormat::Svg => ui::svg::svg(&app, width, height),
+        TreemapFormat::Html => ui::svg::html(&app, width, height),
+    };
+    match output {
+        Some(path) => std::fs::write(&path, document)
+            .map_err(|err| eyre!("cannot write {}: {err}", path.display()))?,
+        None => match stdout().lock().write_all(document.as_bytes()) {
+            Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => {}
+            result => result?,
+        },
+    }
+    Ok(())
+}
+
 fn run_perf_capture(config: config::Config, cli: &Cli) -> Result<()> {
     #[cfg(not(feature = "perf-tracing"))]
     {
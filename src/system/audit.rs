@@ -0,0 +1,249 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind, Users};
+
+use crate::format::format_timestamp_rfc3339;
+use crate::system::process::ProcessInfo;
+
+/// One process-control action, as one line of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// RFC 3339 UTC time the action was taken.
+    pub timestamp: String,
+    /// User treetop runs as.
+    pub user: String,
+    /// Who ran treetop through sudo, when it was.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sudo_user: Option<String>,
+    /// `signal`, `renice` or `ioprio`; `freeze` and `resume` for the SIGSTOP
+    /// and SIGCONT around a tree signal.
+    pub action: String,
+    pub pid: u32,
+    /// Start of the process as its identity records it: clock ticks since
+    /// boot on Linux, seconds since the Unix epoch elsewhere. With `pid`
+    /// this names the process.
+    pub start_time: Option<u64>,
+    pub name: String,
+    pub command: String,
+    /// Signal name, e.g. `SIGTERM`, for `signal` actions.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub signal: String,
+    /// Value applied by other actions, e.g. `nice 10` or `io idle`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setting: Option<String>,
+    /// `sent` or `applied`, `not_found`, `pid_reused`, `refused` or `failed`.
+    pub result: String,
+    /// Refusal reason or OS error text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Append-only JSON-lines file of [`AuditEntry`] records.
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    user: String,
+    sudo_user: Option<String>,
+    /// Last write failure, kept until the UI reports it.
+    error: Mutex<Option<String>>,
+}
+
+impl AuditLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            user: invoking_user(),
+            sudo_user: std::env::var("SUDO_USER").ok(),
+            error: Mutex::new(None),
+        }
+    }
+
+    /// `audit.jsonl` in the platform's local data directory.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(dirs::data_local_dir()?.join("treetop").join("audit.jsonl"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// An entry stamped with the current time and user; the caller fills in
+    /// the target and outcome.
+    pub fn entry(&self, action: &str) -> AuditEntry {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        AuditEntry {
+            timestamp: format_timestamp_rfc3339(now),
+            user: self.user.clone(),
+            sudo_user: self.sudo_user.clone(),
+            action: action.to_string(),
+            pid: 0,
+            start_time: None,
+            name: String::new(),
+            command: String::new(),
+            signal: String::new(),
+            setting: None,
+            result: String::new(),
+            detail: None,
+        }
+    }
+
+    /// [`AuditLog::entry`] aimed at `target`, with its name and command
+    /// line as the snapshot showed them.
+    pub fn entry_for(&self, action: &str, target: &ProcessInfo) -> AuditEntry {
+        let mut entry = self.entry(action);
+        entry.pid = target.pid;
+        entry.start_time = target.identity().start;
+        entry.name = target.name.clone();
+        entry.command = target.command.clone();
+        entry
+    }
+
+    /// Appends `entry` as one line, creating the file and its directory on
+    /// first use. A failure is kept for [`AuditLog::take_error`].
+    pub fn append(&self, entry: &AuditEntry) {
+        if let Err(err) = self.write_line(entry)
+            && let Ok(mut error) = self.error.lock()
+        {
+            *error = Some(format!("{}: {err}", self.path.display()));
+        }
+    }
+
+    fn write_line(&self, entry: &AuditEntry) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        // One write per line so concurrent treetops never interleave entries
+        file.write_all(line.as_bytes())
+    }
+
+    /// The last write failure since the previous call, if any.
+    pub fn take_error(&self) -> Option<String> {
+        self.error.lock().ok()?.take()
+    }
+
+    /// Up to `limit` of the most recent entries, newest first. Lines that do
+    /// not parse are skipped; a missing file reads as empty.
+    pub fn read_recent(&self, limit: usize) -> io::Result<Vec<AuditEntry>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        Ok(parse_entries(&contents)
+            .into_iter()
+            .rev()
+            .take(limit)
+            .collect())
+    }
+}
+
+pub fn parse_entries(contents: &str) -> Vec<AuditEntry> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Name of the user this process runs as, falling back to `$USER`.
+fn invoking_user() -> String {
+    let from_uid = || {
+        let pid = sysinfo::get_current_pid().ok()?;
+        let mut sys = System::new();
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            false,
+            ProcessRefreshKind::nothing().with_user(UpdateKind::Always),
+        );
+        let uid = sys.process(pid)?.user_id()?.clone();
+        let users = Users::new_with_refreshed_list();
+        Some(users.get_user_by_id(&uid)?.name().to_string())
+    };
+    from_uid()
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_round_trip_and_read_newest_first() {
+        let path = std::env::temp_dir().join(format!("treetop_audit_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let log = AuditLog::new(path.clone());
+        assert!(log.read_recent(10).unwrap().is_empty());
+
+        for (pid, result) in [(10, "sent"), (11, "refused")] {
+            let mut entry = log.entry("signal");
+            entry.pid = pid;
+            entry.signal = "SIGTERM".to_string();
+            entry.result = result.to_string();
+            log.append(&entry);
+        }
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"not json\n")
+            .unwrap();
+
+        let entries = log.read_recent(10).unwrap();
+        let _ = fs::remove_file(&path);
+        assert!(log.take_error().is_none());
+        let pids: Vec<u32> = entries.iter().map(|e| e.pid).collect();
+        assert_eq!(pids, vec![11, 10]);
+        assert_eq!(entries[0].result, "refused");
+        assert!(!entries[0].user.is_empty());
+    }
+
+    #[test]
+    fn optional_fields_are_left_out() {
+        let entry = AuditEntry {
+            timestamp: "2025-02-22T14:03:20Z".to_string(),
+            user: "alice".to_string(),
+            sudo_user: None,
+            action: "signal".to_string(),
+            pid: 4242,
+            start_time: Some(1_740_230_000),
+            name: "nginx".to_string(),
+            command: "nginx -g daemon off;".to_string(),
+            signal: "SIGKILL".to_string(),
+            setting: None,
+            result: "sent".to_string(),
+            detail: None,
+        };
+        let line = serde_json::to_string(&entry).unwrap();
+        assert_eq!(
+            line,
+            r#"{"timestamp":"2025-02-22T14:03:20Z","user":"alice","action":"signal","pid":4242,"start_time":1740230000,"name":"nginx","command":"nginx -g daemon off;","signal":"SIGKILL","result":"sent"}"#
+        );
+        assert_eq!(parse_entries(&line), vec![entry]);
+    }
+
+    #[test]
+    fn write_failures_are_kept_until_taken() {
+        // A regular file where the log's directory should be
+        let file = std::env::temp_dir().join(format!("treetop_audit_dir_{}", std::process::id()));
+        fs::write(&file, "").unwrap();
+        let log = AuditLog::new(file.join("audit.jsonl"));
+        log.append(&log.entry("signal"));
+        let _ = fs::remove_file(&file);
+        assert!(log.take_error().is_some());
+        assert!(log.take_error().is_none());
+    }
+}
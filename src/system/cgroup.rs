@@ -0,0 +1,185 @@
+use std::fs;
+use std::path::Path;
+
+/// First tile id used for cgroup tiles, below the container group range.
+pub const CGROUP_TILE_BASE: u32 = 0xD000_0000;
+
+pub fn cgroup_tile_id(index: usize) -> u32 {
+    CGROUP_TILE_BASE + index as u32
+}
+
+pub fn is_cgroup_tile(id: u32) -> bool {
+    (CGROUP_TILE_BASE..crate::system::container::GROUP_TILE_BASE).contains(&id)
+}
+
+/// Counters from `memory.events`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryEvents {
+    pub high: u64,
+    pub max: u64,
+    pub oom: u64,
+    pub oom_kill: u64,
+}
+
+/// Counters from `cpu.stat`, in microseconds where applicable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuStat {
+    pub usage_usec: u64,
+    pub user_usec: u64,
+    pub system_usec: u64,
+    pub nr_throttled: u64,
+    pub throttled_usec: u64,
+}
+
+/// One cgroup v2 directory.
+///
+/// `memory.current` includes kernel-accounted memory (page cache, sockets,
+/// slab) that never shows up in any process's RSS.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CgroupNode {
+    /// Path relative to the cgroup root, e.g. `/system.slice/nginx.service`.
+    pub path: String,
+    pub name: String,
+    pub memory_current: u64,
+    /// `None` when the limit is `max` or the memory controller is disabled.
+    pub memory_max: Option<u64>,
+    pub memory_high: Option<u64>,
+    pub memory_events: MemoryEvents,
+    pub cpu_stat: CpuStat,
+    pub process_count: usize,
+    pub has_children: bool,
+}
+
+/// Reads the direct children of the cgroup at `path` under `root`, largest
+/// `memory.current` first.
+pub fn read_children(root: &Path, path: &str) -> Vec<CgroupNode> {
+    let dir = root.join(path.trim_start_matches('/'));
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut nodes: Vec<CgroupNode> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|e| {
+            let name = e.file_name().to_str()?.to_string();
+            let child = format!("{}/{name}", path.trim_end_matches('/'));
+            read_node(root, &child)
+        })
+        .collect();
+    nodes.sort_by_key(|n| std::cmp::Reverse(n.memory_current));
+    nodes
+}
+
+/// Reads a single cgroup. Missing interface files read as zero/unset.
+pub fn read_node(root: &Path, path: &str) -> Option<CgroupNode> {
+    let dir = root.join(path.trim_start_matches('/'));
+    if !dir.is_dir() {
+        return None;
+    }
+    let read = |file: &str| fs::read_to_string(dir.join(file)).unwrap_or_default();
+    let has_children = fs::read_dir(&dir)
+        .map(|mut entries| entries.any(|e| e.is_ok_and(|e| e.path().is_dir())))
+        .unwrap_or(false);
+
+    Some(CgroupNode {
+        path: path.to_string(),
+        name: path.rsplit('/').next().unwrap_or(path).to_string(),
+        memory_current: read("memory.current").trim().parse().unwrap_or(0),
+        memory_max: parse_limit(&read("memory.max")),
+        memory_high: parse_limit(&read("memory.high")),
+        memory_events: parse_memory_events(&read("memory.events")),
+        cpu_stat: parse_cpu_stat(&read("cpu.stat")),
+        process_count: read("cgroup.procs").lines().count(),
+        has_children,
+    })
+}
+
+/// Parses `memory.max` / `memory.high`; `max` means unlimited.
+pub fn parse_limit(contents: &str) -> Option<u64> {
+    match contents.trim() {
+        "" | "max" => None,
+        value => value.parse().ok(),
+    }
+}
+
+pub fn parse_memory_events(contents: &str) -> MemoryEvents {
+    let mut events = MemoryEvents::default();
+    for (key, value) in flat_keyed(contents) {
+        match key {
+            "high" => events.high = value,
+            "max" => events.max = value,
+            "oom" => events.oom = value,
+            "oom_kill" => events.oom_kill = value,
+            _ => {}
+        }
+    }
+    events
+}
+
+pub fn parse_cpu_stat(contents: &str) -> CpuStat {
+    let mut stat = CpuStat::default();
+    for (key, value) in flat_keyed(contents) {
+        match key {
+            "usage_usec" => stat.usage_usec = value,
+            "user_usec" => stat.user_usec = value,
+            "system_usec" => stat.system_usec = value,
+            "nr_throttled" => stat.nr_throttled = value,
+            "throttled_usec" => stat.throttled_usec = value,
+            _ => {}
+        }
+    }
+    stat
+}
+
+/// Iterates a cgroup "flat keyed" file of `key value` lines.
+fn flat_keyed(contents: &str) -> impl Iterator<Item = (&str, u64)> {
+    contents.lines().filter_map(|line| {
+        let (key, value) = line.split_once(' ')?;
+        Some((key, value.trim().parse().ok()?))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_treat_max_as_unlimited() {
+        assert_eq!(parse_limit("max\n"), None);
+        assert_eq!(parse_limit("536870912\n"), Some(536_870_912));
+        assert_eq!(parse_limit(""), None);
+    }
+
+    #[test]
+    fn memory_events_reads_oom_counters() {
+        let events =
+            parse_memory_events("low 0\nhigh 12\nmax 3\noom 2\noom_kill 1\noom_group_kill 0\n");
+        assert_eq!(
+            events,
+            MemoryEvents {
+                high: 12,
+                max: 3,
+                oom: 2,
+                oom_kill: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn cpu_stat_reads_usage_and_throttling() {
+        let stat = parse_cpu_stat(
+            "usage_usec 8000000\nuser_usec 6000000\nsystem_usec 2000000\n\
+             nr_periods 100\nnr_throttled 7\nthrottled_usec 350000\n",
+        );
+        assert_eq!(stat.usage_usec, 8_000_000);
+        assert_eq!(stat.system_usec, 2_000_000);
+        assert_eq!(stat.nr_throttled, 7);
+        assert_eq!(stat.throttled_usec, 350_000);
+    }
+
+    #[test]
+    fn cgroup_tiles_stay_below_group_tiles() {
+        assert!(is_cgroup_tile(cgroup_tile_id(0)));
+        assert!(!is_cgroup_tile(crate::system::container::group_tile_id(0)));
+    }
+}
@@ -1,7 +1,13 @@
-use sysinfo::{ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System};
+use std::collections::HashMap;
+use std::time::Instant;
+
+use sysinfo::{Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, UpdateKind};
 
 use super::platform;
-use super::process::{ProcessInfo, ProcessState, build_process_tree_from_flat};
+use super::platform::IoStats;
+use super::process::{
+    IoRate, MemoryMetric, ProcessInfo, ProcessState, build_process_tree_from_flat,
+};
 use super::snapshot::SystemSnapshot;
 
 fn map_process_status(status: ProcessStatus) -> ProcessState {
@@ -17,57 +23,59 @@ fn map_process_status(status: ProcessStatus) -> ProcessState {
     }
 }
 
-pub struct Collector {
-    sys: System,
+/// Source of per-process data for a [`Collector`].
+///
+/// System-wide figures (memory, swap, CPU, load) always come from sysinfo;
+/// backends only fill in the process list. `proportional` asks for PSS/USS,
+/// which are only worth their cost while the active metric shows them.
+pub trait CollectorBackend {
+    fn name(&self) -> &'static str;
+    fn collect_processes(&mut self, sys: &mut System, proportional: bool) -> Vec<ProcessInfo>;
 }
 
-impl Default for Collector {
-    fn default() -> Self {
-        Self::new()
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackendKind {
+    /// Native backend where the platform has one, sysinfo otherwise.
+    #[default]
+    Auto,
+    Sysinfo,
+    Native,
 }
 
-impl Collector {
-    pub fn new() -> Self {
-        let mut sys = System::new();
-        sys.refresh_memory();
-        sys.refresh_cpu_all();
-        sys.refresh_processes_specifics(
-            ProcessesToUpdate::All,
-            true,
-            ProcessRefreshKind::everything(),
-        );
-        Collector { sys }
-    }
-
-    pub fn system(&self) -> &System {
-        &self.sys
+impl BackendKind {
+    pub fn from_str_config(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "sysinfo" => BackendKind::Sysinfo,
+            "native" | "procfs" => BackendKind::Native,
+            _ => BackendKind::Auto,
+        }
     }
+}
 
-    pub fn refresh(&mut self) -> SystemSnapshot {
-        #[cfg(feature = "perf-tracing")]
-        let _refresh_span = tracing::debug_span!("collector.refresh").entered();
+/// Portable backend built on sysinfo, enriched with the per-PID
+/// `platform` extension lookups.
+#[derive(Default)]
+pub struct SysinfoBackend {
+    primed: bool,
+}
 
-        self.sys.refresh_memory();
-        self.sys.refresh_cpu_all();
-        self.sys.refresh_processes_specifics(
-            ProcessesToUpdate::All,
-            true,
-            ProcessRefreshKind::nothing().with_memory().with_cpu(),
-        );
-        self.build_snapshot()
+impl CollectorBackend for SysinfoBackend {
+    fn name(&self) -> &'static str {
+        "sysinfo"
     }
 
-    fn build_snapshot(&self) -> SystemSnapshot {
-        #[cfg(feature = "perf-tracing")]
-        let _snapshot_span = tracing::debug_span!("collector.build_snapshot").entered();
+    fn collect_processes(&mut self, sys: &mut System, proportional: bool) -> Vec<ProcessInfo> {
+        let refresh_kind = if self.primed {
+            ProcessRefreshKind::nothing().with_memory().with_cpu()
+        } else {
+            ProcessRefreshKind::everything()
+        };
+        self.primed = true;
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
 
-        let total_memory = self.sys.total_memory();
-        let used_memory = self.sys.used_memory();
+        let mut flat_processes = Vec::with_capacity(sys.processes().len());
 
-        let mut flat_processes = Vec::new();
-
-        for (pid, process) in self.sys.processes() {
+        for (pid, process) in sys.processes() {
             let pid_u32 = pid.as_u32();
             let ppid_u32 = process.parent().map(|p| p.as_u32()).unwrap_or(0);
 
@@ -83,7 +91,13 @@ impl Collector {
             let group_id = process.group_id().map(|gid| format!("{gid:?}"));
             let status = map_process_status(process.status());
 
-            let info = ProcessInfo {
+            let extras = platform::process_extras(pid_u32, proportional);
+            let mut memory_detail = extras.memory;
+            memory_detail
+                .virtual_bytes
+                .get_or_insert(process.virtual_memory());
+
+            flat_processes.push(ProcessInfo {
                 pid: pid_u32,
                 ppid: ppid_u32,
                 name,
@@ -94,14 +108,148 @@ impl Collector {
                 group_id,
                 status,
                 children: Vec::new(),
-                group_name: platform::process_group_name(pid_u32),
-                priority: platform::process_priority(pid_u32),
-                io_stats: platform::process_io(pid_u32),
-            };
+                group_name: extras.group_name,
+                priority: extras.priority,
+                io_stats: extras.io_stats,
+                memory_detail,
+                container: extras.container,
+                io_rate: None,
+                fd_count: extras.fd_count,
+                start_time: extras.start_time.or(Some(process.start_time())),
+                // Same sources as the identity check before signalling
+                start_ticks: extras.start_ticks,
+                cpu_time: extras.cpu_time,
+            });
+        }
+
+        flat_processes
+    }
+}
+
+/// Turns cumulative I/O counters into per-second rates by remembering the
+/// previous sample of every PID.
+#[derive(Debug, Default)]
+struct IoRateTracker {
+    previous: HashMap<u32, IoStats>,
+    last_sample: Option<Instant>,
+}
 
-            flat_processes.push(info);
+impl IoRateTracker {
+    fn apply(&mut self, processes: &mut [ProcessInfo], now: Instant) {
+        let elapsed = self
+            .last_sample
+            .map(|last| now.duration_since(last).as_secs_f64())
+            .filter(|secs| *secs > 0.0);
+        let mut current = HashMap::with_capacity(processes.len());
+
+        for process in processes.iter_mut() {
+            let Some(io) = process.io_stats else {
+                continue;
+            };
+            if let (Some(secs), Some(prev)) = (elapsed, self.previous.get(&process.pid)) {
+                // A reused PID starts from lower counters; saturate to zero
+                // instead of reporting a bogus spike.
+                let per_sec =
+                    |now: u64, before: u64| (now.saturating_sub(before) as f64 / secs) as u64;
+                process.io_rate = Some(IoRate {
+                    read_per_sec: per_sec(io.read_bytes, prev.read_bytes),
+                    write_per_sec: per_sec(io.write_bytes, prev.write_bytes),
+                });
+            }
+            current.insert(process.pid, io);
         }
 
+        self.previous = current;
+        self.last_sample = Some(now);
+    }
+}
+
+pub struct Collector {
+    sys: System,
+    backend: Box<dyn CollectorBackend>,
+    memory_metric: MemoryMetric,
+    io_rates: IoRateTracker,
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        Self::with_backend(BackendKind::Auto)
+    }
+
+    pub fn with_backend(kind: BackendKind) -> Self {
+        let backend: Box<dyn CollectorBackend> = match kind {
+            BackendKind::Sysinfo => Box::new(SysinfoBackend::default()),
+            BackendKind::Auto | BackendKind::Native => {
+                platform::native_backend().unwrap_or_else(|| Box::new(SysinfoBackend::default()))
+            }
+        };
+
+        let mut sys = System::new();
+        sys.refresh_memory();
+        sys.refresh_cpu_all();
+
+        let mut collector = Collector {
+            sys,
+            backend,
+            memory_metric: MemoryMetric::default(),
+            io_rates: IoRateTracker::default(),
+        };
+        // Prime per-process counters so the first real refresh has CPU and
+        // I/O baselines.
+        let mut primed = collector
+            .backend
+            .collect_processes(&mut collector.sys, false);
+        collector.io_rates.apply(&mut primed, Instant::now());
+        collector
+    }
+
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// Selects the metric later refreshes must be able to report.
+    pub fn set_memory_metric(&mut self, metric: MemoryMetric) {
+        self.memory_metric = metric;
+    }
+
+    /// Refreshes sysinfo's entries for `pids` and returns the underlying system.
+    ///
+    /// Native backends never populate sysinfo's process table, so signal
+    /// delivery looks the targets up on demand instead of trusting the last tick.
+    /// Owners are read too, for the `user:` protection rules.
+    pub fn system_for_pids(&mut self, pids: &[u32]) -> &System {
+        let pids: Vec<Pid> = pids.iter().copied().map(Pid::from_u32).collect();
+        self.sys.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&pids),
+            true,
+            ProcessRefreshKind::nothing().with_user(UpdateKind::Always),
+        );
+        &self.sys
+    }
+
+    pub fn refresh(&mut self) -> SystemSnapshot {
+        #[cfg(feature = "perf-tracing")]
+        let _refresh_span = tracing::debug_span!("collector.refresh").entered();
+
+        self.sys.refresh_memory();
+        self.sys.refresh_cpu_all();
+        let mut processes = self
+            .backend
+            .collect_processes(&mut self.sys, self.memory_metric.needs_smaps());
+        self.io_rates.apply(&mut processes, Instant::now());
+        self.build_snapshot(processes)
+    }
+
+    fn build_snapshot(&self, flat_processes: Vec<ProcessInfo>) -> SystemSnapshot {
+        #[cfg(feature = "perf-tracing")]
+        let _snapshot_span = tracing::debug_span!("collector.build_snapshot").entered();
+
         let process_tree = build_process_tree_from_flat(flat_processes);
 
         let cpu_per_core: Vec<f32> = self.sys.cpus().iter().map(|c| c.cpu_usage()).collect();
@@ -111,8 +259,8 @@ impl Collector {
 
         SystemSnapshot {
             cpu_usage_percent: self.sys.global_cpu_usage(),
-            memory_total: total_memory,
-            memory_used: used_memory,
+            memory_total: self.sys.total_memory(),
+            memory_used: self.sys.used_memory(),
             swap_total: self.sys.total_swap(),
             swap_used: self.sys.used_swap(),
             cpu_per_core,
@@ -121,3 +269,90 @@ impl Collector {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backend_kind_from_config() {
+        assert_eq!(
+            BackendKind::from_str_config("sysinfo"),
+            BackendKind::Sysinfo
+        );
+        assert_eq!(BackendKind::from_str_config("procfs"), BackendKind::Native);
+        assert_eq!(BackendKind::from_str_config("Native"), BackendKind::Native);
+        assert_eq!(BackendKind::from_str_config("auto"), BackendKind::Auto);
+        assert_eq!(BackendKind::from_str_config("bogus"), BackendKind::Auto);
+    }
+
+    fn process_with_io(pid: u32, read_bytes: u64, write_bytes: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            ppid: 0,
+            name: String::new(),
+            command: String::new(),
+            memory_bytes: 0,
+            cpu_percent: 0.0,
+            user_id: None,
+            group_id: None,
+            status: ProcessState::Running,
+            children: Vec::new(),
+            group_name: None,
+            priority: None,
+            io_stats: Some(IoStats {
+                read_bytes,
+                write_bytes,
+            }),
+            memory_detail: Default::default(),
+            container: None,
+            io_rate: None,
+            fd_count: None,
+            start_time: None,
+            start_ticks: None,
+            cpu_time: None,
+        }
+    }
+
+    #[test]
+    fn io_rates_from_consecutive_samples() {
+        let mut tracker = IoRateTracker::default();
+        let start = Instant::now();
+        let mut first = vec![process_with_io(1, 1000, 0), process_with_io(2, 5000, 5000)];
+        tracker.apply(&mut first, start);
+        assert!(first.iter().all(|p| p.io_rate.is_none()));
+
+        let mut second = vec![
+            process_with_io(1, 3000, 4000),
+            // PID 2 was reused by a process with fresh counters
+            process_with_io(2, 100, 100),
+            process_with_io(3, 9000, 9000),
+        ];
+        tracker.apply(&mut second, start + std::time::Duration::from_secs(2));
+        assert_eq!(
+            second[0].io_rate,
+            Some(IoRate {
+                read_per_sec: 1000,
+                write_per_sec: 2000,
+            })
+        );
+        assert_eq!(second[1].io_rate.map(IoRate::total), Some(0));
+        assert_eq!(second[2].io_rate, None, "new PIDs need a baseline");
+    }
+
+    #[test]
+    fn both_backends_see_current_process() {
+        let pid = std::process::id();
+        for kind in [BackendKind::Sysinfo, BackendKind::Native] {
+            let mut collector = Collector::with_backend(kind);
+            let snapshot = collector.refresh();
+            let process = snapshot
+                .process_tree
+                .processes
+                .get(&pid)
+                .unwrap_or_else(|| panic!("{} backend missed own PID", collector.backend_name()));
+            assert!(process.memory_bytes > 0);
+            assert!(process.memory_detail.virtual_bytes.is_some());
+        }
+    }
+}
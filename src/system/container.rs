@@ -0,0 +1,242 @@
+use serde::{Deserialize, Serialize};
+
+/// First tile id used for container/pod group tiles, below the memory-map
+/// range and far above any kernel PID.
+pub const GROUP_TILE_BASE: u32 = 0xE000_0000;
+
+pub fn group_tile_id(index: usize) -> u32 {
+    GROUP_TILE_BASE + index as u32
+}
+
+pub fn is_group_tile(id: u32) -> bool {
+    (GROUP_TILE_BASE..crate::system::memmap::REGION_TILE_BASE).contains(&id)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+    Containerd,
+    CriO,
+    Lxc,
+}
+
+impl ContainerRuntime {
+    pub fn label(self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Containerd => "containerd",
+            ContainerRuntime::CriO => "cri-o",
+            ContainerRuntime::Lxc => "lxc",
+        }
+    }
+}
+
+/// Kubernetes pod QoS class, encoded in the kubepods cgroup hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QosClass {
+    Guaranteed,
+    Burstable,
+    BestEffort,
+}
+
+impl QosClass {
+    pub fn label(self) -> &'static str {
+        match self {
+            QosClass::Guaranteed => "guaranteed",
+            QosClass::Burstable => "burstable",
+            QosClass::BestEffort => "besteffort",
+        }
+    }
+}
+
+/// Everything the cgroup path says about where a process runs.
+///
+/// Host processes usually only carry a `systemd_unit`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContainerInfo {
+    pub cgroup_path: String,
+    pub runtime: Option<ContainerRuntime>,
+    pub container_id: Option<String>,
+    pub pod_uid: Option<String>,
+    pub qos: Option<QosClass>,
+    pub systemd_unit: Option<String>,
+}
+
+impl ContainerInfo {
+    /// Key shared by every process of the same pod, or of the same container
+    /// outside Kubernetes. `None` for host processes.
+    pub fn group_key(&self) -> Option<String> {
+        if let Some(uid) = &self.pod_uid {
+            return Some(format!("pod:{uid}"));
+        }
+        self.container_id.as_ref().map(|id| {
+            let runtime = self.runtime.map(|r| r.label()).unwrap_or("container");
+            format!("{runtime}:{id}")
+        })
+    }
+
+    /// Short human-readable name for tiles and the detail panel.
+    pub fn display_name(&self) -> Option<String> {
+        if let Some(uid) = &self.pod_uid {
+            let qos = self.qos.map(|q| format!(" ({})", q.label()));
+            // First UUID group is what `kubectl` users recognize
+            let short = uid.split('-').next().unwrap_or(uid);
+            return Some(format!("pod {short}{}", qos.unwrap_or_default()));
+        }
+        if let Some(id) = &self.container_id {
+            let runtime = self.runtime.map(|r| r.label()).unwrap_or("container");
+            return Some(format!("{runtime} {}", short_id(id)));
+        }
+        self.systemd_unit.clone()
+    }
+}
+
+fn short_id(id: &str) -> &str {
+    // Hex IDs are shortened like `docker ps`; names (LXC) are kept whole
+    if id.len() > 12 && id.bytes().all(|b| b.is_ascii_hexdigit()) {
+        &id[..12]
+    } else {
+        id
+    }
+}
+
+/// Picks the relevant hierarchy out of a `/proc/<pid>/cgroup` file and parses it.
+///
+/// cgroup v2 has a single `0::/path` line. On v1 the memory controller's path
+/// is used, then systemd's named hierarchy, then whatever comes last.
+pub fn parse_proc_cgroup(contents: &str) -> Option<ContainerInfo> {
+    let mut fallback = None;
+    let mut systemd = None;
+    for line in contents.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (Some(id), Some(controllers), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        if id == "0" && controllers.is_empty() {
+            return Some(parse_cgroup_path(path));
+        }
+        if controllers.split(',').any(|c| c == "memory") {
+            return Some(parse_cgroup_path(path));
+        }
+        if controllers == "name=systemd" {
+            systemd = Some(path);
+        }
+        fallback = Some(path);
+    }
+    systemd.or(fallback).map(parse_cgroup_path)
+}
+
+/// Parses a single cgroup path such as
+/// `/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod<uid>.slice/cri-containerd-<id>.scope`.
+pub fn parse_cgroup_path(path: &str) -> ContainerInfo {
+    let mut info = ContainerInfo {
+        cgroup_path: path.to_string(),
+        ..ContainerInfo::default()
+    };
+    let mut in_kubepods = false;
+    let mut prev = "";
+
+    for seg in path.split('/').filter(|s| !s.is_empty()) {
+        if seg.ends_with(".service") || seg.ends_with(".scope") {
+            info.systemd_unit = Some(seg.to_string());
+        }
+
+        if seg.starts_with("kubepods") {
+            in_kubepods = true;
+            if seg.contains("burstable") {
+                info.qos = Some(QosClass::Burstable);
+            } else if seg.contains("besteffort") {
+                info.qos = Some(QosClass::BestEffort);
+            }
+            if let Some(uid) = seg
+                .strip_suffix(".slice")
+                .and_then(|s| s.rsplit_once('-'))
+                .and_then(|(_, last)| last.strip_prefix("pod"))
+            {
+                // systemd escapes the dashes of the pod UID as underscores
+                info.pod_uid = Some(uid.replace('_', "-"));
+            }
+        } else if in_kubepods && (seg == "burstable" || seg == "besteffort") {
+            info.qos = Some(if seg == "burstable" {
+                QosClass::Burstable
+            } else {
+                QosClass::BestEffort
+            });
+        } else if let Some(uid) = seg.strip_prefix("pod").filter(|_| in_kubepods) {
+            info.pod_uid = Some(uid.to_string());
+        } else if let Some((runtime, id)) = container_scope(seg) {
+            info.runtime = Some(runtime);
+            info.container_id = Some(id.to_string());
+        } else if let Some(name) = seg.strip_prefix("lxc.payload.") {
+            info.runtime = Some(ContainerRuntime::Lxc);
+            info.container_id = Some(name.to_string());
+        } else if prev == "lxc" {
+            info.runtime = Some(ContainerRuntime::Lxc);
+            info.container_id = Some(seg.to_string());
+        } else if is_container_hash(seg) && (prev == "docker" || info.pod_uid.is_some()) {
+            if prev == "docker" {
+                info.runtime = Some(ContainerRuntime::Docker);
+            }
+            info.container_id = Some(seg.to_string());
+        }
+        prev = seg;
+    }
+
+    if info.pod_uid.is_some() && info.qos.is_none() {
+        // Guaranteed pods sit directly under kubepods with no QoS level
+        info.qos = Some(QosClass::Guaranteed);
+    }
+    info
+}
+
+/// Recognizes systemd scopes that runtimes create per container.
+fn container_scope(seg: &str) -> Option<(ContainerRuntime, &str)> {
+    let name = seg.strip_suffix(".scope")?;
+    const PREFIXES: [(&str, ContainerRuntime); 6] = [
+        ("docker-", ContainerRuntime::Docker),
+        ("libpod-conmon-", ContainerRuntime::Podman),
+        ("libpod-", ContainerRuntime::Podman),
+        ("cri-containerd-", ContainerRuntime::Containerd),
+        ("crio-conmon-", ContainerRuntime::CriO),
+        ("crio-", ContainerRuntime::CriO),
+    ];
+    PREFIXES.iter().find_map(|(prefix, runtime)| {
+        name.strip_prefix(prefix)
+            .filter(|id| is_container_hash(id))
+            .map(|id| (*runtime, id))
+    })
+}
+
+fn is_container_hash(s: &str) -> bool {
+    s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_service_has_only_a_unit() {
+        let info = parse_cgroup_path("/system.slice/nginx.service");
+        assert_eq!(info.systemd_unit.as_deref(), Some("nginx.service"));
+        assert_eq!(info.group_key(), None);
+        assert_eq!(info.display_name().as_deref(), Some("nginx.service"));
+    }
+
+    #[test]
+    fn container_scope_requires_full_hash() {
+        assert!(container_scope("docker-abc.scope").is_none());
+        assert!(container_scope("session-2.scope").is_none());
+    }
+
+    #[test]
+    fn group_tiles_stay_below_region_tiles() {
+        assert!(is_group_tile(group_tile_id(3)));
+        assert!(!is_group_tile(crate::system::memmap::region_tile_id(0)));
+        assert!(!is_group_tile(1));
+    }
+}
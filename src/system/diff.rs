@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use crate::system::process::{MemoryMetric, ProcessInfo, ProcessTree, SizeMode};
+
+/// A process tree kept to compare later snapshots against.
+#[derive(Debug, Clone)]
+pub struct Baseline {
+    /// How the header names the baseline, e.g. `startup`.
+    pub label: String,
+    pub tree: ProcessTree,
+}
+
+/// What happened to a process between the baseline and now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    /// Running in both snapshots.
+    Kept,
+    /// Started after the baseline.
+    New,
+    /// Gone since the baseline.
+    Exited,
+}
+
+/// Memory of one process before and after, alone and with its
+/// descendants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessDelta {
+    pub change: Change,
+    pub before: u64,
+    pub after: u64,
+    pub subtree_before: u64,
+    pub subtree_after: u64,
+}
+
+impl ProcessDelta {
+    /// Change in the process's own memory.
+    pub fn own(&self) -> i128 {
+        i128::from(self.after) - i128::from(self.before)
+    }
+
+    /// Change in the memory of the process and its descendants.
+    pub fn subtree(&self) -> i128 {
+        i128::from(self.subtree_after) - i128::from(self.subtree_before)
+    }
+}
+
+/// Per-process memory change from a baseline tree to the current one.
+///
+/// Processes are matched by PID and start time; a PID reused by a later
+/// process counts as new, and the earlier holder is not shown.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotDiff {
+    pub deltas: HashMap<u32, ProcessDelta>,
+    /// Baseline entries of exited processes, so their tiles keep a name.
+    pub exited: HashMap<u32, ProcessInfo>,
+    /// Children of each process in either tree.
+    children: HashMap<u32, Vec<u32>>,
+}
+
+impl SnapshotDiff {
+    pub fn between(before: &ProcessTree, after: &ProcessTree, metric: MemoryMetric) -> Self {
+        let sizes_before = before.all_subtree_sizes(SizeMode::Memory, metric);
+        let sizes_after = after.all_subtree_sizes(SizeMode::Memory, metric);
+        let mut deltas = HashMap::with_capacity(after.processes.len());
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+
+        for p in after.processes.values() {
+            let old = before
+                .processes
+                .get(&p.pid)
+                .filter(|old| p.identity().matches(old.identity().start));
+            let (change, before_own, before_subtree) = match old {
+                Some(old) => (
+                    Change::Kept,
+                    old.memory_for(metric),
+                    sizes_before.get(&p.pid).copied().unwrap_or(0),
+                ),
+                None => (Change::New, 0, 0),
+            };
+            deltas.insert(
+                p.pid,
+                ProcessDelta {
+                    change,
+                    before: before_own,
+                    after: p.memory_for(metric),
+                    subtree_before: before_subtree,
+                    subtree_after: sizes_after.get(&p.pid).copied().unwrap_or(0),
+                },
+            );
+            children.insert(p.pid, p.children.clone());
+        }
+
+        let exited: HashMap<u32, ProcessInfo> = before
+            .processes
+            .values()
+            .filter(|p| !after.processes.contains_key(&p.pid))
+            .map(|p| (p.pid, p.clone()))
+            .collect();
+        for p in exited.values() {
+            deltas.insert(
+                p.pid,
+                ProcessDelta {
+                    change: Change::Exited,
+                    before: p.memory_for(metric),
+                    after: 0,
+                    subtree_before: sizes_before.get(&p.pid).copied().unwrap_or(0),
+                    subtree_after: 0,
+                },
+            );
+            // A parent PID now held by an unrelated process is not the parent
+            let parent_known = exited.contains_key(&p.ppid)
+                || deltas
+                    .get(&p.ppid)
+                    .is_some_and(|d| d.change == Change::Kept);
+            if parent_known {
+                children.entry(p.ppid).or_default().push(p.pid);
+            }
+        }
+        for list in children.values_mut() {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        Self {
+            deltas,
+            exited,
+            children,
+        }
+    }
+
+    /// Children of `pid` in either snapshot, exited ones included.
+    pub fn children(&self, pid: u32) -> &[u32] {
+        self.children.get(&pid).map_or(&[], Vec::as_slice)
+    }
+
+    /// Total growth and total shrinkage of individual processes, in bytes.
+    pub fn totals(&self) -> (u64, u64) {
+        self.deltas
+            .values()
+            .fold((0, 0), |(grown, shrunk), d| match d.own() {
+                n if n > 0 => (grown + n as u64, shrunk),
+                n => (grown, shrunk + n.unsigned_abs() as u64),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::process::{MemoryDetail, ProcessState, build_process_tree_from_flat};
+
+    fn process(pid: u32, ppid: u32, memory_bytes: u64, start_time: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            ppid,
+            name: format!("p{pid}"),
+            command: String::new(),
+            memory_bytes,
+            cpu_percent: 0.0,
+            user_id: None,
+            group_id: None,
+            status: ProcessState::Running,
+            children: vec![],
+            group_name: None,
+            priority: None,
+            io_stats: None,
+            memory_detail: MemoryDetail::default(),
+            container: None,
+            io_rate: None,
+            fd_count: None,
+            start_time: Some(start_time),
+            start_ticks: None,
+            cpu_time: None,
+        }
+    }
+
+    #[test]
+    fn diff_tracks_growth_new_and_exited_processes() {
+        let before = build_process_tree_from_flat(vec![
+            process(1, 0, 100, 1),
+            process(2, 1, 50, 2),
+            process(3, 1, 40, 3),
+            process(4, 1, 10, 4),
+        ]);
+        let after = build_process_tree_from_flat(vec![
+            process(1, 0, 100, 1),
+            process(2, 1, 80, 2),
+            process(4, 1, 30, 9),
+            process(5, 2, 20, 10),
+        ]);
+        let diff = SnapshotDiff::between(&before, &after, MemoryMetric::Rss);
+
+        let grown = diff.deltas[&2];
+        assert_eq!(
+            (grown.change, grown.own(), grown.subtree()),
+            (Change::Kept, 30, 50)
+        );
+        assert_eq!(diff.deltas[&3].change, Change::Exited);
+        assert_eq!(diff.deltas[&3].own(), -40);
+        assert_eq!(diff.exited[&3].name, "p3");
+        assert_eq!(diff.deltas[&5].change, Change::New);
+        let reused = diff.deltas[&4];
+        assert_eq!((reused.change, reused.before), (Change::New, 0));
+
+        assert_eq!(diff.children(1), &[2, 3, 4]);
+        // 200 before, 230 after across the whole tree
+        assert_eq!(diff.deltas[&1].subtree(), 30);
+        assert_eq!(diff.totals(), (30 + 30 + 20, 40));
+    }
+}
@@ -0,0 +1,340 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::system::process::{IoRate, MemoryMetric, ProcessInfo, SizeMode};
+use crate::system::snapshot::SystemSnapshot;
+
+/// One process as written by exports.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportProcess {
+    pub pid: u32,
+    pub ppid: u32,
+    pub name: String,
+    pub command: String,
+    pub user: Option<String>,
+    pub status: String,
+    /// Seconds since the Unix epoch.
+    pub start_time: Option<u64>,
+    pub memory_bytes: u64,
+    pub pss_bytes: Option<u64>,
+    pub uss_bytes: Option<u64>,
+    pub swap_bytes: Option<u64>,
+    pub cpu_percent: f32,
+    pub fd_count: Option<u32>,
+}
+
+impl From<&ProcessInfo> for ExportProcess {
+    fn from(p: &ProcessInfo) -> Self {
+        Self {
+            pid: p.pid,
+            ppid: p.ppid,
+            name: p.name.clone(),
+            command: p.command.clone(),
+            user: p.user_id.clone(),
+            status: p.status.to_string(),
+            start_time: p.start_time,
+            memory_bytes: p.memory_bytes,
+            pss_bytes: p.memory_detail.pss,
+            uss_bytes: p.memory_detail.uss,
+            swap_bytes: p.memory_detail.swap,
+            cpu_percent: p.cpu_percent,
+            fd_count: p.fd_count,
+        }
+    }
+}
+
+/// Writes `records` to `path` as a pretty-printed JSON array.
+pub fn write_json(path: &Path, records: &[ExportProcess]) -> io::Result<()> {
+    let mut json = serde_json::to_string_pretty(records)?;
+    json.push('\n');
+    fs::write(path, json)
+}
+
+/// A process of a full-tree snapshot: its own figures, its children and the
+/// totals of its subtree, as the treemap aggregates them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TreeExportProcess {
+    #[serde(flatten)]
+    pub process: ExportProcess,
+    pub group: Option<String>,
+    pub priority: Option<i32>,
+    pub virtual_bytes: Option<u64>,
+    pub io_read_bytes: Option<u64>,
+    pub io_write_bytes: Option<u64>,
+    pub io_read_per_sec: Option<u64>,
+    pub io_write_per_sec: Option<u64>,
+    /// User plus system CPU time, in seconds.
+    pub cpu_time_secs: Option<f64>,
+    pub container: Option<String>,
+    /// Memory of the process and all of its descendants, under the
+    /// snapshot's `memory_metric`.
+    pub subtree_memory_bytes: u64,
+    /// I/O rate of the process and all of its descendants.
+    pub subtree_io_per_sec: u64,
+    pub children: Vec<u32>,
+}
+
+/// Everything `treetop snapshot` prints for one refresh.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SnapshotRecord {
+    /// Milliseconds since the Unix epoch.
+    pub time_ms: u64,
+    pub cpu_usage_percent: f32,
+    pub memory_total: u64,
+    pub memory_used: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+    pub cpu_per_core: Vec<f32>,
+    pub load_average: [f64; 3],
+    /// Metric the subtree memory totals add up, as named in the config,
+    /// e.g. `pss`.
+    pub memory_metric: String,
+    /// Ordered by PID.
+    pub processes: Vec<TreeExportProcess>,
+}
+
+impl SnapshotRecord {
+    /// The snapshot with subtree memory totals under `metric`.
+    pub fn new(snapshot: &SystemSnapshot, time_ms: u64, metric: MemoryMetric) -> Self {
+        let tree = &snapshot.process_tree;
+        let memory = tree.all_subtree_sizes(SizeMode::Memory, metric);
+        let io = tree.all_subtree_sizes(SizeMode::IoRate, metric);
+        let mut processes: Vec<TreeExportProcess> = tree
+            .processes
+            .values()
+            .map(|p| TreeExportProcess {
+                process: ExportProcess::from(p),
+                group: p.group_name.clone().or_else(|| p.group_id.clone()),
+                priority: p.priority,
+                virtual_bytes: p.memory_detail.virtual_bytes,
+                io_read_bytes: p.io_stats.map(|s| s.read_bytes),
+                io_write_bytes: p.io_stats.map(|s| s.write_bytes),
+                io_read_per_sec: p.io_rate.map(|r| r.read_per_sec),
+                io_write_per_sec: p.io_rate.map(|r| r.write_per_sec),
+                cpu_time_secs: p.cpu_time.map(|t| t.total().as_secs_f64()),
+                container: p.container.as_ref().and_then(|c| c.display_name()),
+                subtree_memory_bytes: memory
+                    .get(&p.pid)
+                    .copied()
+                    .unwrap_or_else(|| p.memory_for(metric)),
+                subtree_io_per_sec: io
+                    .get(&p.pid)
+                    .copied()
+                    .unwrap_or_else(|| p.io_rate.map_or(0, IoRate::total)),
+                children: p.children.clone(),
+            })
+            .collect();
+        processes.sort_by_key(|r| r.process.pid);
+        Self {
+            time_ms,
+            cpu_usage_percent: snapshot.cpu_usage_percent,
+            memory_total: snapshot.memory_total,
+            memory_used: snapshot.memory_used,
+            swap_total: snapshot.swap_total,
+            swap_used: snapshot.swap_used,
+            cpu_per_core: snapshot.cpu_per_core.clone(),
+            load_average: snapshot.load_average,
+            memory_metric: metric.label().to_lowercase(),
+            processes,
+        }
+    }
+}
+
+/// Column names of [`write_csv`], in order.
+const CSV_COLUMNS: [&str; 26] = [
+    "pid",
+    "ppid",
+    "name",
+    "command",
+    "user",
+    "group",
+    "status",
+    "start_time",
+    "priority",
+    "memory_bytes",
+    "pss_bytes",
+    "uss_bytes",
+    "swap_bytes",
+    "virtual_bytes",
+    "cpu_percent",
+    "cpu_time_secs",
+    "io_read_bytes",
+    "io_write_bytes",
+    "io_read_per_sec",
+    "io_write_per_sec",
+    "fd_count",
+    "container",
+    "subtree_memory_bytes",
+    "subtree_memory_metric",
+    "subtree_io_per_sec",
+    "children",
+];
+
+/// Writes the processes of `record` as CSV with a header row. Unknown
+/// values are empty and children are separated by `;`.
+pub fn write_csv(out: &mut impl Write, record: &SnapshotRecord) -> io::Result<()> {
+    writeln!(out, "{}", CSV_COLUMNS.join(","))?;
+    for r in &record.processes {
+        let p = &r.process;
+        let opt = |v: Option<String>| v.unwrap_or_default();
+        let children: Vec<String> = r.children.iter().map(u32::to_string).collect();
+        let fields = [
+            p.pid.to_string(),
+            p.ppid.to_string(),
+            p.name.clone(),
+            p.command.clone(),
+            opt(p.user.clone()),
+            opt(r.group.clone()),
+            p.status.clone(),
+            opt(p.start_time.map(|t| t.to_string())),
+            opt(r.priority.map(|v| v.to_string())),
+            p.memory_bytes.to_string(),
+            opt(p.pss_bytes.map(|v| v.to_string())),
+            opt(p.uss_bytes.map(|v| v.to_string())),
+            opt(p.swap_bytes.map(|v| v.to_string())),
+            opt(r.virtual_bytes.map(|v| v.to_string())),
+            p.cpu_percent.to_string(),
+            opt(r.cpu_time_secs.map(|v| v.to_string())),
+            opt(r.io_read_bytes.map(|v| v.to_string())),
+            opt(r.io_write_bytes.map(|v| v.to_string())),
+            opt(r.io_read_per_sec.map(|v| v.to_string())),
+            opt(r.io_write_per_sec.map(|v| v.to_string())),
+            opt(p.fd_count.map(|v| v.to_string())),
+            opt(r.container.clone()),
+            r.subtree_memory_bytes.to_string(),
+            record.memory_metric.clone(),
+            r.subtree_io_per_sec.to_string(),
+            children.join(";"),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        writeln!(out, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+/// Quotes a CSV field when it holds a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_serialize_with_stable_field_names() {
+        let record = ExportProcess {
+            pid: 42,
+            ppid: 1,
+            name: "worker".to_string(),
+            command: "worker --queue jobs".to_string(),
+            user: Some("1000".to_string()),
+            status: "Running".to_string(),
+            start_time: Some(1_740_230_000),
+            memory_bytes: 1024,
+            pss_bytes: None,
+            uss_bytes: None,
+            swap_bytes: Some(0),
+            cpu_percent: 12.5,
+            fd_count: Some(7),
+        };
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["pid"], 42);
+        assert_eq!(json["name"], "worker");
+        assert_eq!(json["pss_bytes"], serde_json::Value::Null);
+        assert_eq!(json["cpu_percent"], 12.5);
+
+        let path = std::env::temp_dir().join(format!("treetop_export_{}.json", std::process::id()));
+        write_json(&path, &[record]).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert!(written.starts_with('['));
+        assert!(written.contains("\"command\": \"worker --queue jobs\""));
+    }
+
+    #[test]
+    fn snapshot_records_carry_subtree_totals_and_quote_csv() {
+        use crate::system::process::{
+            IoRate, MemoryDetail, ProcessState, build_process_tree_from_flat,
+        };
+
+        let process = |pid: u32, ppid: u32, command: &str, memory_bytes: u64| ProcessInfo {
+            pid,
+            ppid,
+            name: format!("p{pid}"),
+            command: command.to_string(),
+            memory_bytes,
+            cpu_percent: 0.0,
+            user_id: None,
+            group_id: None,
+            status: ProcessState::Running,
+            children: vec![],
+            group_name: None,
+            priority: None,
+            io_stats: None,
+            memory_detail: MemoryDetail {
+                pss: Some(memory_bytes / 2),
+                ..MemoryDetail::default()
+            },
+            container: None,
+            io_rate: Some(IoRate {
+                read_per_sec: 10,
+                write_per_sec: 0,
+            }),
+            fd_count: None,
+            start_time: None,
+            start_ticks: None,
+            cpu_time: None,
+        };
+        let snapshot = SystemSnapshot {
+            cpu_usage_percent: 5.0,
+            memory_total: 4096,
+            memory_used: 1024,
+            swap_total: 0,
+            swap_used: 0,
+            cpu_per_core: vec![5.0],
+            load_average: [0.5, 0.25, 0.0],
+            process_tree: build_process_tree_from_flat(vec![
+                process(1, 0, "init", 100),
+                process(2, 1, "sh -c \"a, b\"", 50),
+                process(3, 1, "worker", 25),
+            ]),
+        };
+        let pss = SnapshotRecord::new(&snapshot, 1_000, MemoryMetric::Pss);
+        assert_eq!(pss.memory_metric, "pss");
+        assert_eq!(pss.processes[0].subtree_memory_bytes, 87);
+
+        let record = SnapshotRecord::new(&snapshot, 1_000, MemoryMetric::Rss);
+        let root = &record.processes[0];
+        assert_eq!(root.children, [2, 3]);
+        assert_eq!(
+            (root.subtree_memory_bytes, root.subtree_io_per_sec),
+            (175, 30)
+        );
+
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["memory_total"], 4096);
+        assert_eq!(
+            json["processes"][0]["pid"], 1,
+            "process fields are flattened"
+        );
+        assert_eq!(json["processes"][0]["subtree_memory_bytes"], 175);
+        assert_eq!(json["memory_metric"], "rss");
+
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &record).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].split(',').count(), CSV_COLUMNS.len());
+        assert!(lines[1].ends_with(",175,rss,30,2;3"));
+        assert!(lines[2].starts_with("2,1,p2,\"sh -c \"\"a, b\"\"\","));
+    }
+}
@@ -0,0 +1,121 @@
+/// What an open file descriptor points at, from its `/proc/<pid>/fd` link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FdKind {
+    File,
+    Device,
+    Socket,
+    Pipe,
+    /// `anon_inode:` descriptors such as eventfd, epoll and timerfd.
+    AnonInode,
+    Other,
+}
+
+impl FdKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            FdKind::File => "file",
+            FdKind::Device => "device",
+            FdKind::Socket => "socket",
+            FdKind::Pipe => "pipe",
+            FdKind::AnonInode => "anon",
+            FdKind::Other => "other",
+        }
+    }
+
+    pub fn from_target(target: &str) -> Self {
+        if target.starts_with("socket:[") {
+            FdKind::Socket
+        } else if target.starts_with("pipe:[") {
+            FdKind::Pipe
+        } else if target.starts_with("anon_inode:") {
+            FdKind::AnonInode
+        } else if target.starts_with("/dev/") {
+            FdKind::Device
+        } else if target.starts_with('/') {
+            FdKind::File
+        } else {
+            FdKind::Other
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenFile {
+    pub fd: u32,
+    pub kind: FdKind,
+    /// Link target: a path, or `socket:[inode]`, `pipe:[inode]`, etc.
+    pub target: String,
+}
+
+impl OpenFile {
+    pub fn new(fd: u32, target: String) -> Self {
+        Self {
+            fd,
+            kind: FdKind::from_target(&target),
+            target,
+        }
+    }
+}
+
+/// Soft `Max open files` limit from `/proc/<pid>/limits`; `None` when
+/// unlimited or missing.
+pub fn parse_nofile_limit(contents: &str) -> Option<u64> {
+    let line = contents
+        .lines()
+        .find(|line| line.starts_with("Max open files"))?;
+    line["Max open files".len()..]
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: &str = "\
+Limit                     Soft Limit           Hard Limit           Units
+Max cpu time              unlimited            unlimited            seconds
+Max open files            1024                 524288               files
+Max locked memory         8388608              8388608              bytes
+";
+
+    #[test]
+    fn reads_soft_nofile_limit() {
+        assert_eq!(parse_nofile_limit(LIMITS), Some(1024));
+        assert_eq!(
+            parse_nofile_limit(
+                "Max open files            unlimited            unlimited            files\n"
+            ),
+            None
+        );
+        assert_eq!(parse_nofile_limit(""), None);
+    }
+
+    #[test]
+    fn classifies_link_targets() {
+        let kinds: Vec<FdKind> = [
+            "/var/log/nginx/access.log",
+            "/dev/null",
+            "socket:[81234]",
+            "pipe:[81235]",
+            "anon_inode:[eventpoll]",
+            "net:[4026531840]",
+        ]
+        .into_iter()
+        .map(FdKind::from_target)
+        .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                FdKind::File,
+                FdKind::Device,
+                FdKind::Socket,
+                FdKind::Pipe,
+                FdKind::AnonInode,
+                FdKind::Other,
+            ]
+        );
+    }
+}
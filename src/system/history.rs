@@ -1,35 +1,83 @@
 use std::collections::{HashMap, VecDeque};
 
 const DEFAULT_CAPACITY: usize = 60;
+/// Fewest samples, and distinct rises within them, before an fd count
+/// counts as climbing.
+const FD_LEAK_MIN_SAMPLES: usize = 10;
+const FD_LEAK_MIN_RISES: usize = 3;
+/// Smallest overall fd growth worth flagging.
+const FD_LEAK_MIN_GROWTH: u64 = 5;
+
+/// One refresh worth of per-process figures.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HistorySample {
+    pub memory: u64,
+    pub cpu: f32,
+    /// Combined read and write throughput in bytes per second.
+    pub io: u64,
+    /// Open file descriptors; 0 when unreadable.
+    pub fds: u64,
+    /// Start time of the process sampled, telling a reused PID apart.
+    pub start_time: Option<u64>,
+}
 
 #[derive(Debug, Clone)]
 pub struct ProcessHistory {
     pub memory: VecDeque<u64>,
     pub cpu: VecDeque<f32>,
+    /// Combined read and write throughput in bytes per second.
+    pub io: VecDeque<u64>,
+    pub fds: VecDeque<u64>,
+    start_time: Option<u64>,
     capacity: usize,
 }
 
 impl ProcessHistory {
-    fn new(capacity: usize) -> Self {
+    fn new(capacity: usize, start_time: Option<u64>) -> Self {
         Self {
             memory: VecDeque::with_capacity(capacity),
             cpu: VecDeque::with_capacity(capacity),
+            io: VecDeque::with_capacity(capacity),
+            fds: VecDeque::with_capacity(capacity),
+            start_time,
             capacity,
         }
     }
 
-    fn push(&mut self, memory: u64, cpu: f32) {
-        if self.memory.len() == self.capacity {
-            self.memory.pop_front();
+    fn push(&mut self, sample: HistorySample) {
+        push_capped(&mut self.memory, sample.memory, self.capacity);
+        push_capped(&mut self.cpu, sample.cpu, self.capacity);
+        push_capped(&mut self.io, sample.io, self.capacity);
+        push_capped(&mut self.fds, sample.fds, self.capacity);
+    }
+
+    /// True when the fd count never dropped across the recorded window and
+    /// rose repeatedly, which is what a slow descriptor leak looks like.
+    pub fn fd_leak_suspected(&self) -> bool {
+        if self.fds.len() < FD_LEAK_MIN_SAMPLES {
+            return false;
         }
-        if self.cpu.len() == self.capacity {
-            self.cpu.pop_front();
+        let mut rises = 0;
+        for (prev, next) in self.fds.iter().zip(self.fds.iter().skip(1)) {
+            if next < prev {
+                return false;
+            }
+            if next > prev {
+                rises += 1;
+            }
         }
-        self.memory.push_back(memory);
-        self.cpu.push_back(cpu);
+        let growth = self.fds.back().copied().unwrap_or(0) - self.fds[0];
+        rises >= FD_LEAK_MIN_RISES && growth >= FD_LEAK_MIN_GROWTH
     }
 }
 
+fn push_capped<T>(buffer: &mut VecDeque<T>, value: T, capacity: usize) {
+    if buffer.len() == capacity {
+        buffer.pop_front();
+    }
+    buffer.push_back(value);
+}
+
 #[derive(Debug)]
 pub struct HistoryStore {
     entries: HashMap<u32, ProcessHistory>,
@@ -46,17 +94,28 @@ impl HistoryStore {
         }
     }
 
-    pub fn record(&mut self, pid: u32, memory: u64, cpu: f32) {
-        self.entries
+    /// Appends a sample, starting over when the PID now belongs to a
+    /// different process than the one recorded so far.
+    pub fn record(&mut self, pid: u32, sample: HistorySample) {
+        let history = self
+            .entries
             .entry(pid)
-            .or_insert_with(|| ProcessHistory::new(self.capacity))
-            .push(memory, cpu);
+            .or_insert_with(|| ProcessHistory::new(self.capacity, sample.start_time));
+        if history.start_time != sample.start_time {
+            *history = ProcessHistory::new(self.capacity, sample.start_time);
+        }
+        history.push(sample);
     }
 
     pub fn get(&self, pid: u32) -> Option<&ProcessHistory> {
         self.entries.get(&pid)
     }
 
+    /// Forgets every process's samples.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
     /// Remove entries for PIDs that are no longer alive.
     /// Called periodically (every 10 refreshes) to avoid unbounded growth.
     pub fn gc(&mut self, alive_pids: &std::collections::HashSet<u32>) {
@@ -78,25 +137,48 @@ impl Default for HistoryStore {
 mod tests {
     use super::*;
 
+    fn sample(memory: u64, cpu: f32) -> HistorySample {
+        HistorySample {
+            memory,
+            cpu,
+            ..HistorySample::default()
+        }
+    }
+
+    fn fds(count: u64) -> HistorySample {
+        HistorySample {
+            fds: count,
+            ..HistorySample::default()
+        }
+    }
+
     #[test]
     fn history_push_get() {
         let mut store = HistoryStore::new(60);
-        store.record(1, 1000, 5.0);
-        store.record(1, 2000, 10.0);
+        store.record(1, sample(1000, 5.0));
+        store.record(
+            1,
+            HistorySample {
+                io: 4096,
+                ..sample(2000, 10.0)
+            },
+        );
         let h = store.get(1).unwrap();
         assert_eq!(h.memory.len(), 2);
         assert_eq!(h.cpu.len(), 2);
         assert_eq!(h.memory[1], 2000);
+        assert_eq!(h.io, [0, 4096]);
     }
 
     #[test]
     fn ring_buffer_caps_at_capacity() {
         let mut store = HistoryStore::new(5);
         for i in 0..10 {
-            store.record(1, i as u64, i as f32);
+            store.record(1, sample(i as u64, i as f32));
         }
         let h = store.get(1).unwrap();
         assert_eq!(h.memory.len(), 5);
+        assert_eq!(h.io.len(), 5);
         assert_eq!(h.memory[0], 5);
         assert_eq!(h.memory[4], 9);
     }
@@ -104,9 +186,9 @@ mod tests {
     #[test]
     fn gc_removes_dead_pids() {
         let mut store = HistoryStore::new(60);
-        store.record(1, 100, 1.0);
-        store.record(2, 200, 2.0);
-        store.record(3, 300, 3.0);
+        store.record(1, sample(100, 1.0));
+        store.record(2, sample(200, 2.0));
+        store.record(3, sample(300, 3.0));
 
         let mut alive = std::collections::HashSet::new();
         alive.insert(1);
@@ -120,4 +202,40 @@ mod tests {
         assert!(store.get(2).is_none());
         assert!(store.get(3).is_some());
     }
+
+    #[test]
+    fn reused_pid_starts_a_new_history() {
+        let mut store = HistoryStore::new(60);
+        let started = |start_time, memory| HistorySample {
+            start_time: Some(start_time),
+            ..sample(memory, 0.0)
+        };
+        store.record(7, started(1_000, 100));
+        store.record(7, started(1_000, 200));
+        store.record(7, started(2_500, 50));
+        assert_eq!(store.get(7).unwrap().memory, [50]);
+    }
+
+    #[test]
+    fn climbing_fd_count_is_flagged() {
+        let mut store = HistoryStore::new(60);
+        for i in 0..12 {
+            // Plateaus between rises still count as climbing
+            store.record(1, fds(100 + i / 2 * 3));
+            store.record(2, fds(100 + i % 3));
+            store.record(3, fds(40));
+        }
+        assert!(store.get(1).unwrap().fd_leak_suspected());
+        assert!(!store.get(2).unwrap().fd_leak_suspected(), "fluctuating");
+        assert!(!store.get(3).unwrap().fd_leak_suspected(), "flat");
+
+        let mut short = HistoryStore::new(60);
+        for i in 0..5 {
+            short.record(1, fds(100 + i * 10));
+        }
+        assert!(
+            !short.get(1).unwrap().fd_leak_suspected(),
+            "too few samples"
+        );
+    }
 }
@@ -1,47 +1,450 @@
 use sysinfo::{Pid, Signal, System};
 
+use crate::system::audit::AuditLog;
+use crate::system::platform;
+use crate::system::process::{ProcessIdentity, ProcessInfo};
+use crate::system::safety::SafetyPolicy;
+
+#[derive(Debug)]
 pub enum KillResult {
     Success(u32, &'static str),
-    Failed(String),
+    Failed {
+        pid: u32,
+        signal: &'static str,
+        /// OS error text, e.g. "Operation not permitted (os error 1)".
+        reason: String,
+    },
     NotFound(u32),
+    /// The PID now belongs to a different process than the one targeted;
+    /// nothing was sent.
+    PidReused(u32),
+    /// Read-only mode or a protection rule forbids signalling the process;
+    /// nothing was sent.
+    Refused {
+        pid: u32,
+        reason: String,
+    },
 }
 
-pub fn kill_process(sys: &System, pid: u32, signal: Signal) -> KillResult {
-    let sysinfo_pid = Pid::from_u32(pid);
-    match sys.process(sysinfo_pid) {
-        Some(process) => {
-            let signal_name = if cfg!(windows) {
-                match signal {
-                    Signal::Term | Signal::Kill => "Terminate",
-                    _ => "signal",
-                }
+/// A signal offered in the signal picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignalInfo {
+    pub signal: Signal,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+const fn info(signal: Signal, name: &'static str, description: &'static str) -> SignalInfo {
+    SignalInfo {
+        signal,
+        name,
+        description,
+    }
+}
+
+/// Every signal sysinfo can send, in Linux numbering order. `IOT` and
+/// `Poll` are left out as aliases of `SIGABRT` and `SIGIO`.
+pub const SIGNALS: [SignalInfo; 30] = [
+    info(Signal::Hangup, "SIGHUP", "Hangup; reload config"),
+    info(Signal::Interrupt, "SIGINT", "Interrupt, like Ctrl+C"),
+    info(Signal::Quit, "SIGQUIT", "Quit and dump core"),
+    info(Signal::Illegal, "SIGILL", "Illegal instruction"),
+    info(Signal::Trap, "SIGTRAP", "Trace/breakpoint trap"),
+    info(Signal::Abort, "SIGABRT", "Abort and dump core"),
+    info(Signal::Bus, "SIGBUS", "Bus error"),
+    info(
+        Signal::FloatingPointException,
+        "SIGFPE",
+        "Arithmetic exception",
+    ),
+    info(Signal::Kill, "SIGKILL", "Kill; cannot be caught"),
+    info(Signal::User1, "SIGUSR1", "User-defined 1"),
+    info(Signal::Segv, "SIGSEGV", "Invalid memory reference"),
+    info(Signal::User2, "SIGUSR2", "User-defined 2"),
+    info(Signal::Pipe, "SIGPIPE", "Broken pipe"),
+    info(Signal::Alarm, "SIGALRM", "Timer expired"),
+    info(Signal::Term, "SIGTERM", "Terminate gracefully"),
+    info(Signal::Child, "SIGCHLD", "Child stopped or exited"),
+    info(Signal::Continue, "SIGCONT", "Continue if stopped"),
+    info(Signal::Stop, "SIGSTOP", "Stop; cannot be caught"),
+    info(Signal::TSTP, "SIGTSTP", "Stop from terminal"),
+    info(Signal::TTIN, "SIGTTIN", "Background tty read"),
+    info(Signal::TTOU, "SIGTTOU", "Background tty write"),
+    info(Signal::Urgent, "SIGURG", "Urgent socket data"),
+    info(Signal::XCPU, "SIGXCPU", "CPU time limit exceeded"),
+    info(Signal::XFSZ, "SIGXFSZ", "File size limit exceeded"),
+    info(Signal::VirtualAlarm, "SIGVTALRM", "Virtual timer expired"),
+    info(Signal::Profiling, "SIGPROF", "Profiling timer expired"),
+    info(Signal::Winch, "SIGWINCH", "Window resized"),
+    info(Signal::IO, "SIGIO", "I/O possible"),
+    info(Signal::Power, "SIGPWR", "Power failure"),
+    info(Signal::Sys, "SIGSYS", "Bad system call"),
+];
+
+pub fn signal_name(signal: Signal) -> &'static str {
+    match signal {
+        Signal::IOT => "SIGIOT",
+        Signal::Poll => "SIGPOLL",
+        _ => SIGNALS
+            .iter()
+            .find(|s| s.signal == signal)
+            .map_or("signal", |s| s.name),
+    }
+}
+
+/// Parses a signal name as written in the config: `SIGKILL`, `kill` and
+/// `Kill` all name the same signal.
+pub fn signal_from_name(name: &str) -> Option<Signal> {
+    let name = name.trim().to_ascii_uppercase();
+    let name = name.strip_prefix("SIG").unwrap_or(&name);
+    SIGNALS
+        .iter()
+        .find(|s| &s.name[3..] == name)
+        .map(|s| s.signal)
+}
+
+/// Signal number on this platform; `None` where signals are not numbered
+/// (Windows) or the signal does not exist.
+pub fn signal_number(signal: Signal) -> Option<i32> {
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    {
+        unix_signal_number(signal)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = signal;
+        None
+    }
+}
+
+/// Signals this platform can deliver, lowest number first.
+pub fn available_signals() -> Vec<(SignalInfo, Option<i32>)> {
+    let mut signals: Vec<(SignalInfo, Option<i32>)> = SIGNALS
+        .iter()
+        .filter(|s| sysinfo::SUPPORTED_SIGNALS.contains(&s.signal))
+        .map(|s| (*s, signal_number(s.signal)))
+        .filter(|(_, number)| number.is_some() || cfg!(windows))
+        .collect();
+    signals.sort_by_key(|(_, number)| *number);
+    signals
+}
+
+/// Sends `signal` to `target`, the process as the snapshot shows it,
+/// refusing if `policy` forbids it or its PID has since been handed to
+/// another process.
+///
+/// On Linux the process is pinned with a pidfd before its start time is
+/// checked, so it cannot be replaced between the check and the signal.
+/// Elsewhere the check and the send are separate steps.
+///
+/// Every attempt, including refused ones, is appended to the policy's audit
+/// log.
+pub fn kill_process(
+    sys: &System,
+    target: &ProcessInfo,
+    signal: Signal,
+    policy: &SafetyPolicy,
+) -> KillResult {
+    signal_logged(sys, target, signal, "signal", policy)
+}
+
+/// [`kill_process`], logged as `action`.
+fn signal_logged(
+    sys: &System,
+    target: &ProcessInfo,
+    signal: Signal,
+    action: &str,
+    policy: &SafetyPolicy,
+) -> KillResult {
+    let signal_name = if cfg!(windows) {
+        match signal {
+            Signal::Term | Signal::Kill => "Terminate",
+            _ => signal_name(signal),
+        }
+    } else {
+        signal_name(signal)
+    };
+    let result = send(sys, target.identity(), signal, signal_name, policy);
+    if let Some(log) = policy.audit() {
+        audit(log, target, action, signal_name, &result);
+    }
+    result
+}
+
+fn send(
+    sys: &System,
+    target: ProcessIdentity,
+    signal: Signal,
+    signal_name: &'static str,
+    policy: &SafetyPolicy,
+) -> KillResult {
+    let pid = target.pid;
+    let Some(process) = sys.process(Pid::from_u32(pid)) else {
+        return KillResult::NotFound(pid);
+    };
+    if let Some(reason) = policy.refusal_in(sys, pid) {
+        return KillResult::Refused { pid, reason };
+    }
+
+    #[cfg(target_os = "linux")]
+    if let Some(result) = pidfd::send(target, signal, signal_name) {
+        return result;
+    }
+
+    let failed = |reason: String| KillResult::Failed {
+        pid,
+        signal: signal_name,
+        reason,
+    };
+    let start = platform::process_start_ticks(pid).or(Some(process.start_time()));
+    if !target.matches(start) {
+        return KillResult::PidReused(pid);
+    }
+    match process.kill_with(signal) {
+        Some(true) => KillResult::Success(pid, signal_name),
+        // sysinfo only reports success; the OS error is still current
+        Some(false) => failed(std::io::Error::last_os_error().to_string()),
+        None if matches!(signal, Signal::Kill | Signal::Term) => {
+            // Not a signal on this platform; fall back to a plain kill()
+            if process.kill() {
+                KillResult::Success(pid, signal_name)
             } else {
-                match signal {
-                    Signal::Term => "SIGTERM",
-                    Signal::Kill => "SIGKILL",
-                    _ => "signal",
+                failed(std::io::Error::last_os_error().to_string())
+            }
+        }
+        None => failed("not supported on this platform".to_string()),
+    }
+}
+
+fn audit(
+    log: &AuditLog,
+    target: &ProcessInfo,
+    action: &str,
+    signal_name: &str,
+    result: &KillResult,
+) {
+    let mut entry = log.entry_for(action, target);
+    entry.signal = signal_name.to_string();
+    let (result, detail) = match result {
+        KillResult::Success(..) => ("sent", None),
+        KillResult::NotFound(_) => ("not_found", None),
+        KillResult::PidReused(_) => ("pid_reused", None),
+        KillResult::Refused { reason, .. } => ("refused", Some(reason.clone())),
+        KillResult::Failed { reason, .. } => ("failed", Some(reason.clone())),
+    };
+    entry.result = result.to_string();
+    entry.detail = detail;
+    log.append(&entry);
+}
+
+/// Signals a whole process tree; `targets` lists every parent before its
+/// children. Returns one result per target, in the same order.
+///
+/// Unless the signal is itself SIGSTOP or SIGCONT, the tree is first frozen
+/// with SIGSTOP parent-first so nobody can fork or respawn a child halfway
+/// through. The signal then goes out leaves-first, and the frozen processes
+/// are resumed with SIGCONT so they act on it. The audit log records the
+/// two extra passes as `freeze` and `resume`.
+///
+/// A tree that includes treetop itself, as it does when rooted at one of
+/// its ancestors, is refused as a whole: a frozen treetop could never send
+/// the SIGCONT.
+pub fn kill_tree(
+    sys: &System,
+    targets: &[ProcessInfo],
+    signal: Signal,
+    policy: &SafetyPolicy,
+) -> Vec<KillResult> {
+    let own_pid = std::process::id();
+    if targets.iter().any(|t| t.pid == own_pid) {
+        return targets
+            .iter()
+            .map(|target| {
+                let result = KillResult::Refused {
+                    pid: target.pid,
+                    reason: "the tree includes treetop itself".to_string(),
+                };
+                if let Some(log) = policy.audit() {
+                    audit(log, target, "signal", signal_name(signal), &result);
                 }
+                result
+            })
+            .collect();
+    }
+
+    let freeze = !matches!(signal, Signal::Stop | Signal::Continue)
+        && sysinfo::SUPPORTED_SIGNALS.contains(&Signal::Stop);
+    let frozen: Vec<bool> = targets
+        .iter()
+        .map(|t| {
+            freeze
+                && matches!(
+                    signal_logged(sys, t, Signal::Stop, "freeze", policy),
+                    KillResult::Success(..)
+                )
+        })
+        .collect();
+
+    let mut results: Vec<KillResult> = targets
+        .iter()
+        .rev()
+        .map(|t| kill_process(sys, t, signal, policy))
+        .collect();
+    results.reverse();
+
+    for ((target, frozen), result) in targets.iter().zip(frozen).zip(&results).rev() {
+        let killed = signal == Signal::Kill && matches!(result, KillResult::Success(..));
+        if frozen && !killed {
+            signal_logged(sys, target, Signal::Continue, "resume", policy);
+        }
+    }
+    results
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn unix_signal_number(signal: Signal) -> Option<i32> {
+    Some(match signal {
+        Signal::Hangup => libc::SIGHUP,
+        Signal::Interrupt => libc::SIGINT,
+        Signal::Quit => libc::SIGQUIT,
+        Signal::Illegal => libc::SIGILL,
+        Signal::Trap => libc::SIGTRAP,
+        Signal::Abort | Signal::IOT => libc::SIGABRT,
+        Signal::Bus => libc::SIGBUS,
+        Signal::FloatingPointException => libc::SIGFPE,
+        Signal::Kill => libc::SIGKILL,
+        Signal::User1 => libc::SIGUSR1,
+        Signal::Segv => libc::SIGSEGV,
+        Signal::User2 => libc::SIGUSR2,
+        Signal::Pipe => libc::SIGPIPE,
+        Signal::Alarm => libc::SIGALRM,
+        Signal::Term => libc::SIGTERM,
+        Signal::Child => libc::SIGCHLD,
+        Signal::Continue => libc::SIGCONT,
+        Signal::Stop => libc::SIGSTOP,
+        Signal::TSTP => libc::SIGTSTP,
+        Signal::TTIN => libc::SIGTTIN,
+        Signal::TTOU => libc::SIGTTOU,
+        Signal::Urgent => libc::SIGURG,
+        Signal::XCPU => libc::SIGXCPU,
+        Signal::XFSZ => libc::SIGXFSZ,
+        Signal::VirtualAlarm => libc::SIGVTALRM,
+        Signal::Profiling => libc::SIGPROF,
+        Signal::Winch => libc::SIGWINCH,
+        Signal::IO | Signal::Poll => libc::SIGIO,
+        #[cfg(target_os = "linux")]
+        Signal::Power => libc::SIGPWR,
+        #[cfg(not(target_os = "linux"))]
+        Signal::Power => return None,
+        Signal::Sys => libc::SIGSYS,
+    })
+}
+
+#[cfg(target_os = "linux")]
+mod pidfd {
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    use sysinfo::Signal;
+
+    use super::KillResult;
+    use crate::system::platform;
+    use crate::system::process::ProcessIdentity;
+
+    /// Signals through `pidfd_open` / `pidfd_send_signal`. `None` when pidfds
+    /// are unavailable (kernels before 5.3, seccomp filters), so the caller
+    /// falls back to a plain `kill`.
+    pub(super) fn send(
+        target: ProcessIdentity,
+        signal: Signal,
+        signal_name: &'static str,
+    ) -> Option<KillResult> {
+        let pid = target.pid;
+        let signo = super::signal_number(signal)?;
+        let raw_pid = libc::pid_t::try_from(pid).ok()?;
+
+        // SAFETY: pidfd_open takes a PID and flags and returns a new fd or -1.
+        let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, raw_pid, 0) };
+        if fd < 0 {
+            return match io::Error::last_os_error().raw_os_error() {
+                Some(libc::ESRCH) => Some(KillResult::NotFound(pid)),
+                _ => None,
             };
-            match process.kill_with(signal) {
-                Some(true) => KillResult::Success(pid, signal_name),
-                Some(false) => {
-                    // Some platforms may reject a specific signal but still permit force kill.
-                    if process.kill() {
-                        KillResult::Success(pid, signal_name)
-                    } else {
-                        KillResult::Failed(format!("Failed to send {signal_name} to PID {pid}"))
-                    }
-                }
-                None => {
-                    // Signal not supported on this platform, fall back to kill()
-                    if process.kill() {
-                        KillResult::Success(pid, signal_name)
-                    } else {
-                        KillResult::Failed(format!("Failed to kill PID {pid} (permission denied?)"))
-                    }
-                }
+        }
+        // SAFETY: the syscall succeeded, so `fd` is a fresh descriptor we own.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd as libc::c_int) };
+
+        // The pidfd refers to whichever process held the PID when it was
+        // opened; if that one still has the expected start time, it is ours.
+        match platform::process_start_ticks(pid) {
+            None => return Some(KillResult::NotFound(pid)),
+            Some(start) if !target.matches(Some(start)) => {
+                return Some(KillResult::PidReused(pid));
             }
+            Some(_) => {}
+        }
+
+        // SAFETY: a null siginfo makes pidfd_send_signal behave like kill(2).
+        let rc = unsafe {
+            libc::syscall(
+                libc::SYS_pidfd_send_signal,
+                fd.as_raw_fd(),
+                signo,
+                std::ptr::null::<libc::siginfo_t>(),
+                0,
+            )
+        };
+        if rc == 0 {
+            return Some(KillResult::Success(pid, signal_name));
+        }
+        let err = io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::ENOSYS) => None,
+            Some(libc::ESRCH) => Some(KillResult::NotFound(pid)),
+            _ => Some(KillResult::Failed {
+                pid,
+                signal: signal_name,
+                reason: err.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_catalog_signal_has_its_own_name() {
+        let mut names: Vec<&str> = SIGNALS.iter().map(|s| s.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), SIGNALS.len());
+        assert_eq!(signal_name(Signal::Hangup), "SIGHUP");
+        assert_eq!(signal_name(Signal::User1), "SIGUSR1");
+        assert_eq!(signal_name(Signal::Poll), "SIGPOLL");
+    }
+
+    #[test]
+    fn signal_names_parse_with_or_without_prefix() {
+        assert_eq!(signal_from_name("SIGKILL"), Some(Signal::Kill));
+        assert_eq!(signal_from_name("term"), Some(Signal::Term));
+        assert_eq!(signal_from_name(" SigUsr1 "), Some(Signal::User1));
+        assert_eq!(signal_from_name("SIGNOPE"), None);
+        assert_eq!(signal_from_name(""), None);
+    }
+
+    #[test]
+    fn available_signals_are_sorted_by_number() {
+        let signals = available_signals();
+        assert!(signals.iter().any(|(info, _)| info.signal == Signal::Kill));
+        let numbers: Vec<Option<i32>> = signals.iter().map(|(_, n)| *n).collect();
+        assert!(numbers.is_sorted());
+        #[cfg(target_os = "linux")]
+        {
+            assert_eq!(signal_number(Signal::Hangup), Some(1));
+            assert_eq!(signal_number(Signal::User1), Some(10));
+            assert_eq!(signal_number(Signal::Term), Some(15));
         }
-        None => KillResult::NotFound(pid),
     }
 }
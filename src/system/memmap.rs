@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use super::process::MemoryMetric;
+
+/// First tile id used for memory-map regions. Kernel PIDs never reach this
+/// range, so region tiles can't be mistaken for processes.
+pub const REGION_TILE_BASE: u32 = 0xF000_0000;
+
+pub fn region_tile_id(index: usize) -> u32 {
+    REGION_TILE_BASE + index as u32
+}
+
+pub fn is_region_tile(id: u32) -> bool {
+    id >= REGION_TILE_BASE
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    Heap,
+    Stack,
+    Anon,
+    SharedLib,
+    File,
+    /// `[vdso]`, `[vvar]` and other kernel-provided mappings.
+    Kernel,
+}
+
+impl RegionKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            RegionKind::Heap => "heap",
+            RegionKind::Stack => "stack",
+            RegionKind::Anon => "anon mmap",
+            RegionKind::SharedLib => "shared lib",
+            RegionKind::File => "mapped file",
+            RegionKind::Kernel => "kernel",
+        }
+    }
+}
+
+/// One tile of the memory map: every mapping backed by the same file, or
+/// every anonymous mapping of the same kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub label: String,
+    pub kind: RegionKind,
+    pub mappings: usize,
+    pub size: u64,
+    pub rss: u64,
+    pub pss: u64,
+    pub uss: u64,
+    pub swap: u64,
+}
+
+impl MemoryRegion {
+    pub fn value(&self, metric: MemoryMetric) -> u64 {
+        match metric {
+            MemoryMetric::Rss => self.rss,
+            MemoryMetric::Pss => self.pss,
+            MemoryMetric::Uss => self.uss,
+            MemoryMetric::Swap => self.swap,
+            MemoryMetric::Virtual => self.size,
+        }
+    }
+}
+
+/// A process address space grouped into regions, largest RSS first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryMap {
+    pub pid: u32,
+    pub regions: Vec<MemoryRegion>,
+}
+
+impl MemoryMap {
+    pub fn total(&self, metric: MemoryMetric) -> u64 {
+        self.regions.iter().map(|r| r.value(metric)).sum()
+    }
+}
+
+/// Parses the contents of `/proc/<pid>/smaps` into grouped regions.
+pub fn parse_smaps(pid: u32, contents: &str) -> MemoryMap {
+    let mut regions: Vec<MemoryRegion> = Vec::new();
+    let mut index_by_key: HashMap<String, usize> = HashMap::new();
+    let mut current: Option<usize> = None;
+
+    for line in contents.lines() {
+        if is_mapping_header(line) {
+            let pathname = line
+                .split_whitespace()
+                .skip(5)
+                .collect::<Vec<_>>()
+                .join(" ");
+            let (key, label, kind) = classify(&pathname);
+            let idx = *index_by_key.entry(key).or_insert_with(|| {
+                regions.push(MemoryRegion {
+                    label,
+                    kind,
+                    mappings: 0,
+                    size: 0,
+                    rss: 0,
+                    pss: 0,
+                    uss: 0,
+                    swap: 0,
+                });
+                regions.len() - 1
+            });
+            regions[idx].mappings += 1;
+            current = Some(idx);
+            continue;
+        }
+
+        let (Some(idx), Some((key, value))) = (current, line.split_once(':')) else {
+            continue;
+        };
+        let Some(bytes) = parse_kb(value) else {
+            continue;
+        };
+        let region = &mut regions[idx];
+        match key {
+            "Size" => region.size += bytes,
+            "Rss" => region.rss += bytes,
+            "Pss" => region.pss += bytes,
+            "Private_Clean" | "Private_Dirty" => region.uss += bytes,
+            "Swap" => region.swap += bytes,
+            _ => {}
+        }
+    }
+
+    regions.sort_by_key(|r| std::cmp::Reverse(r.rss));
+    MemoryMap { pid, regions }
+}
+
+/// Mapping headers start with an `start-end` hex address range; field lines
+/// start with a `Key:` name.
+fn is_mapping_header(line: &str) -> bool {
+    line.split_whitespace()
+        .next()
+        .and_then(|range| range.split_once('-'))
+        .is_some_and(|(start, end)| {
+            !start.is_empty()
+                && start.bytes().all(|b| b.is_ascii_hexdigit())
+                && end.bytes().all(|b| b.is_ascii_hexdigit())
+        })
+}
+
+/// Returns (group key, tile label, kind) for a mapping's pathname column.
+fn classify(pathname: &str) -> (String, String, RegionKind) {
+    let simple = |label: &str, kind| (label.to_string(), label.to_string(), kind);
+    match pathname {
+        "" => simple("[anon]", RegionKind::Anon),
+        "[heap]" => simple("[heap]", RegionKind::Heap),
+        p if p == "[stack]" || p.starts_with("[stack:") => simple("[stack]", RegionKind::Stack),
+        p if p.starts_with("[anon") => simple("[anon]", RegionKind::Anon),
+        p if p.starts_with('[') => simple(p, RegionKind::Kernel),
+        path => {
+            let file_name = path.rsplit('/').next().unwrap_or(path);
+            let kind = if file_name.contains(".so") {
+                RegionKind::SharedLib
+            } else {
+                RegionKind::File
+            };
+            (path.to_string(), file_name.to_string(), kind)
+        }
+    }
+}
+
+/// Parses a `"  1234 kB"` value from `smaps` or `status` into bytes.
+pub(crate) fn parse_kb(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let kb: u64 = parts.next()?.parse().ok()?;
+    (parts.next() == Some("kB")).then_some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMAPS: &str = "\
+55d0c0a00000-55d0c0a21000 r-xp 00000000 08:01 1234                       /usr/bin/node
+Size:                132 kB
+Rss:                 100 kB
+Pss:                  50 kB
+Private_Clean:        20 kB
+Private_Dirty:         0 kB
+Swap:                  0 kB
+VmFlags: rd ex mr mw me
+55d0c1000000-55d0c2000000 rw-p 00000000 00:00 0                          [heap]
+Size:              16384 kB
+Rss:                8192 kB
+Pss:                8192 kB
+Private_Clean:         0 kB
+Private_Dirty:      8192 kB
+Swap:                512 kB
+7f0000000000-7f0000100000 rw-p 00000000 00:00 0
+Size:               1024 kB
+Rss:                 256 kB
+Pss:                 256 kB
+Private_Dirty:       256 kB
+7f0000200000-7f0000300000 rw-p 00000000 00:00 0
+Size:               1024 kB
+Rss:                 128 kB
+Pss:                 128 kB
+Private_Dirty:       128 kB
+7f1000000000-7f1000200000 r-xp 00000000 08:01 5678                       /usr/lib/x86_64-linux-gnu/libc.so.6
+Size:               2048 kB
+Rss:                1024 kB
+Pss:                  64 kB
+Private_Clean:         0 kB
+7ffc00000000-7ffc00021000 rw-p 00000000 00:00 0                          [stack]
+Size:                132 kB
+Rss:                  32 kB
+Pss:                  32 kB
+Private_Dirty:        32 kB
+7ffc00100000-7ffc00102000 r-xp 00000000 00:00 0                          [vdso]
+Size:                  8 kB
+Rss:                   4 kB
+Pss:                   0 kB
+";
+
+    fn region<'a>(map: &'a MemoryMap, label: &str) -> &'a MemoryRegion {
+        map.regions.iter().find(|r| r.label == label).unwrap()
+    }
+
+    #[test]
+    fn groups_anonymous_mappings_together() {
+        let map = parse_smaps(42, SMAPS);
+        let anon = region(&map, "[anon]");
+        assert_eq!(anon.kind, RegionKind::Anon);
+        assert_eq!(anon.mappings, 2);
+        assert_eq!(anon.rss, 384 * 1024);
+        assert_eq!(anon.size, 2048 * 1024);
+    }
+
+    #[test]
+    fn classifies_special_and_file_mappings() {
+        let map = parse_smaps(42, SMAPS);
+        assert_eq!(map.pid, 42);
+        assert_eq!(map.regions.len(), 6);
+        assert_eq!(region(&map, "[heap]").kind, RegionKind::Heap);
+        assert_eq!(region(&map, "[stack]").kind, RegionKind::Stack);
+        assert_eq!(region(&map, "[vdso]").kind, RegionKind::Kernel);
+        assert_eq!(region(&map, "libc.so.6").kind, RegionKind::SharedLib);
+        assert_eq!(region(&map, "node").kind, RegionKind::File);
+    }
+
+    #[test]
+    fn regions_sorted_by_rss_and_sized_by_metric() {
+        let map = parse_smaps(42, SMAPS);
+        assert_eq!(map.regions[0].label, "[heap]");
+        let heap = &map.regions[0];
+        assert_eq!(heap.value(MemoryMetric::Uss), 8192 * 1024);
+        assert_eq!(heap.value(MemoryMetric::Swap), 512 * 1024);
+
+        let libc = region(&map, "libc.so.6");
+        assert_eq!(libc.value(MemoryMetric::Rss), 1024 * 1024);
+        assert_eq!(libc.value(MemoryMetric::Pss), 64 * 1024);
+        assert_eq!(
+            map.total(MemoryMetric::Rss),
+            (100 + 8192 + 384 + 1024 + 32 + 4) * 1024
+        );
+    }
+
+    #[test]
+    fn vmflags_line_is_not_a_header() {
+        assert!(!is_mapping_header("VmFlags: rd ex mr mw me"));
+        assert!(is_mapping_header(
+            "7ffc00000000-7ffc00021000 rw-p 00000000 00:00 0   [stack]"
+        ));
+    }
+
+    #[test]
+    fn region_tiles_never_collide_with_pids() {
+        assert!(is_region_tile(region_tile_id(0)));
+        assert!(!is_region_tile(4_194_304));
+    }
+}
@@ -1,6 +1,18 @@
+pub mod audit;
+pub mod cgroup;
 pub mod collector;
+pub mod container;
+pub mod diff;
+pub mod export;
+pub mod fds;
 pub mod history;
 pub mod kill;
+pub mod memmap;
+pub mod net;
 pub mod platform;
+pub mod priority;
 pub mod process;
+pub mod recording;
+pub mod replay;
+pub mod safety;
 pub mod snapshot;
@@ -0,0 +1,309 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// `/proc/net/tcp` state codes (`include/net/tcp_states.h`).
+const TCP_ESTABLISHED: u8 = 0x01;
+const TCP_CLOSE: u8 = 0x07;
+const TCP_LISTEN: u8 = 0x0A;
+/// `__SO_ACCEPTCON` in the flags column of `/proc/net/unix`.
+const UNIX_ACCEPTCON: u32 = 0x0001_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SocketProtocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl SocketProtocol {
+    pub fn label(self) -> &'static str {
+        match self {
+            SocketProtocol::Tcp => "tcp",
+            SocketProtocol::Tcp6 => "tcp6",
+            SocketProtocol::Udp => "udp",
+            SocketProtocol::Udp6 => "udp6",
+            SocketProtocol::Unix => "unix",
+        }
+    }
+
+    pub const ALL: [SocketProtocol; 5] = [
+        SocketProtocol::Tcp,
+        SocketProtocol::Tcp6,
+        SocketProtocol::Udp,
+        SocketProtocol::Udp6,
+        SocketProtocol::Unix,
+    ];
+}
+
+/// One row of a `/proc/net` socket table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SocketEntry {
+    pub protocol: SocketProtocol,
+    /// `None` for unix sockets.
+    pub local: Option<(IpAddr, u16)>,
+    pub state: u8,
+    pub listening: bool,
+    pub inode: u64,
+}
+
+impl SocketEntry {
+    /// Connected TCP, or a UDP socket with a fixed peer.
+    pub fn is_established(&self) -> bool {
+        self.local.is_some() && self.state == TCP_ESTABLISHED
+    }
+}
+
+/// A port a process accepts connections or datagrams on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ListeningSocket {
+    pub port: u16,
+    pub protocol: SocketProtocol,
+    pub address: IpAddr,
+}
+
+impl fmt::Display for ListeningSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let proto = self.protocol.label();
+        match self.address {
+            addr if addr.is_unspecified() => write!(f, "{proto} :{}", self.port),
+            IpAddr::V4(addr) => write!(f, "{proto} {addr}:{}", self.port),
+            IpAddr::V6(addr) => write!(f, "{proto} [{addr}]:{}", self.port),
+        }
+    }
+}
+
+/// Network summary of one process.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessSockets {
+    /// Listening TCP and bound UDP sockets, lowest port first.
+    pub listening: Vec<ListeningSocket>,
+    pub established: usize,
+    pub unix: usize,
+}
+
+/// Every socket of one network namespace, keyed by inode.
+#[derive(Debug, Default)]
+pub struct SocketTable {
+    sockets: HashMap<u64, SocketEntry>,
+}
+
+impl SocketTable {
+    /// Adds the rows of a `/proc/net/{tcp,tcp6,udp,udp6}` file.
+    pub fn insert_inet(&mut self, protocol: SocketProtocol, contents: &str) {
+        let is_udp = matches!(protocol, SocketProtocol::Udp | SocketProtocol::Udp6);
+        for line in contents.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (Some(local), Some(state), Some(inode)) = (
+                fields.get(1).and_then(|s| parse_inet_address(s)),
+                fields.get(3).and_then(|s| u8::from_str_radix(s, 16).ok()),
+                fields.get(9).and_then(|s| s.parse::<u64>().ok()),
+            ) else {
+                continue;
+            };
+            // Unconnected UDP sockets sit in TCP_CLOSE once bound
+            let listening = if is_udp {
+                state == TCP_CLOSE && local.1 != 0
+            } else {
+                state == TCP_LISTEN
+            };
+            self.insert(SocketEntry {
+                protocol,
+                local: Some(local),
+                state,
+                listening,
+                inode,
+            });
+        }
+    }
+
+    /// Adds the rows of `/proc/net/unix`.
+    pub fn insert_unix(&mut self, contents: &str) {
+        for line in contents.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (Some(flags), Some(state), Some(inode)) = (
+                fields.get(3).and_then(|s| u32::from_str_radix(s, 16).ok()),
+                fields.get(5).and_then(|s| u8::from_str_radix(s, 16).ok()),
+                fields.get(6).and_then(|s| s.parse::<u64>().ok()),
+            ) else {
+                continue;
+            };
+            self.insert(SocketEntry {
+                protocol: SocketProtocol::Unix,
+                local: None,
+                state,
+                listening: flags & UNIX_ACCEPTCON != 0,
+                inode,
+            });
+        }
+    }
+
+    fn insert(&mut self, entry: SocketEntry) {
+        // Inode 0 marks sockets already torn down (TIME_WAIT and friends)
+        if entry.inode != 0 {
+            self.sockets.insert(entry.inode, entry);
+        }
+    }
+
+    /// Summarizes the sockets behind a process's descriptors.
+    pub fn summarize(&self, inodes: &HashSet<u64>) -> ProcessSockets {
+        let mut summary = ProcessSockets::default();
+        for entry in inodes.iter().filter_map(|inode| self.sockets.get(inode)) {
+            match entry.local {
+                None => summary.unix += 1,
+                Some((address, port)) if entry.listening => {
+                    summary.listening.push(ListeningSocket {
+                        port,
+                        protocol: entry.protocol,
+                        address,
+                    });
+                }
+                Some(_) if entry.is_established() => summary.established += 1,
+                Some(_) => {}
+            }
+        }
+        summary.listening.sort();
+        summary.listening.dedup();
+        summary
+    }
+
+    /// Inodes of TCP/UDP sockets bound to local `port`, in any state.
+    pub fn inodes_on_port(&self, port: u16) -> HashSet<u64> {
+        self.sockets
+            .values()
+            .filter(|e| e.local.is_some_and(|(_, p)| p == port))
+            .map(|e| e.inode)
+            .collect()
+    }
+}
+
+/// Extracts the inode from a `/proc/<pid>/fd` link target like `socket:[12345]`.
+pub fn parse_socket_link(target: &str) -> Option<u64> {
+    target
+        .strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Parses `0100007F:1F90` (IPv4) or a 32-digit IPv6 address plus port.
+///
+/// The kernel prints each 32-bit word of the address in host byte order.
+fn parse_inet_address(field: &str) -> Option<(IpAddr, u16)> {
+    let (addr, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let word = |hex: &str| u32::from_str_radix(hex, 16).ok().map(u32::to_ne_bytes);
+    let ip = match addr.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(word(addr)?)),
+        32 => {
+            let mut bytes = [0u8; 16];
+            for (i, chunk) in bytes.chunks_mut(4).enumerate() {
+                chunk.copy_from_slice(&word(addr.get(i * 8..i * 8 + 8)?)?);
+            }
+            IpAddr::V6(Ipv6Addr::from(bytes))
+        }
+        _ => return None,
+    };
+    Some((ip, port))
+}
+
+/// Parses a port filter such as `port:8080`.
+pub fn parse_port_filter(filter: &str) -> Option<u16> {
+    filter.trim().strip_prefix("port:")?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Addresses below are little-endian, as printed on x86 and arm64
+    const TCP: &str = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 1001 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1002 1 0000000000000000 100 0 0 10 0
+   2: 0100007F:1F90 0100007F:D431 01 00000000:00000000 00:00000000 00000000  1000        0 1003 1 0000000000000000 20 4 30 10 -1
+   3: 0100007F:D432 0100007F:1F90 06 00000000:00000000 03:00000F9E 00000000     0        0 0 3 0000000000000000
+";
+    const TCP6: &str = "\
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:1F90 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 1004 1 0000000000000000 100 0 0 10 0
+   1: 00000000000000000000000001000000:0277 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1005 1 0000000000000000 100 0 0 10 0
+";
+    const UDP: &str = "\
+   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  100: 3500007F:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 1006 2 0000000000000000 0
+  101: 00000000:0000 0100007F:0035 01 00000000:00000000 00:00000000 00000000  1000        0 1007 2 0000000000000000 0
+";
+    const UNIX: &str = "\
+Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 1008 /run/app.sock
+0000000000000000: 00000003 00000000 00000000 0001 03 1009
+";
+
+    fn table() -> SocketTable {
+        let mut table = SocketTable::default();
+        table.insert_inet(SocketProtocol::Tcp, TCP);
+        table.insert_inet(SocketProtocol::Tcp6, TCP6);
+        table.insert_inet(SocketProtocol::Udp, UDP);
+        table.insert_unix(UNIX);
+        table
+    }
+
+    #[test]
+    fn parses_ipv4_and_ipv6_addresses() {
+        assert_eq!(
+            parse_inet_address("0100007F:1F90"),
+            Some((IpAddr::V4(Ipv4Addr::LOCALHOST), 8080))
+        );
+        assert_eq!(
+            parse_inet_address("00000000000000000000000001000000:0277"),
+            Some((IpAddr::V6(Ipv6Addr::LOCALHOST), 631))
+        );
+        assert_eq!(parse_inet_address("zz:1F90"), None);
+    }
+
+    #[test]
+    fn summarizes_only_the_processes_own_inodes() {
+        let table = table();
+        let server = table.summarize(&HashSet::from([1001, 1003, 1004, 1008, 1009, 4242]));
+        let labels: Vec<String> = server.listening.iter().map(|l| l.to_string()).collect();
+        assert_eq!(labels, vec!["tcp :8080", "tcp6 :8080"]);
+        assert_eq!(server.established, 1);
+        assert_eq!(server.unix, 2);
+
+        let cups = table.summarize(&HashSet::from([1002, 1005]));
+        let labels: Vec<String> = cups.listening.iter().map(|l| l.to_string()).collect();
+        assert_eq!(labels, vec!["tcp 127.0.0.1:631", "tcp6 [::1]:631"]);
+    }
+
+    #[test]
+    fn bound_udp_counts_as_listening() {
+        let resolver = table().summarize(&HashSet::from([1006, 1007]));
+        assert_eq!(resolver.listening.len(), 1);
+        assert_eq!(resolver.listening[0].to_string(), "udp 127.0.0.53:53");
+        assert_eq!(resolver.established, 1);
+    }
+
+    #[test]
+    fn port_lookup_skips_dead_sockets() {
+        let table = table();
+        assert_eq!(
+            table.inodes_on_port(8080),
+            HashSet::from([1001, 1003, 1004])
+        );
+        assert!(table.inodes_on_port(9999).is_empty());
+    }
+
+    #[test]
+    fn socket_links_and_port_filters() {
+        assert_eq!(parse_socket_link("socket:[1001]"), Some(1001));
+        assert_eq!(parse_socket_link("pipe:[1001]"), None);
+        assert_eq!(parse_socket_link("/dev/null"), None);
+        assert_eq!(parse_port_filter("port:8080"), Some(8080));
+        assert_eq!(parse_port_filter(" port: 443 "), Some(443));
+        assert_eq!(parse_port_filter("portal"), None);
+        assert_eq!(parse_port_filter("port:99999"), None);
+    }
+}
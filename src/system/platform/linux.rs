@@ -1,4 +1,18 @@
-use super::{IoStats, PlatformExtensions};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::procfs::parse_stat;
+use super::{IoStats, PlatformExtensions, ProcessExtras};
+use crate::system::collector::CollectorBackend;
+use crate::system::container::{ContainerInfo, parse_proc_cgroup};
+use crate::system::fds::{OpenFile, parse_nofile_limit};
+use crate::system::memmap::{MemoryMap, parse_kb, parse_smaps};
+use crate::system::net::{ProcessSockets, SocketProtocol, SocketTable, parse_socket_link};
+use crate::system::priority::{IoPriority, SchedPolicy};
+use crate::system::process::{CpuTimes, MemoryDetail};
 
 pub struct Platform;
 
@@ -7,21 +21,12 @@ impl PlatformExtensions for Platform {
         // Read /proc/{pid}/cgroup and parse the last path segment
         let path = format!("/proc/{pid}/cgroup");
         let contents = std::fs::read_to_string(path).ok()?;
-        // cgroup v2: single line "0::/path/to/group"
-        // cgroup v1: multiple lines "hierarchy-id:controller-list:path"
-        for line in contents.lines().rev() {
-            let parts: Vec<&str> = line.splitn(3, ':').collect();
-            if parts.len() == 3 {
-                let cgroup_path = parts[2].trim_start_matches('/');
-                if !cgroup_path.is_empty()
-                    && let Some(name) = cgroup_path.rsplit('/').next()
-                    && !name.is_empty()
-                {
-                    return Some(name.to_string());
-                }
-            }
-        }
-        None
+        parse_cgroup_group_name(&contents)
+    }
+
+    fn process_container(pid: u32) -> Option<ContainerInfo> {
+        let contents = std::fs::read_to_string(format!("/proc/{pid}/cgroup")).ok()?;
+        parse_proc_cgroup(&contents)
     }
 
     fn process_priority(pid: u32) -> Option<i32> {
@@ -37,22 +42,387 @@ impl PlatformExtensions for Platform {
         fields.get(15)?.parse().ok()
     }
 
+    fn process_nice(pid: u32) -> Option<i32> {
+        let contents = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        let after_comm = contents.rfind(')')? + 1;
+        // nice(16) in the numbering used by process_priority
+        contents[after_comm..]
+            .split_whitespace()
+            .nth(16)?
+            .parse()
+            .ok()
+    }
+
+    fn set_process_nice(pid: u32, nice: i32) -> std::io::Result<()> {
+        // SAFETY: setpriority only reads its integer arguments.
+        let rc = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) };
+        if rc == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
+
+    fn process_io_priority(pid: u32) -> Option<IoPriority> {
+        // SAFETY: ioprio_get only reads its integer arguments.
+        let raw = unsafe {
+            libc::syscall(
+                libc::SYS_ioprio_get,
+                IOPRIO_WHO_PROCESS,
+                libc::c_long::from(pid),
+            )
+        };
+        if raw < 0 {
+            None
+        } else {
+            IoPriority::from_raw(raw as u32)
+        }
+    }
+
+    fn set_process_io_priority(pid: u32, priority: IoPriority) -> std::io::Result<()> {
+        // SAFETY: ioprio_set only reads its integer arguments.
+        let rc = unsafe {
+            libc::syscall(
+                libc::SYS_ioprio_set,
+                IOPRIO_WHO_PROCESS,
+                libc::c_long::from(pid),
+                libc::c_long::from(priority.to_raw()),
+            )
+        };
+        if rc == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
+
+    fn process_sched_policy(pid: u32) -> Option<SchedPolicy> {
+        // SAFETY: sched_getscheduler only reads its pid argument.
+        let raw = unsafe { libc::sched_getscheduler(pid as libc::pid_t) };
+        (raw >= 0).then(|| SchedPolicy::from_raw(raw))
+    }
+
     fn process_io(pid: u32) -> Option<IoStats> {
         // Read /proc/{pid}/io
         let path = format!("/proc/{pid}/io");
         let contents = std::fs::read_to_string(path).ok()?;
-        let mut read_bytes = None;
-        let mut write_bytes = None;
-        for line in contents.lines() {
-            if let Some(val) = line.strip_prefix("read_bytes: ") {
-                read_bytes = val.trim().parse().ok();
-            } else if let Some(val) = line.strip_prefix("write_bytes: ") {
-                write_bytes = val.trim().parse().ok();
+        parse_io(&contents)
+    }
+
+    fn process_extras(pid: u32, proportional: bool) -> ProcessExtras {
+        let dir = format!("/proc/{pid}");
+        let stat = fs::read_to_string(format!("{dir}/stat"))
+            .ok()
+            .and_then(|c| parse_stat(&c));
+        let status = fs::read_to_string(format!("{dir}/status")).unwrap_or_default();
+        let mut memory = parse_status_memory(&status);
+        if proportional {
+            let rollup = fs::read_to_string(format!("{dir}/smaps_rollup")).unwrap_or_default();
+            (memory.pss, memory.uss) = parse_smaps_rollup(&rollup);
+        }
+        let cgroup = fs::read_to_string(format!("{dir}/cgroup")).unwrap_or_default();
+        let clock_ticks = clock_ticks();
+        ProcessExtras {
+            group_name: parse_cgroup_group_name(&cgroup),
+            container: parse_proc_cgroup(&cgroup),
+            priority: stat.as_ref().map(|s| s.priority),
+            io_stats: fs::read_to_string(format!("{dir}/io"))
+                .ok()
+                .and_then(|c| parse_io(&c)),
+            memory,
+            fd_count: fd_count(pid),
+            start_time: stat.as_ref().and_then(|s| {
+                Some(start_time_from_ticks(
+                    boot_time()?,
+                    s.starttime,
+                    clock_ticks,
+                ))
+            }),
+            start_ticks: stat.as_ref().map(|s| s.starttime),
+            cpu_time: stat
+                .as_ref()
+                .map(|s| cpu_times_from_ticks([s.utime, s.stime, s.cutime, s.cstime], clock_ticks)),
+        }
+    }
+
+    fn process_memory_map(pid: u32) -> Option<MemoryMap> {
+        // Kernel threads have an empty smaps; treat that like an unreadable one
+        let contents = std::fs::read_to_string(format!("/proc/{pid}/smaps")).ok()?;
+        let map = parse_smaps(pid, &contents);
+        (!map.regions.is_empty()).then_some(map)
+    }
+
+    fn process_start_ticks(pid: u32) -> Option<u64> {
+        let contents = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        let after_comm = contents.rfind(')')? + 1;
+        // starttime(19) in the numbering used by process_priority
+        contents[after_comm..]
+            .split_whitespace()
+            .nth(19)?
+            .parse()
+            .ok()
+    }
+
+    fn process_fd_limit(pid: u32) -> Option<u64> {
+        let contents = fs::read_to_string(format!("/proc/{pid}/limits")).ok()?;
+        parse_nofile_limit(&contents)
+    }
+
+    fn process_open_files(pid: u32) -> Option<Vec<OpenFile>> {
+        let entries = fs::read_dir(format!("/proc/{pid}/fd")).ok()?;
+        let mut files: Vec<OpenFile> = entries
+            .flatten()
+            .filter_map(|e| {
+                let fd = e.file_name().to_str()?.parse().ok()?;
+                // The descriptor may close between readdir and readlink
+                let target = fs::read_link(e.path()).ok()?;
+                Some(OpenFile::new(fd, target.to_string_lossy().into_owned()))
+            })
+            .collect();
+        files.sort_by_key(|f| f.fd);
+        Some(files)
+    }
+
+    fn process_sockets(pid: u32) -> Option<ProcessSockets> {
+        let inodes = socket_inodes(pid)?;
+        Some(socket_table(pid).summarize(&inodes))
+    }
+
+    fn port_owners(port: u16) -> HashSet<u32> {
+        // Socket tables are per network namespace; read each one once
+        let mut inodes_by_netns: HashMap<PathBuf, HashSet<u64>> = HashMap::new();
+        let mut owners = HashSet::new();
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return owners;
+        };
+        for entry in entries.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse().ok()) else {
+                continue;
+            };
+            let Ok(netns) = fs::read_link(format!("/proc/{pid}/ns/net")) else {
+                continue;
+            };
+            let on_port = inodes_by_netns
+                .entry(netns)
+                .or_insert_with(|| socket_table(pid).inodes_on_port(port));
+            if on_port.is_empty() {
+                continue;
+            }
+            if socket_inodes(pid).is_some_and(|own| !own.is_disjoint(on_port)) {
+                owners.insert(pid);
+            }
+        }
+        owners
+    }
+
+    fn cgroup_root() -> Option<PathBuf> {
+        // Only the unified hierarchy has cgroup.controllers at its root
+        let root = PathBuf::from("/sys/fs/cgroup");
+        root.join("cgroup.controllers").exists().then_some(root)
+    }
+
+    fn native_backend() -> Option<Box<dyn CollectorBackend>> {
+        Some(Box::new(super::procfs::ProcfsBackend::new()))
+    }
+}
+
+/// `IOPRIO_WHO_PROCESS` from `linux/ioprio.h`; not exported by libc.
+const IOPRIO_WHO_PROCESS: libc::c_long = 1;
+
+/// `USER_HZ`, the unit of the time fields in `/proc/<pid>/stat`.
+pub(super) fn clock_ticks() -> f64 {
+    // SAFETY: sysconf has no preconditions; it returns -1 on unsupported names.
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 { ticks as f64 } else { 100.0 }
+}
+
+/// Builds [`CpuTimes`] from `[utime, stime, cutime, cstime]` in clock ticks.
+pub(super) fn cpu_times_from_ticks(ticks: [u64; 4], clock_ticks: f64) -> CpuTimes {
+    let secs = |t: u64| Duration::from_secs_f64(t as f64 / clock_ticks);
+    CpuTimes {
+        user: secs(ticks[0]),
+        system: secs(ticks[1]),
+        children_user: secs(ticks[2]),
+        children_system: secs(ticks[3]),
+    }
+}
+
+/// Boot time in seconds since the Unix epoch, read once.
+pub(super) fn boot_time() -> Option<u64> {
+    static BOOT_TIME: OnceLock<Option<u64>> = OnceLock::new();
+    *BOOT_TIME.get_or_init(|| {
+        fs::read_to_string("/proc/stat")
+            .ok()
+            .and_then(|s| parse_boot_time(&s))
+    })
+}
+
+/// Converts `starttime` (clock ticks after boot) to seconds since the epoch.
+pub(super) fn start_time_from_ticks(boot_time: u64, ticks: u64, clock_ticks: f64) -> u64 {
+    boot_time + (ticks as f64 / clock_ticks) as u64
+}
+
+/// Boot time in seconds since the Unix epoch, from the `btime` line of `/proc/stat`.
+pub(super) fn parse_boot_time(contents: &str) -> Option<u64> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Counts `/proc/<pid>/fd` entries without resolving their links.
+pub(super) fn fd_count(pid: u32) -> Option<u32> {
+    let entries = fs::read_dir(format!("/proc/{pid}/fd")).ok()?;
+    Some(entries.count() as u32)
+}
+
+/// Inodes of every socket among a process's open descriptors.
+fn socket_inodes(pid: u32) -> Option<HashSet<u64>> {
+    let entries = fs::read_dir(format!("/proc/{pid}/fd")).ok()?;
+    Some(
+        entries
+            .flatten()
+            .filter_map(|e| fs::read_link(e.path()).ok())
+            .filter_map(|target| parse_socket_link(target.to_str()?))
+            .collect(),
+    )
+}
+
+/// Socket tables as seen from the network namespace of `pid`.
+fn socket_table(pid: u32) -> SocketTable {
+    let mut table = SocketTable::default();
+    for protocol in SocketProtocol::ALL {
+        let path = format!("/proc/{pid}/net/{}", protocol.label());
+        let contents = fs::read_to_string(path).unwrap_or_default();
+        match protocol {
+            SocketProtocol::Unix => table.insert_unix(&contents),
+            _ => table.insert_inet(protocol, &contents),
+        }
+    }
+    table
+}
+
+/// Last non-empty segment of the cgroup path in a `/proc/{pid}/cgroup` file.
+pub(super) fn parse_cgroup_group_name(contents: &str) -> Option<String> {
+    // cgroup v2: single line "0::/path/to/group"
+    // cgroup v1: multiple lines "hierarchy-id:controller-list:path"
+    for line in contents.lines().rev() {
+        let parts: Vec<&str> = line.splitn(3, ':').collect();
+        if parts.len() == 3 {
+            let cgroup_path = parts[2].trim_start_matches('/');
+            if !cgroup_path.is_empty()
+                && let Some(name) = cgroup_path.rsplit('/').next()
+                && !name.is_empty()
+            {
+                return Some(name.to_string());
+            }
+        }
+    }
+    None
+}
+
+pub(super) fn parse_io(contents: &str) -> Option<IoStats> {
+    let mut read_bytes = None;
+    let mut write_bytes = None;
+    for line in contents.lines() {
+        if let Some(val) = line.strip_prefix("read_bytes: ") {
+            read_bytes = val.trim().parse().ok();
+        } else if let Some(val) = line.strip_prefix("write_bytes: ") {
+            write_bytes = val.trim().parse().ok();
+        }
+    }
+    Some(IoStats {
+        read_bytes: read_bytes?,
+        write_bytes: write_bytes?,
+    })
+}
+
+/// Swap and virtual size from `/proc/{pid}/status`.
+pub(super) fn parse_status_memory(contents: &str) -> MemoryDetail {
+    let mut detail = MemoryDetail::default();
+    for line in contents.lines() {
+        if let Some(val) = line.strip_prefix("VmSwap:") {
+            detail.swap = parse_kb(val);
+        } else if let Some(val) = line.strip_prefix("VmSize:") {
+            detail.virtual_bytes = parse_kb(val);
+        }
+    }
+    detail
+}
+
+/// PSS and USS (private clean + private dirty) from `/proc/{pid}/smaps_rollup`.
+/// Unreadable for other users' processes without CAP_SYS_PTRACE.
+pub(super) fn parse_smaps_rollup(contents: &str) -> (Option<u64>, Option<u64>) {
+    let mut pss = None;
+    let mut private: Option<u64> = None;
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key {
+            "Pss" => pss = parse_kb(value),
+            "Private_Clean" | "Private_Dirty" => {
+                if let Some(bytes) = parse_kb(value) {
+                    private = Some(private.unwrap_or(0) + bytes);
+                }
             }
+            _ => {}
         }
-        Some(IoStats {
-            read_bytes: read_bytes?,
-            write_bytes: write_bytes?,
-        })
+    }
+    (pss, private)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smaps_rollup_sums_private_pages() {
+        let rollup = "55d0c0a00000-7ffc1b5fe000 ---p 00000000 00:00 0  [rollup]\n\
+            Rss:                5120 kB\nPss:                2048 kB\n\
+            Shared_Clean:       3072 kB\nPrivate_Clean:       512 kB\n\
+            Private_Dirty:      1024 kB\n";
+        assert_eq!(
+            parse_smaps_rollup(rollup),
+            (Some(2048 * 1024), Some(1536 * 1024))
+        );
+        assert_eq!(parse_smaps_rollup(""), (None, None));
+    }
+
+    #[test]
+    fn boot_time_and_cpu_ticks() {
+        let stat = "cpu  10 0 20 300 0 0 0 0 0 0\nctxt 4242\nbtime 1760600000\nprocesses 99\n";
+        assert_eq!(parse_boot_time(stat), Some(1_760_600_000));
+        assert_eq!(parse_boot_time("cpu 1 2 3\n"), None);
+
+        let times = cpu_times_from_ticks([250, 75, 12, 4], 100.0);
+        assert_eq!(times.user, Duration::from_millis(2500));
+        assert_eq!(times.total(), Duration::from_millis(3250));
+        assert_eq!(times.children_system, Duration::from_millis(40));
+    }
+
+    #[test]
+    fn extras_of_own_process_come_from_one_pass() {
+        let extras = Platform::process_extras(std::process::id(), true);
+        assert!(extras.fd_count.is_some_and(|n| n > 0));
+        assert!(extras.memory.virtual_bytes.is_some());
+        assert!(extras.memory.pss.is_some());
+        assert!(extras.cpu_time.is_some());
+        assert!(extras.start_time.is_some());
+        assert_eq!(
+            extras.start_ticks,
+            Platform::process_start_ticks(std::process::id())
+        );
+    }
+
+    #[test]
+    fn status_memory_reads_swap_and_vsz() {
+        let detail =
+            parse_status_memory("VmSize:\t  20480 kB\nVmRSS:\t 5120 kB\nVmSwap:\t 64 kB\n");
+        assert_eq!(detail.virtual_bytes, Some(20480 * 1024));
+        assert_eq!(detail.swap, Some(64 * 1024));
+        assert_eq!(detail.pss, None);
     }
 }
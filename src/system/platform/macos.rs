@@ -1,4 +1,13 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
 use super::{IoStats, PlatformExtensions};
+use crate::system::collector::CollectorBackend;
+use crate::system::container::ContainerInfo;
+use crate::system::fds::OpenFile;
+use crate::system::memmap::MemoryMap;
+use crate::system::net::ProcessSockets;
+use crate::system::priority::{IoPriority, SchedPolicy};
 
 pub struct Platform;
 
@@ -8,6 +17,11 @@ impl PlatformExtensions for Platform {
         libproc::libproc::proc_pid::name(pid as i32).ok()
     }
 
+    fn process_container(_pid: u32) -> Option<ContainerInfo> {
+        // Containers on macOS run inside a Linux VM, invisible from the host
+        None
+    }
+
     fn process_priority(pid: u32) -> Option<i32> {
         // Use libc getpriority (libc is a transitive dep of sysinfo)
         // Clear errno before call
@@ -23,8 +37,70 @@ impl PlatformExtensions for Platform {
         }
     }
 
+    fn process_nice(pid: u32) -> Option<i32> {
+        // getpriority already reports the nice value here
+        Self::process_priority(pid)
+    }
+
+    fn set_process_nice(pid: u32, nice: i32) -> std::io::Result<()> {
+        // SAFETY: setpriority only reads its integer arguments.
+        let rc = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) };
+        if rc == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
+
+    fn process_io_priority(_pid: u32) -> Option<IoPriority> {
+        // macOS I/O policies are per thread, not per process
+        None
+    }
+
+    fn set_process_io_priority(_pid: u32, _priority: IoPriority) -> std::io::Result<()> {
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    fn process_sched_policy(_pid: u32) -> Option<SchedPolicy> {
+        None
+    }
+
     fn process_io(_pid: u32) -> Option<IoStats> {
         // macOS doesn't expose per-process I/O bytes easily
         None
     }
+
+    fn process_memory_map(_pid: u32) -> Option<MemoryMap> {
+        // macOS has no smaps equivalent
+        None
+    }
+
+    fn process_start_ticks(_pid: u32) -> Option<u64> {
+        None
+    }
+
+    fn process_fd_limit(_pid: u32) -> Option<u64> {
+        None
+    }
+
+    fn process_open_files(_pid: u32) -> Option<Vec<OpenFile>> {
+        None
+    }
+
+    fn process_sockets(_pid: u32) -> Option<ProcessSockets> {
+        // Needs proc_pidfdinfo per descriptor; not wired up on macOS yet
+        None
+    }
+
+    fn port_owners(_port: u16) -> HashSet<u32> {
+        HashSet::new()
+    }
+
+    fn cgroup_root() -> Option<PathBuf> {
+        None
+    }
+
+    fn native_backend() -> Option<Box<dyn CollectorBackend>> {
+        None
+    }
 }
@@ -1,19 +1,94 @@
-#[derive(Clone, Copy, Debug)]
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::system::collector::CollectorBackend;
+use crate::system::container::ContainerInfo;
+use crate::system::fds::OpenFile;
+use crate::system::memmap::MemoryMap;
+use crate::system::net::ProcessSockets;
+use crate::system::priority::{IoPriority, SchedPolicy};
+use crate::system::process::{CpuTimes, MemoryDetail};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct IoStats {
     pub read_bytes: u64,
     pub write_bytes: u64,
 }
 
+/// What the sysinfo backend adds to each process beyond sysinfo's own
+/// fields, gathered with a single read of each per-process source.
+#[derive(Debug, Default)]
+pub struct ProcessExtras {
+    pub group_name: Option<String>,
+    pub container: Option<ContainerInfo>,
+    pub priority: Option<i32>,
+    pub io_stats: Option<IoStats>,
+    /// Swap, virtual size and, when asked for, PSS/USS.
+    pub memory: MemoryDetail,
+    /// Number of open file descriptors.
+    pub fd_count: Option<u32>,
+    pub start_time: Option<u64>,
+    /// Raw start in clock ticks since boot, for [`ProcessIdentity`].
+    ///
+    /// [`ProcessIdentity`]: crate::system::process::ProcessIdentity
+    pub start_ticks: Option<u64>,
+    /// User/system time of the process and of its reaped children.
+    pub cpu_time: Option<CpuTimes>,
+}
+
 pub trait PlatformExtensions {
     fn process_group_name(pid: u32) -> Option<String>;
+    /// Container, pod and systemd unit parsed from the full cgroup path.
+    fn process_container(pid: u32) -> Option<ContainerInfo>;
     fn process_priority(pid: u32) -> Option<i32>;
+    /// Nice value, from -20 (highest priority) to 19.
+    fn process_nice(pid: u32) -> Option<i32>;
+    fn set_process_nice(pid: u32, nice: i32) -> std::io::Result<()>;
+    fn process_io_priority(pid: u32) -> Option<IoPriority>;
+    fn set_process_io_priority(pid: u32, priority: IoPriority) -> std::io::Result<()>;
+    fn process_sched_policy(pid: u32) -> Option<SchedPolicy>;
     fn process_io(pid: u32) -> Option<IoStats>;
+    /// Everything [`ProcessExtras`] holds for one process, PSS/USS only when
+    /// `proportional` is set. Platforms that can't do better combine the
+    /// single-field lookups.
+    fn process_extras(pid: u32, _proportional: bool) -> ProcessExtras {
+        ProcessExtras {
+            group_name: Self::process_group_name(pid),
+            container: Self::process_container(pid),
+            priority: Self::process_priority(pid),
+            io_stats: Self::process_io(pid),
+            ..ProcessExtras::default()
+        }
+    }
+    /// Grouped address-space regions of a single process.
+    fn process_memory_map(pid: u32) -> Option<MemoryMap>;
+    /// Start in clock ticks since boot, exactly as the kernel records it, so
+    /// a process can be told apart from one that reuses its PID within the
+    /// same second. `None` where the platform keeps no such counter.
+    fn process_start_ticks(pid: u32) -> Option<u64>;
+    /// Soft `RLIMIT_NOFILE`; `None` when unlimited or unreadable.
+    fn process_fd_limit(pid: u32) -> Option<u64>;
+    /// Every open descriptor of a single process, lowest fd first.
+    fn process_open_files(pid: u32) -> Option<Vec<OpenFile>>;
+    /// Listening ports and connection counts of a single process.
+    fn process_sockets(pid: u32) -> Option<ProcessSockets>;
+    /// PIDs holding a TCP or UDP socket bound to local `port`.
+    fn port_owners(port: u16) -> HashSet<u32>;
+    /// Mount point of a unified (v2) cgroup hierarchy.
+    fn cgroup_root() -> Option<PathBuf>;
+    /// Platform-specific process collector, if one exists.
+    fn native_backend() -> Option<Box<dyn CollectorBackend>>;
 }
 
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "macos")]
 mod macos;
+#[cfg(target_os = "linux")]
+mod procfs;
 #[cfg(target_os = "windows")]
 mod windows;
 
@@ -24,16 +99,60 @@ use macos as platform_impl;
 #[cfg(target_os = "windows")]
 use windows as platform_impl;
 
-pub fn process_group_name(pid: u32) -> Option<String> {
-    platform_impl::Platform::process_group_name(pid)
+pub fn process_nice(pid: u32) -> Option<i32> {
+    platform_impl::Platform::process_nice(pid)
+}
+
+pub fn set_process_nice(pid: u32, nice: i32) -> std::io::Result<()> {
+    platform_impl::Platform::set_process_nice(pid, nice)
+}
+
+pub fn process_io_priority(pid: u32) -> Option<IoPriority> {
+    platform_impl::Platform::process_io_priority(pid)
+}
+
+pub fn set_process_io_priority(pid: u32, priority: IoPriority) -> std::io::Result<()> {
+    platform_impl::Platform::set_process_io_priority(pid, priority)
+}
+
+pub fn process_sched_policy(pid: u32) -> Option<SchedPolicy> {
+    platform_impl::Platform::process_sched_policy(pid)
+}
+
+pub fn process_extras(pid: u32, proportional: bool) -> ProcessExtras {
+    platform_impl::Platform::process_extras(pid, proportional)
+}
+
+pub fn process_memory_map(pid: u32) -> Option<MemoryMap> {
+    platform_impl::Platform::process_memory_map(pid)
+}
+
+pub fn process_start_ticks(pid: u32) -> Option<u64> {
+    platform_impl::Platform::process_start_ticks(pid)
+}
+
+pub fn process_fd_limit(pid: u32) -> Option<u64> {
+    platform_impl::Platform::process_fd_limit(pid)
+}
+
+pub fn process_open_files(pid: u32) -> Option<Vec<OpenFile>> {
+    platform_impl::Platform::process_open_files(pid)
+}
+
+pub fn process_sockets(pid: u32) -> Option<ProcessSockets> {
+    platform_impl::Platform::process_sockets(pid)
+}
+
+pub fn port_owners(port: u16) -> HashSet<u32> {
+    platform_impl::Platform::port_owners(port)
 }
 
-pub fn process_priority(pid: u32) -> Option<i32> {
-    platform_impl::Platform::process_priority(pid)
+pub fn cgroup_root() -> Option<PathBuf> {
+    platform_impl::Platform::cgroup_root()
 }
 
-pub fn process_io(pid: u32) -> Option<IoStats> {
-    platform_impl::Platform::process_io(pid)
+pub fn native_backend() -> Option<Box<dyn CollectorBackend>> {
+    platform_impl::Platform::native_backend()
 }
 
 #[cfg(test)]
@@ -43,8 +162,16 @@ mod tests {
     #[test]
     fn wrappers_do_not_panic_for_current_pid() {
         let pid = std::process::id();
-        let _ = process_group_name(pid);
-        let _ = process_priority(pid);
-        let _ = process_io(pid);
+        let _ = process_nice(pid);
+        let _ = process_io_priority(pid);
+        let _ = process_sched_policy(pid);
+        let _ = process_extras(pid, true);
+        let _ = process_memory_map(pid);
+        let _ = process_start_ticks(pid);
+        let _ = process_fd_limit(pid);
+        let _ = process_open_files(pid);
+        let _ = process_sockets(pid);
+        let _ = port_owners(1);
+        let _ = cgroup_root();
     }
 }
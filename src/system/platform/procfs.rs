@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Instant;
+
+use sysinfo::System;
+
+use super::linux::{
+    boot_time, clock_ticks, cpu_times_from_ticks, fd_count, parse_cgroup_group_name, parse_io,
+    parse_smaps_rollup, parse_status_memory, start_time_from_ticks,
+};
+use crate::system::collector::CollectorBackend;
+use crate::system::container::parse_proc_cgroup;
+use crate::system::memmap::parse_kb;
+use crate::system::process::{ProcessInfo, ProcessState};
+
+/// Linux collector that reads every `/proc/<pid>` file it needs exactly once
+/// per tick, instead of sysinfo's pass followed by per-PID extension reads.
+pub struct ProcfsBackend {
+    clock_ticks: f64,
+    /// Seconds since the epoch; `starttime` in `stat` counts ticks from here.
+    boot_time: Option<u64>,
+    prev_cpu_ticks: HashMap<u32, u64>,
+    last_sample: Option<Instant>,
+}
+
+impl ProcfsBackend {
+    pub fn new() -> Self {
+        Self {
+            clock_ticks: clock_ticks(),
+            boot_time: boot_time(),
+            prev_cpu_ticks: HashMap::new(),
+            last_sample: None,
+        }
+    }
+}
+
+impl CollectorBackend for ProcfsBackend {
+    fn name(&self) -> &'static str {
+        "procfs"
+    }
+
+    fn collect_processes(&mut self, _sys: &mut System, proportional: bool) -> Vec<ProcessInfo> {
+        let now = Instant::now();
+        let elapsed = self
+            .last_sample
+            .map(|t| now.duration_since(t).as_secs_f64())
+            .unwrap_or(0.0);
+        self.last_sample = Some(now);
+
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return Vec::new();
+        };
+
+        let mut cpu_ticks = HashMap::with_capacity(self.prev_cpu_ticks.len());
+        let mut processes = Vec::with_capacity(self.prev_cpu_ticks.len());
+
+        for entry in entries.flatten() {
+            let Some(pid) = entry
+                .file_name()
+                .to_str()
+                .and_then(|s| s.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            // The process may exit between readdir and the reads below; skip it quietly.
+            let Some(mut info) = read_process(pid, proportional, self.clock_ticks, self.boot_time)
+            else {
+                continue;
+            };
+
+            let total = info.ticks;
+            let cpu_percent = match self.prev_cpu_ticks.get(&pid) {
+                Some(&prev) if elapsed > 0.0 => {
+                    (total.saturating_sub(prev) as f64 / self.clock_ticks / elapsed * 100.0) as f32
+                }
+                _ => 0.0,
+            };
+            cpu_ticks.insert(pid, total);
+            info.process.cpu_percent = cpu_percent;
+            processes.push(info.process);
+        }
+
+        self.prev_cpu_ticks = cpu_ticks;
+        processes
+    }
+}
+
+struct ProcfsSample {
+    process: ProcessInfo,
+    ticks: u64,
+}
+
+fn read_process(
+    pid: u32,
+    proportional: bool,
+    clock_ticks: f64,
+    boot_time: Option<u64>,
+) -> Option<ProcfsSample> {
+    let dir = format!("/proc/{pid}");
+    let stat = parse_stat(&fs::read_to_string(format!("{dir}/stat")).ok()?)?;
+    let status_raw = fs::read_to_string(format!("{dir}/status")).unwrap_or_default();
+    let status = parse_status(&status_raw);
+    let mut memory_detail = parse_status_memory(&status_raw);
+    if proportional {
+        let rollup = fs::read_to_string(format!("{dir}/smaps_rollup")).unwrap_or_default();
+        (memory_detail.pss, memory_detail.uss) = parse_smaps_rollup(&rollup);
+    }
+    let command = fs::read(format!("{dir}/cmdline"))
+        .map(|raw| parse_cmdline(&raw))
+        .unwrap_or_default();
+    let cgroup = fs::read_to_string(format!("{dir}/cgroup")).unwrap_or_default();
+    let group_name = parse_cgroup_group_name(&cgroup);
+    let container = parse_proc_cgroup(&cgroup);
+    let io_stats = fs::read_to_string(format!("{dir}/io"))
+        .ok()
+        .and_then(|c| parse_io(&c));
+
+    // Match sysinfo's `Uid(..)` / `Gid(..)` rendering so color-by-user stays stable
+    // across backends.
+    let user_id = status
+        .uid
+        .and_then(|uid| uid.to_string().parse::<sysinfo::Uid>().ok())
+        .map(|uid| format!("{uid:?}"));
+    let group_id = status
+        .gid
+        .and_then(|gid| gid.to_string().parse::<sysinfo::Gid>().ok())
+        .map(|gid| format!("{gid:?}"));
+
+    Some(ProcfsSample {
+        ticks: stat.utime + stat.stime,
+        process: ProcessInfo {
+            pid,
+            ppid: stat.ppid,
+            name: stat.comm,
+            command,
+            memory_bytes: status.rss_bytes,
+            cpu_percent: 0.0,
+            user_id,
+            group_id,
+            status: map_state(stat.state),
+            children: Vec::new(),
+            group_name,
+            priority: Some(stat.priority),
+            io_stats,
+            memory_detail,
+            container,
+            io_rate: None,
+            fd_count: fd_count(pid),
+            start_time: boot_time
+                .map(|boot| start_time_from_ticks(boot, stat.starttime, clock_ticks)),
+            start_ticks: Some(stat.starttime),
+            cpu_time: Some(cpu_times_from_ticks(
+                [stat.utime, stat.stime, stat.cutime, stat.cstime],
+                clock_ticks,
+            )),
+        },
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct StatFields {
+    pub(super) comm: String,
+    pub(super) state: char,
+    pub(super) ppid: u32,
+    pub(super) utime: u64,
+    pub(super) stime: u64,
+    pub(super) cutime: u64,
+    pub(super) cstime: u64,
+    pub(super) priority: i32,
+    /// Clock ticks after boot.
+    pub(super) starttime: u64,
+}
+
+pub(super) fn parse_stat(contents: &str) -> Option<StatFields> {
+    // comm may contain spaces and parens, so split on the first '(' and last ')'
+    let open = contents.find('(')?;
+    let close = contents.rfind(')')?;
+    let comm = contents.get(open + 1..close)?.to_string();
+    let fields: Vec<&str> = contents[close + 1..].split_whitespace().collect();
+    // Fields after comm: state(0) ppid(1) ... utime(11) stime(12) cutime(13)
+    // cstime(14) priority(15) ... starttime(19)
+    Some(StatFields {
+        comm,
+        state: fields.first()?.chars().next()?,
+        ppid: fields.get(1)?.parse().ok()?,
+        utime: fields.get(11)?.parse().ok()?,
+        stime: fields.get(12)?.parse().ok()?,
+        cutime: fields.get(13)?.parse().ok()?,
+        cstime: fields.get(14)?.parse().ok()?,
+        priority: fields.get(15)?.parse().ok()?,
+        starttime: fields.get(19)?.parse().ok()?,
+    })
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct StatusFields {
+    uid: Option<u32>,
+    gid: Option<u32>,
+    rss_bytes: u64,
+}
+
+fn parse_status(contents: &str) -> StatusFields {
+    let mut out = StatusFields::default();
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key {
+            // Real id is the first of the four columns
+            "Uid" => out.uid = value.split_whitespace().next().and_then(|v| v.parse().ok()),
+            "Gid" => out.gid = value.split_whitespace().next().and_then(|v| v.parse().ok()),
+            "VmRSS" => out.rss_bytes = parse_kb(value).unwrap_or(0),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn parse_cmdline(raw: &[u8]) -> String {
+    raw.split(|&b| b == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn map_state(state: char) -> ProcessState {
+    match state {
+        'R' => ProcessState::Running,
+        'S' | 'D' => ProcessState::Sleeping,
+        'T' | 't' => ProcessState::Stopped,
+        'Z' | 'X' | 'x' => ProcessState::Zombie,
+        'I' | 'P' => ProcessState::Idle,
+        _ => ProcessState::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAT: &str = "4242 (tmux: server) S 1 4242 4242 0 -1 4194560 1200 0 3 0 \
+        250 75 12 4 20 0 1 0 8123 12345678 900 18446744073709551615 1 1 0 0 0 0 0 \
+        3670016 1266777851 0 0 0 17 3 0 0 0 0 0";
+
+    #[test]
+    fn parse_stat_handles_spaces_in_comm() {
+        let stat = parse_stat(STAT).unwrap();
+        assert_eq!(
+            stat,
+            StatFields {
+                comm: "tmux: server".to_string(),
+                state: 'S',
+                ppid: 1,
+                utime: 250,
+                stime: 75,
+                cutime: 12,
+                cstime: 4,
+                priority: 20,
+                starttime: 8123,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_stat_rejects_truncated_input() {
+        assert!(parse_stat("12 (sh) S 1").is_none());
+    }
+
+    #[test]
+    fn parse_status_reads_ids_and_rss() {
+        let status = "Name:\tbash\nUid:\t1000\t1000\t1000\t1000\n\
+            Gid:\t100\t100\t100\t100\nVmRSS:\t    5120 kB\n";
+        let fields = parse_status(status);
+        assert_eq!(fields.uid, Some(1000));
+        assert_eq!(fields.gid, Some(100));
+        assert_eq!(fields.rss_bytes, 5120 * 1024);
+    }
+
+    #[test]
+    fn parse_status_kernel_thread_has_no_rss() {
+        let fields = parse_status("Name:\tkthreadd\nUid:\t0\t0\t0\t0\n");
+        assert_eq!(fields.rss_bytes, 0);
+    }
+
+    #[test]
+    fn parse_cmdline_joins_nul_separated_args() {
+        assert_eq!(
+            parse_cmdline(b"nginx\0-g\0daemon off;\0"),
+            "nginx -g daemon off;"
+        );
+        assert_eq!(parse_cmdline(b""), "");
+    }
+
+    #[test]
+    fn state_chars_map_to_process_state() {
+        assert_eq!(map_state('R'), ProcessState::Running);
+        assert_eq!(map_state('D'), ProcessState::Sleeping);
+        assert_eq!(map_state('t'), ProcessState::Stopped);
+        assert_eq!(map_state('Z'), ProcessState::Zombie);
+        assert_eq!(map_state('I'), ProcessState::Idle);
+        assert_eq!(map_state('?'), ProcessState::Unknown);
+    }
+}
@@ -1,4 +1,13 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
 use super::{IoStats, PlatformExtensions};
+use crate::system::collector::CollectorBackend;
+use crate::system::container::ContainerInfo;
+use crate::system::fds::OpenFile;
+use crate::system::memmap::MemoryMap;
+use crate::system::net::ProcessSockets;
+use crate::system::priority::{IoPriority, SchedPolicy};
 
 pub struct Platform;
 
@@ -16,6 +25,11 @@ impl PlatformExtensions for Platform {
         None
     }
 
+    fn process_container(_pid: u32) -> Option<ContainerInfo> {
+        // Containers on Windows run inside a Linux VM, invisible from the host
+        None
+    }
+
     #[cfg(target_os = "windows")]
     fn process_priority(pid: u32) -> Option<i32> {
         unsafe {
@@ -58,4 +72,59 @@ impl PlatformExtensions for Platform {
     fn process_io(_pid: u32) -> Option<IoStats> {
         None
     }
+
+    fn process_nice(_pid: u32) -> Option<i32> {
+        // Windows has priority classes, not nice values
+        None
+    }
+
+    fn set_process_nice(_pid: u32, _nice: i32) -> std::io::Result<()> {
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    fn process_io_priority(_pid: u32) -> Option<IoPriority> {
+        None
+    }
+
+    fn set_process_io_priority(_pid: u32, _priority: IoPriority) -> std::io::Result<()> {
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    fn process_sched_policy(_pid: u32) -> Option<SchedPolicy> {
+        None
+    }
+
+    fn process_memory_map(_pid: u32) -> Option<MemoryMap> {
+        // Windows has no smaps equivalent
+        None
+    }
+
+    fn process_start_ticks(_pid: u32) -> Option<u64> {
+        None
+    }
+
+    fn process_fd_limit(_pid: u32) -> Option<u64> {
+        None
+    }
+
+    fn process_open_files(_pid: u32) -> Option<Vec<OpenFile>> {
+        None
+    }
+
+    fn process_sockets(_pid: u32) -> Option<ProcessSockets> {
+        // GetExtendedTcpTable would be the source; not wired up on Windows yet
+        None
+    }
+
+    fn port_owners(_port: u16) -> HashSet<u32> {
+        HashSet::new()
+    }
+
+    fn cgroup_root() -> Option<PathBuf> {
+        None
+    }
+
+    fn native_backend() -> Option<Box<dyn CollectorBackend>> {
+        None
+    }
 }
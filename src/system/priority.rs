@@ -0,0 +1,291 @@
+use std::fmt;
+
+use sysinfo::{Pid, System};
+
+use crate::system::platform;
+use crate::system::process::ProcessInfo;
+use crate::system::safety::SafetyPolicy;
+
+/// Lowest and highest nice values.
+pub const NICE_RANGE: std::ops::RangeInclusive<i32> = -20..=19;
+
+/// I/O scheduling class from `ioprio_get`/`ioprio_set`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoClass {
+    /// No explicit class; the kernel derives best-effort from the nice value.
+    None,
+    RealTime,
+    BestEffort,
+    Idle,
+}
+
+/// An I/O priority: a class and, for real-time and best-effort, a level
+/// from 0 (highest) to 7.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoPriority {
+    pub class: IoClass,
+    pub level: u8,
+}
+
+const IOPRIO_CLASS_SHIFT: u32 = 13;
+
+impl IoPriority {
+    /// Decodes the value returned by `ioprio_get`.
+    pub fn from_raw(raw: u32) -> Option<Self> {
+        let class = match raw >> IOPRIO_CLASS_SHIFT {
+            0 => IoClass::None,
+            1 => IoClass::RealTime,
+            2 => IoClass::BestEffort,
+            3 => IoClass::Idle,
+            _ => return None,
+        };
+        Some(Self {
+            class,
+            level: (raw & ((1 << IOPRIO_CLASS_SHIFT) - 1)).min(7) as u8,
+        })
+    }
+
+    /// Encodes the value passed to `ioprio_set`.
+    pub fn to_raw(self) -> u32 {
+        let class = match self.class {
+            IoClass::None => 0,
+            IoClass::RealTime => 1,
+            IoClass::BestEffort => 2,
+            IoClass::Idle => 3,
+        };
+        (class << IOPRIO_CLASS_SHIFT) | u32::from(self.level.min(7))
+    }
+
+    /// Next class offered by the UI: best-effort, then idle, then back to
+    /// the nice-derived default. Real-time is never offered; it can starve
+    /// the whole machine.
+    pub fn next_class(self) -> Self {
+        match self.class {
+            IoClass::None | IoClass::RealTime => Self {
+                class: IoClass::BestEffort,
+                level: 4,
+            },
+            IoClass::BestEffort => Self {
+                class: IoClass::Idle,
+                level: 0,
+            },
+            IoClass::Idle => Self {
+                class: IoClass::None,
+                level: 0,
+            },
+        }
+    }
+}
+
+impl fmt::Display for IoPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.class {
+            IoClass::None => write!(f, "none"),
+            IoClass::RealTime => write!(f, "realtime/{}", self.level),
+            IoClass::BestEffort => write!(f, "best-effort/{}", self.level),
+            IoClass::Idle => write!(f, "idle"),
+        }
+    }
+}
+
+/// CPU scheduling policy from `sched_getscheduler`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedPolicy {
+    Other,
+    Fifo,
+    RoundRobin,
+    Batch,
+    Idle,
+    Deadline,
+    Unknown(i32),
+}
+
+impl SchedPolicy {
+    /// Decodes a Linux `SCHED_*` value; `SCHED_RESET_ON_FORK` is ignored.
+    pub fn from_raw(raw: i32) -> Self {
+        match raw & !0x4000_0000 {
+            0 => SchedPolicy::Other,
+            1 => SchedPolicy::Fifo,
+            2 => SchedPolicy::RoundRobin,
+            3 => SchedPolicy::Batch,
+            5 => SchedPolicy::Idle,
+            6 => SchedPolicy::Deadline,
+            other => SchedPolicy::Unknown(other),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            SchedPolicy::Other => "other".to_string(),
+            SchedPolicy::Fifo => "fifo".to_string(),
+            SchedPolicy::RoundRobin => "rr".to_string(),
+            SchedPolicy::Batch => "batch".to_string(),
+            SchedPolicy::Idle => "idle".to_string(),
+            SchedPolicy::Deadline => "deadline".to_string(),
+            SchedPolicy::Unknown(raw) => format!("policy {raw}"),
+        }
+    }
+}
+
+/// Outcome of changing a process's CPU or I/O priority.
+#[derive(Debug)]
+pub enum PriorityResult {
+    /// `setting` is what was applied, e.g. `nice 10` or `io idle`.
+    Success {
+        pid: u32,
+        setting: String,
+    },
+    Failed {
+        pid: u32,
+        setting: String,
+        /// OS error text, e.g. "Permission denied (os error 13)".
+        reason: String,
+    },
+    NotFound(u32),
+    /// The PID now belongs to a different process than the one targeted;
+    /// nothing was changed.
+    PidReused(u32),
+    /// Read-only mode or a protection rule forbids changing the process.
+    Refused {
+        pid: u32,
+        reason: String,
+    },
+}
+
+/// Sets the nice value of `target`, clamped to [`NICE_RANGE`]. Lowering it
+/// below the current value usually needs `CAP_SYS_NICE` or root.
+pub fn renice(
+    sys: &System,
+    target: &ProcessInfo,
+    nice: i32,
+    policy: &SafetyPolicy,
+) -> PriorityResult {
+    let nice = nice.clamp(*NICE_RANGE.start(), *NICE_RANGE.end());
+    apply(
+        sys,
+        target,
+        "renice",
+        format!("nice {nice}"),
+        policy,
+        |pid| platform::set_process_nice(pid, nice),
+    )
+}
+
+/// Sets the I/O priority of `target`.
+pub fn set_io_priority(
+    sys: &System,
+    target: &ProcessInfo,
+    priority: IoPriority,
+    policy: &SafetyPolicy,
+) -> PriorityResult {
+    apply(
+        sys,
+        target,
+        "ioprio",
+        format!("io {priority}"),
+        policy,
+        |pid| platform::set_process_io_priority(pid, priority),
+    )
+}
+
+/// Runs `change` on `target` after the same policy and PID-reuse checks as
+/// `kill_process`, and records the attempt in the audit log.
+fn apply(
+    sys: &System,
+    target: &ProcessInfo,
+    action: &str,
+    setting: String,
+    policy: &SafetyPolicy,
+    change: impl FnOnce(u32) -> std::io::Result<()>,
+) -> PriorityResult {
+    let pid = target.pid;
+    let result = match sys.process(Pid::from_u32(pid)) {
+        None => PriorityResult::NotFound(pid),
+        Some(process) => {
+            let start = platform::process_start_ticks(pid).or(Some(process.start_time()));
+            if let Some(reason) = policy.refusal_in(sys, pid) {
+                PriorityResult::Refused { pid, reason }
+            } else if !target.identity().matches(start) {
+                PriorityResult::PidReused(pid)
+            } else {
+                match change(pid) {
+                    Ok(()) => PriorityResult::Success {
+                        pid,
+                        setting: setting.clone(),
+                    },
+                    Err(err) => PriorityResult::Failed {
+                        pid,
+                        setting: setting.clone(),
+                        reason: err.to_string(),
+                    },
+                }
+            }
+        }
+    };
+
+    if let Some(log) = policy.audit() {
+        let mut entry = log.entry_for(action, target);
+        entry.setting = Some(setting);
+        let (outcome, detail) = match &result {
+            PriorityResult::Success { .. } => ("applied", None),
+            PriorityResult::NotFound(_) => ("not_found", None),
+            PriorityResult::PidReused(_) => ("pid_reused", None),
+            PriorityResult::Refused { reason, .. } => ("refused", Some(reason.clone())),
+            PriorityResult::Failed { reason, .. } => ("failed", Some(reason.clone())),
+        };
+        entry.result = outcome.to_string();
+        entry.detail = detail;
+        log.append(&entry);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn io_priority_round_trips_through_raw() {
+        let idle = IoPriority {
+            class: IoClass::Idle,
+            level: 0,
+        };
+        assert_eq!(idle.to_raw(), 3 << 13);
+        let be = IoPriority::from_raw((2 << 13) | 4).unwrap();
+        assert_eq!(
+            be,
+            IoPriority {
+                class: IoClass::BestEffort,
+                level: 4
+            }
+        );
+        assert_eq!(IoPriority::from_raw(be.to_raw()), Some(be));
+        assert_eq!(IoPriority::from_raw(7 << 13), None);
+        assert_eq!(be.to_string(), "best-effort/4");
+    }
+
+    #[test]
+    fn io_class_cycle_skips_realtime() {
+        let mut priority = IoPriority {
+            class: IoClass::None,
+            level: 0,
+        };
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            priority = priority.next_class();
+            seen.push(priority.class);
+        }
+        assert_eq!(
+            seen,
+            vec![IoClass::BestEffort, IoClass::Idle, IoClass::None]
+        );
+    }
+
+    #[test]
+    fn sched_policy_ignores_reset_on_fork() {
+        assert_eq!(SchedPolicy::from_raw(0), SchedPolicy::Other);
+        assert_eq!(SchedPolicy::from_raw(0x4000_0002), SchedPolicy::RoundRobin);
+        assert_eq!(SchedPolicy::from_raw(5).label(), "idle");
+        assert_eq!(SchedPolicy::from_raw(9).label(), "policy 9");
+    }
+}
@@ -1,15 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
+
+use crate::system::container::ContainerInfo;
 use crate::system::platform::IoStats;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProcessState {
     Running,
     Sleeping,
     Stopped,
     Zombie,
     Idle,
+    /// Also what states written by a newer build read as.
+    #[serde(other)]
     Unknown,
 }
 
@@ -32,7 +38,155 @@ impl fmt::Display for ProcessState {
     }
 }
 
-#[derive(Clone, Debug)]
+/// Which memory figure sizes tiles, drives the memory heatmap and leads the
+/// detail panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MemoryMetric {
+    /// Resident set size; shared pages count once per process.
+    #[default]
+    Rss,
+    /// Proportional set size; shared pages are split between their users.
+    Pss,
+    /// Unique set size; private pages only.
+    Uss,
+    Swap,
+    Virtual,
+}
+
+impl MemoryMetric {
+    pub fn next(self) -> Self {
+        match self {
+            MemoryMetric::Rss => MemoryMetric::Pss,
+            MemoryMetric::Pss => MemoryMetric::Uss,
+            MemoryMetric::Uss => MemoryMetric::Swap,
+            MemoryMetric::Swap => MemoryMetric::Virtual,
+            MemoryMetric::Virtual => MemoryMetric::Rss,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MemoryMetric::Rss => "RSS",
+            MemoryMetric::Pss => "PSS",
+            MemoryMetric::Uss => "USS",
+            MemoryMetric::Swap => "Swap",
+            MemoryMetric::Virtual => "Virtual",
+        }
+    }
+
+    pub fn from_str_config(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "pss" => MemoryMetric::Pss,
+            "uss" => MemoryMetric::Uss,
+            "swap" => MemoryMetric::Swap,
+            "virtual" | "vsz" => MemoryMetric::Virtual,
+            _ => MemoryMetric::Rss,
+        }
+    }
+
+    /// PSS and USS come from `smaps_rollup`, which is too costly to read for
+    /// every process unless the metric actually needs it.
+    pub fn needs_smaps(self) -> bool {
+        matches!(self, MemoryMetric::Pss | MemoryMetric::Uss)
+    }
+}
+
+/// Memory figures beyond sysinfo's RSS. Each is `None` when the platform
+/// doesn't expose it or the process isn't readable by the current user.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryDetail {
+    pub pss: Option<u64>,
+    pub uss: Option<u64>,
+    pub swap: Option<u64>,
+    pub virtual_bytes: Option<u64>,
+}
+
+/// What tile area represents in the process views.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SizeMode {
+    /// The active [`MemoryMetric`].
+    #[default]
+    Memory,
+    /// Combined read and write throughput since the previous refresh.
+    IoRate,
+}
+
+impl SizeMode {
+    pub fn next(self) -> Self {
+        match self {
+            SizeMode::Memory => SizeMode::IoRate,
+            SizeMode::IoRate => SizeMode::Memory,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SizeMode::Memory => "memory",
+            SizeMode::IoRate => "I/O rate",
+        }
+    }
+
+    pub fn from_str_config(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "io" | "io_rate" | "io-rate" => SizeMode::IoRate,
+            _ => SizeMode::Memory,
+        }
+    }
+}
+
+/// Disk throughput since the previous refresh, in bytes per second.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IoRate {
+    pub read_per_sec: u64,
+    pub write_per_sec: u64,
+}
+
+impl IoRate {
+    pub fn total(self) -> u64 {
+        self.read_per_sec.saturating_add(self.write_per_sec)
+    }
+}
+
+/// A PID together with the start time of the process holding it, so a
+/// process can be told apart from a later one that reuses the PID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ProcessIdentity {
+    pub pid: u32,
+    /// Clock ticks since boot where the platform reports them, seconds
+    /// since the epoch otherwise. `None` when unknown; such an identity
+    /// matches any process on `pid`.
+    pub start: Option<u64>,
+}
+
+impl ProcessIdentity {
+    /// Whether a process that started at `start`, in the same units, is
+    /// this one.
+    pub fn matches(self, start: Option<u64>) -> bool {
+        self.start.is_none_or(|expected| start == Some(expected))
+    }
+}
+
+/// CPU time consumed over the life of a process.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CpuTimes {
+    pub user: Duration,
+    pub system: Duration,
+    /// Time of waited-for children that have exited (`cutime`/`cstime`).
+    pub children_user: Duration,
+    pub children_system: Duration,
+}
+
+impl CpuTimes {
+    /// User plus system time of the process itself.
+    pub fn total(self) -> Duration {
+        self.user + self.system
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub ppid: u32,
@@ -43,10 +197,68 @@ pub struct ProcessInfo {
     pub user_id: Option<String>,
     pub group_id: Option<String>,
     pub status: ProcessState,
+    /// Rebuilt from `ppid` links, so never stored.
+    #[serde(skip)]
     pub children: Vec<u32>,
     pub group_name: Option<String>,
     pub priority: Option<i32>,
     pub io_stats: Option<IoStats>,
+    pub memory_detail: MemoryDetail,
+    pub container: Option<ContainerInfo>,
+    /// `None` until two I/O samples of this PID have been seen.
+    pub io_rate: Option<IoRate>,
+    /// Entries in `/proc/<pid>/fd`; `None` when unreadable.
+    pub fd_count: Option<u32>,
+    /// Seconds since the Unix epoch, for display.
+    pub start_time: Option<u64>,
+    /// Start in clock ticks since boot, where the platform has them.
+    /// Together with `pid` this identifies a process across refreshes even
+    /// when the PID is reused within the same second.
+    #[serde(default)]
+    pub start_ticks: Option<u64>,
+    pub cpu_time: Option<CpuTimes>,
+}
+
+impl ProcessInfo {
+    /// Memory attributed to this process under `metric`.
+    ///
+    /// PSS and USS fall back to RSS when unreadable so unprivileged sessions
+    /// still get a usable map of other users' processes.
+    pub fn memory_for(&self, metric: MemoryMetric) -> u64 {
+        let detail = &self.memory_detail;
+        match metric {
+            MemoryMetric::Rss => self.memory_bytes,
+            MemoryMetric::Pss => detail.pss.unwrap_or(self.memory_bytes),
+            MemoryMetric::Uss => detail.uss.unwrap_or(self.memory_bytes),
+            MemoryMetric::Swap => detail.swap.unwrap_or(0),
+            MemoryMetric::Virtual => detail.virtual_bytes.unwrap_or(0),
+        }
+    }
+
+    /// Whether `metric` shows RSS for this process because its PSS or USS
+    /// could not be read.
+    pub fn falls_back_to_rss(&self, metric: MemoryMetric) -> bool {
+        match metric {
+            MemoryMetric::Pss => self.memory_detail.pss.is_none(),
+            MemoryMetric::Uss => self.memory_detail.uss.is_none(),
+            _ => false,
+        }
+    }
+
+    pub fn identity(&self) -> ProcessIdentity {
+        ProcessIdentity {
+            pid: self.pid,
+            start: self.start_ticks.or(self.start_time),
+        }
+    }
+
+    /// Own tile value under `size`, using `metric` when sizing by memory.
+    pub fn size_for(&self, size: SizeMode, metric: MemoryMetric) -> u64 {
+        match size {
+            SizeMode::Memory => self.memory_for(metric),
+            SizeMode::IoRate => self.io_rate.map(IoRate::total).unwrap_or(0),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -78,27 +290,53 @@ pub fn build_process_tree_from_flat(processes: Vec<ProcessInfo>) -> ProcessTree
 }
 
 impl ProcessTree {
-    /// Compute subtree sizes for all processes, returned as a map.
-    pub fn all_subtree_sizes(&self) -> HashMap<u32, u64> {
+    /// `pid` followed by all of its descendants, every parent before its
+    /// children. Empty when `pid` is not in the tree.
+    pub fn subtree(&self, pid: u32) -> Vec<u32> {
+        let mut order = Vec::new();
+        let mut seen = HashSet::new();
+        let mut stack = vec![pid];
+        while let Some(pid) = stack.pop() {
+            let Some(process) = self.processes.get(&pid) else {
+                continue;
+            };
+            if !seen.insert(pid) {
+                continue;
+            }
+            order.push(pid);
+            stack.extend(process.children.iter().rev());
+        }
+        order
+    }
+
+    /// Compute subtree sizes under `size` and `metric` for all processes,
+    /// returned as a map.
+    pub fn all_subtree_sizes(&self, size: SizeMode, metric: MemoryMetric) -> HashMap<u32, u64> {
         let mut cache = HashMap::new();
         for &pid in self.processes.keys() {
-            self.subtree_memory_cached(pid, &mut cache);
+            self.subtree_size_cached(pid, size, metric, &mut cache);
         }
         cache
     }
 
-    fn subtree_memory_cached(&self, pid: u32, cache: &mut HashMap<u32, u64>) -> u64 {
+    fn subtree_size_cached(
+        &self,
+        pid: u32,
+        size: SizeMode,
+        metric: MemoryMetric,
+        cache: &mut HashMap<u32, u64>,
+    ) -> u64 {
         if let Some(&cached) = cache.get(&pid) {
             return cached;
         }
         let Some(proc) = self.processes.get(&pid) else {
             return 0;
         };
-        let own = proc.memory_bytes;
+        let own = proc.size_for(size, metric);
         let children_sum: u64 = proc
             .children
             .iter()
-            .map(|&child| self.subtree_memory_cached(child, cache))
+            .map(|&child| self.subtree_size_cached(child, size, metric, cache))
             .sum();
         let total = own + children_sum;
         cache.insert(pid, total);
@@ -127,6 +365,13 @@ mod tests {
                 group_name: None,
                 priority: None,
                 io_stats: None,
+                memory_detail: MemoryDetail::default(),
+                container: None,
+                io_rate: None,
+                fd_count: None,
+                start_time: None,
+                start_ticks: None,
+                cpu_time: None,
             },
             ProcessInfo {
                 pid: 2,
@@ -142,6 +387,13 @@ mod tests {
                 group_name: None,
                 priority: None,
                 io_stats: None,
+                memory_detail: MemoryDetail::default(),
+                container: None,
+                io_rate: None,
+                fd_count: None,
+                start_time: None,
+                start_ticks: None,
+                cpu_time: None,
             },
             ProcessInfo {
                 pid: 3,
@@ -157,6 +409,13 @@ mod tests {
                 group_name: None,
                 priority: None,
                 io_stats: None,
+                memory_detail: MemoryDetail::default(),
+                container: None,
+                io_rate: None,
+                fd_count: None,
+                start_time: None,
+                start_ticks: None,
+                cpu_time: None,
             },
             ProcessInfo {
                 pid: 4,
@@ -172,6 +431,13 @@ mod tests {
                 group_name: None,
                 priority: None,
                 io_stats: None,
+                memory_detail: MemoryDetail::default(),
+                container: None,
+                io_rate: None,
+                fd_count: None,
+                start_time: None,
+                start_ticks: None,
+                cpu_time: None,
             },
         ];
         build_process_tree_from_flat(processes)
@@ -180,13 +446,91 @@ mod tests {
     #[test]
     fn all_subtree_sizes_complete() {
         let tree = build_tree();
-        let sizes = tree.all_subtree_sizes();
+        let sizes = tree.all_subtree_sizes(SizeMode::Memory, MemoryMetric::Rss);
         assert_eq!(sizes[&1], 225);
         assert_eq!(sizes[&2], 75);
         assert_eq!(sizes[&3], 50);
         assert_eq!(sizes[&4], 25);
     }
 
+    #[test]
+    fn subtree_lists_parents_before_children() {
+        let tree = build_tree();
+        assert_eq!(tree.subtree(1), vec![1, 2, 4, 3]);
+        assert_eq!(tree.subtree(2), vec![2, 4]);
+        assert_eq!(tree.subtree(3), vec![3]);
+        assert!(tree.subtree(99).is_empty());
+    }
+
+    #[test]
+    fn identity_tells_apart_starts_within_one_second() {
+        let mut tree = build_tree();
+        let first = tree.processes.get_mut(&4).unwrap();
+        first.start_time = Some(1_740_230_000);
+        first.start_ticks = Some(500);
+        let identity = first.identity();
+
+        let mut reused = first.clone();
+        reused.start_ticks = Some(550);
+        assert!(!identity.matches(reused.identity().start));
+
+        // Without ticks the seconds stand in
+        reused.start_ticks = None;
+        assert_eq!(reused.identity().start, Some(1_740_230_000));
+    }
+
+    #[test]
+    fn subtree_sizes_follow_metric() {
+        let mut tree = build_tree();
+        for (pid, pss) in [(1, 40), (2, 20), (3, 10)] {
+            tree.processes.get_mut(&pid).unwrap().memory_detail.pss = Some(pss);
+        }
+        let sizes = tree.all_subtree_sizes(SizeMode::Memory, MemoryMetric::Pss);
+        // PID 4 has no PSS reading and falls back to its RSS of 25.
+        assert_eq!(sizes[&1], 40 + 20 + 10 + 25);
+        assert_eq!(sizes[&2], 45);
+
+        let swap = tree.all_subtree_sizes(SizeMode::Memory, MemoryMetric::Swap);
+        assert_eq!(swap[&1], 0);
+    }
+
+    #[test]
+    fn subtree_sizes_by_io_rate() {
+        let mut tree = build_tree();
+        for (pid, read, write) in [(2, 1000, 500), (4, 0, 250)] {
+            tree.processes.get_mut(&pid).unwrap().io_rate = Some(IoRate {
+                read_per_sec: read,
+                write_per_sec: write,
+            });
+        }
+        let sizes = tree.all_subtree_sizes(SizeMode::IoRate, MemoryMetric::Rss);
+        assert_eq!(sizes[&1], 1750);
+        assert_eq!(sizes[&2], 1750);
+        assert_eq!(sizes[&3], 0, "no sample yet counts as idle");
+        assert_eq!(SizeMode::from_str_config("io"), SizeMode::IoRate);
+        assert_eq!(SizeMode::IoRate.next(), SizeMode::Memory);
+    }
+
+    #[test]
+    fn memory_metric_cycles_and_parses() {
+        let mut metric = MemoryMetric::Rss;
+        for expected in [
+            MemoryMetric::Pss,
+            MemoryMetric::Uss,
+            MemoryMetric::Swap,
+            MemoryMetric::Virtual,
+            MemoryMetric::Rss,
+        ] {
+            metric = metric.next();
+            assert_eq!(metric, expected);
+        }
+        assert_eq!(MemoryMetric::from_str_config("PSS"), MemoryMetric::Pss);
+        assert_eq!(MemoryMetric::from_str_config("vsz"), MemoryMetric::Virtual);
+        assert_eq!(MemoryMetric::from_str_config("nope"), MemoryMetric::Rss);
+        assert!(MemoryMetric::Uss.needs_smaps());
+        assert!(!MemoryMetric::Swap.needs_smaps());
+    }
+
     #[test]
     fn process_state_display_round_trip() {
         let states = [
@@ -0,0 +1,692 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::system::container::ContainerInfo;
+use crate::system::platform::IoStats;
+use crate::system::process::{
+    CpuTimes, IoRate, MemoryDetail, ProcessInfo, ProcessState, build_process_tree_from_flat,
+};
+use crate::system::snapshot::SystemSnapshot;
+
+/// `format` of the first line of every recording.
+const FORMAT: &str = "treetop-recording";
+
+/// Newest recording version this build writes and reads. Version 2 lists
+/// only the fields of a process that changed; version 1 frames, which list
+/// every field, read the same way.
+pub const VERSION: u32 = 2;
+
+/// Frames between full process lists, so a damaged or truncated file loses
+/// at most this many frames and replay can seek without decoding from the
+/// start.
+const KEYFRAME_INTERVAL: u64 = 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+}
+
+/// One collector refresh, as one line of a recording.
+///
+/// Keyframes list every process in full; the frames in between only list
+/// the processes that appeared or changed, with just the fields that
+/// changed, and the PIDs that exited since the frame before.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Frame {
+    /// Milliseconds since the Unix epoch.
+    pub time_ms: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub keyframe: bool,
+    pub cpu_usage_percent: f32,
+    pub memory_total: u64,
+    pub memory_used: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+    pub cpu_per_core: Vec<f32>,
+    pub load_average: [f64; 3],
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed: Vec<ProcessRecord>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exited: Vec<u32>,
+}
+
+/// A process as stored in a frame, kept apart from [`ProcessInfo`] so the
+/// file format only changes on purpose.
+///
+/// Every field but `pid` is left out when it holds the value of the frame
+/// before, and read as unchanged when missing, so fields added later are
+/// simply absent from older recordings. Fields that can be empty are
+/// double options: `Some(None)` is written as `null` and clears the value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProcessRecord {
+    pub pid: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ppid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<f32>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<Option<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ProcessState>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_name: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub io_stats: Option<Option<IoStats>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_detail: Option<MemoryDetail>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<Option<ContainerInfo>>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub io_rate: Option<Option<IoRate>>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fd_count: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<Option<u64>>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_ticks: Option<Option<u64>>,
+    #[serde(default, deserialize_with = "present")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_time: Option<Option<CpuTimes>>,
+}
+
+/// Reads a field that is present, `null` included, as `Some`; a missing
+/// one falls back to `None` through `#[serde(default)]`.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
+
+/// `new` unless it equals `old`.
+fn changed<T: PartialEq + Clone>(old: Option<&T>, new: &T) -> Option<T> {
+    (old != Some(new)).then(|| new.clone())
+}
+
+impl ProcessRecord {
+    /// The fields of `current` that differ from `previous`, or every field
+    /// without one. `None` when nothing changed.
+    fn diff(previous: Option<&ProcessInfo>, current: &ProcessInfo) -> Option<Self> {
+        let record = Self {
+            pid: current.pid,
+            ppid: changed(previous.map(|p| &p.ppid), &current.ppid),
+            name: changed(previous.map(|p| &p.name), &current.name),
+            command: changed(previous.map(|p| &p.command), &current.command),
+            memory_bytes: changed(previous.map(|p| &p.memory_bytes), &current.memory_bytes),
+            cpu_percent: changed(previous.map(|p| &p.cpu_percent), &current.cpu_percent),
+            user_id: changed(previous.map(|p| &p.user_id), &current.user_id),
+            group_id: changed(previous.map(|p| &p.group_id), &current.group_id),
+            status: changed(previous.map(|p| &p.status), &current.status),
+            group_name: changed(previous.map(|p| &p.group_name), &current.group_name),
+            priority: changed(previous.map(|p| &p.priority), &current.priority),
+            io_stats: changed(previous.map(|p| &p.io_stats), &current.io_stats),
+            memory_detail: changed(previous.map(|p| &p.memory_detail), &current.memory_detail),
+            container: changed(previous.map(|p| &p.container), &current.container),
+            io_rate: changed(previous.map(|p| &p.io_rate), &current.io_rate),
+            fd_count: changed(previous.map(|p| &p.fd_count), &current.fd_count),
+            start_time: changed(previous.map(|p| &p.start_time), &current.start_time),
+            start_ticks: changed(previous.map(|p| &p.start_ticks), &current.start_ticks),
+            cpu_time: changed(previous.map(|p| &p.cpu_time), &current.cpu_time),
+        };
+        let unchanged = Self {
+            pid: current.pid,
+            ..Self::default()
+        };
+        (previous.is_none() || record != unchanged).then_some(record)
+    }
+
+    /// Updates the process on `pid` in `processes` with the fields this
+    /// record holds, starting from an empty one when the PID is new.
+    fn apply(&self, processes: &mut HashMap<u32, ProcessInfo>) {
+        let p = processes
+            .entry(self.pid)
+            .or_insert_with(|| empty_process(self.pid));
+        set(&mut p.ppid, &self.ppid);
+        set(&mut p.name, &self.name);
+        set(&mut p.command, &self.command);
+        set(&mut p.memory_bytes, &self.memory_bytes);
+        set(&mut p.cpu_percent, &self.cpu_percent);
+        set(&mut p.user_id, &self.user_id);
+        set(&mut p.group_id, &self.group_id);
+        set(&mut p.status, &self.status);
+        set(&mut p.group_name, &self.group_name);
+        set(&mut p.priority, &self.priority);
+        set(&mut p.io_stats, &self.io_stats);
+        set(&mut p.memory_detail, &self.memory_detail);
+        set(&mut p.container, &self.container);
+        set(&mut p.io_rate, &self.io_rate);
+        set(&mut p.fd_count, &self.fd_count);
+        set(&mut p.start_time, &self.start_time);
+        set(&mut p.start_ticks, &self.start_ticks);
+        set(&mut p.cpu_time, &self.cpu_time);
+    }
+}
+
+fn set<T: Clone>(field: &mut T, value: &Option<T>) {
+    if let Some(value) = value {
+        field.clone_from(value);
+    }
+}
+
+/// What a record's missing fields read as for a PID not seen before.
+fn empty_process(pid: u32) -> ProcessInfo {
+    ProcessInfo {
+        pid,
+        ppid: 0,
+        name: String::new(),
+        command: String::new(),
+        memory_bytes: 0,
+        cpu_percent: 0.0,
+        user_id: None,
+        group_id: None,
+        status: ProcessState::Unknown,
+        children: Vec::new(),
+        group_name: None,
+        priority: None,
+        io_stats: None,
+        memory_detail: MemoryDetail::default(),
+        container: None,
+        io_rate: None,
+        fd_count: None,
+        start_time: None,
+        start_ticks: None,
+        cpu_time: None,
+    }
+}
+
+impl Frame {
+    /// Brings `processes`, the state after the previous frame, up to this one.
+    fn apply(&self, processes: &mut HashMap<u32, ProcessInfo>) {
+        if self.keyframe {
+            processes.clear();
+        }
+        for pid in &self.exited {
+            processes.remove(pid);
+        }
+        for record in &self.changed {
+            record.apply(processes);
+        }
+    }
+
+    fn to_snapshot(&self, processes: &HashMap<u32, ProcessInfo>) -> SystemSnapshot {
+        SystemSnapshot {
+            cpu_usage_percent: self.cpu_usage_percent,
+            memory_total: self.memory_total,
+            memory_used: self.memory_used,
+            swap_total: self.swap_total,
+            swap_used: self.swap_used,
+            cpu_per_core: self.cpu_per_core.clone(),
+            load_average: self.load_average,
+            process_tree: build_process_tree_from_flat(processes.values().cloned().collect()),
+        }
+    }
+}
+
+/// Milliseconds since the Unix epoch, the clock frames are stamped with.
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// Appends snapshots to a recording, delta-encoded against the previous one.
+#[derive(Debug)]
+pub struct Recorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    /// Processes as of the last frame written, without `children`.
+    previous: HashMap<u32, ProcessInfo>,
+    frames: u64,
+}
+
+impl Recorder {
+    /// Opens `path` for appending, writing the header when the file is new.
+    /// An existing file must be a recording of this build's version; a
+    /// frame cut off at its end by a crash is dropped.
+    pub fn create(path: PathBuf) -> io::Result<Self> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)?;
+        let mut len = file.metadata()?.len();
+        if len > 0 {
+            let header = read_header(&mut BufReader::new(&file))?;
+            if header.version != VERSION {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "recording version {} cannot be appended to, this build writes version {VERSION}",
+                        header.version
+                    ),
+                ));
+            }
+            // A line cut off by a crash would make the file unreadable
+            // once more frames follow it
+            let complete = complete_len(&file, len)?;
+            if complete < len {
+                file.set_len(complete)?;
+                len = complete;
+            }
+        }
+
+        let mut writer = BufWriter::new(file);
+        if len == 0 {
+            let header = Header {
+                format: FORMAT.to_string(),
+                version: VERSION,
+            };
+            write_line(&mut writer, &header)?;
+        }
+        Ok(Self {
+            path,
+            writer,
+            previous: HashMap::new(),
+            frames: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Frames written by this recorder.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Appends `snapshot` taken at `time_ms` and flushes it, so a recording
+    /// cut short by a crash or reboot keeps every frame written so far.
+    pub fn record(&mut self, snapshot: &SystemSnapshot, time_ms: u64) -> io::Result<()> {
+        let keyframe = self.frames.is_multiple_of(KEYFRAME_INTERVAL);
+        let mut current = HashMap::with_capacity(snapshot.process_tree.processes.len());
+        let mut changed = Vec::new();
+        for (&pid, process) in &snapshot.process_tree.processes {
+            let previous = self.previous.get(&pid).filter(|_| !keyframe);
+            changed.extend(ProcessRecord::diff(previous, process));
+            let mut process = process.clone();
+            process.children.clear();
+            current.insert(pid, process);
+        }
+        // Stable order keeps recordings of the same data byte-identical
+        changed.sort_unstable_by_key(|p| p.pid);
+        let mut exited: Vec<u32> = if keyframe {
+            Vec::new()
+        } else {
+            self.previous
+                .keys()
+                .filter(|pid| !current.contains_key(pid))
+                .copied()
+                .collect()
+        };
+        exited.sort_unstable();
+
+        let frame = Frame {
+            time_ms,
+            keyframe,
+            cpu_usage_percent: snapshot.cpu_usage_percent,
+            memory_total: snapshot.memory_total,
+            memory_used: snapshot.memory_used,
+            swap_total: snapshot.swap_total,
+            swap_used: snapshot.swap_used,
+            cpu_per_core: snapshot.cpu_per_core.clone(),
+            load_average: snapshot.load_average,
+            changed,
+            exited,
+        };
+        write_line(&mut self.writer, &frame)?;
+        self.writer.flush()?;
+        self.previous = current;
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+/// Length of `file` up to and including its last line break.
+fn complete_len(mut file: &File, len: u64) -> io::Result<u64> {
+    let mut buf = [0u8; 4096];
+    let mut end = len;
+    while end > 0 {
+        let start = end.saturating_sub(buf.len() as u64);
+        let chunk = &mut buf[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(chunk)?;
+        if let Some(i) = chunk.iter().rposition(|&b| b == b'\n') {
+            return Ok(start + i as u64 + 1);
+        }
+        end = start;
+    }
+    Ok(0)
+}
+
+fn write_line(writer: &mut impl Write, value: &impl Serialize) -> io::Result<()> {
+    let mut line = serde_json::to_string(value)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())
+}
+
+fn read_header(reader: &mut impl BufRead) -> io::Result<Header> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let header: Header = serde_json::from_str(&line)
+        .ok()
+        .filter(|h: &Header| h.format == FORMAT)
+        .ok_or_else(|| invalid("not a treetop recording".to_string()))?;
+    if header.version > VERSION {
+        return Err(invalid(format!(
+            "recording version {} is newer than supported version {VERSION}",
+            header.version
+        )));
+    }
+    Ok(header)
+}
+
+/// Every frame of a recording, decoded on demand into snapshots.
+#[derive(Debug)]
+pub struct Recording {
+    pub frames: Vec<Frame>,
+}
+
+impl Recording {
+    /// Reads a recording written by [`Recorder`]. A last line cut off by
+    /// a crash mid-write is skipped; any other line that does not parse is
+    /// an error naming it.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        read_header(&mut reader)?;
+        let mut frames = Vec::new();
+        let mut line = String::new();
+        // Line 1 is the header
+        for number in 2.. {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            match serde_json::from_str::<Frame>(&line) {
+                Ok(frame) => frames.push(frame),
+                Err(_) if !line.ends_with('\n') => break,
+                Err(err) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {number} of the recording is damaged: {err}"),
+                    ));
+                }
+            }
+        }
+        // Deltas need the keyframe before them
+        let start = frames
+            .iter()
+            .position(|f| f.keyframe)
+            .unwrap_or(frames.len());
+        frames.drain(..start);
+        Ok(Self { frames })
+    }
+
+    /// The snapshot recorded as frame `index`, rebuilt from the keyframe
+    /// before it. `None` past the end.
+    pub fn snapshot_at(&self, index: usize) -> Option<SystemSnapshot> {
+        let frame = self.frames.get(index)?;
+        let start = self.frames[..=index]
+            .iter()
+            .rposition(|f| f.keyframe)
+            .unwrap_or(0);
+        let mut processes = HashMap::new();
+        for frame in &self.frames[start..=index] {
+            frame.apply(&mut processes);
+        }
+        Some(frame.to_snapshot(&processes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::process::{MemoryDetail, ProcessState};
+
+    fn process(pid: u32, ppid: u32, memory: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            ppid,
+            name: format!("proc{pid}"),
+            command: format!("/usr/bin/proc{pid} --serve"),
+            memory_bytes: memory,
+            cpu_percent: 0.0,
+            user_id: Some("1000".to_string()),
+            group_id: None,
+            status: ProcessState::Sleeping,
+            children: Vec::new(),
+            group_name: None,
+            priority: Some(20),
+            io_stats: None,
+            memory_detail: MemoryDetail::default(),
+            container: None,
+            io_rate: None,
+            fd_count: Some(4),
+            start_time: Some(1_740_230_000 + u64::from(pid)),
+            start_ticks: None,
+            cpu_time: None,
+        }
+    }
+
+    fn snapshot(processes: Vec<ProcessInfo>) -> SystemSnapshot {
+        SystemSnapshot {
+            cpu_usage_percent: 12.5,
+            memory_total: 8 << 30,
+            memory_used: 2 << 30,
+            swap_total: 0,
+            swap_used: 0,
+            cpu_per_core: vec![10.0, 15.0],
+            load_average: [0.5, 0.25, 0.125],
+            process_tree: build_process_tree_from_flat(processes),
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("treetop_{name}_{}.jsonl", std::process::id()))
+    }
+
+    #[test]
+    fn frames_hold_only_changes_and_replay_exactly() {
+        let path = temp_path("recording");
+        let _ = fs::remove_file(&path);
+        let ticks = [
+            vec![process(1, 0, 100), process(2, 1, 50), process(3, 1, 25)],
+            // 2 grows, 3 exits, 4 starts
+            vec![process(1, 0, 100), process(2, 1, 80), process(4, 1, 10)],
+            vec![process(1, 0, 100), process(2, 1, 80), process(4, 1, 10)],
+        ];
+        let mut recorder = Recorder::create(path.clone()).unwrap();
+        for (i, tick) in ticks.iter().enumerate() {
+            recorder
+                .record(&snapshot(tick.clone()), 1_000 * i as u64)
+                .unwrap();
+        }
+        assert_eq!(recorder.frames(), 3);
+        drop(recorder);
+
+        let recording = Recording::open(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(recording.frames.len(), 3);
+        assert!(recording.frames[0].keyframe);
+        assert_eq!(recording.frames[0].changed.len(), 3);
+        let changed: Vec<u32> = recording.frames[1].changed.iter().map(|p| p.pid).collect();
+        assert_eq!(changed, vec![2, 4]);
+        // Only the field that changed is written for a known process
+        assert_eq!(
+            recording.frames[1].changed[0],
+            ProcessRecord {
+                pid: 2,
+                memory_bytes: Some(80),
+                ..ProcessRecord::default()
+            }
+        );
+        assert_eq!(
+            recording.frames[1].changed[1].command.as_deref(),
+            Some("/usr/bin/proc4 --serve")
+        );
+        assert_eq!(recording.frames[1].exited, vec![3]);
+        assert!(recording.frames[2].changed.is_empty());
+        assert_eq!(recording.frames[2].time_ms, 2_000);
+
+        let last = recording.snapshot_at(2).unwrap();
+        let processes = &last.process_tree.processes;
+        let mut pids: Vec<u32> = processes.keys().copied().collect();
+        pids.sort_unstable();
+        assert_eq!(pids, vec![1, 2, 4]);
+        assert_eq!(processes[&2].memory_bytes, 80);
+        assert_eq!(processes[&1].children, vec![2, 4]);
+        assert_eq!(processes[&4].command, "/usr/bin/proc4 --serve");
+        assert_eq!(last.load_average, [0.5, 0.25, 0.125]);
+        assert!(recording.snapshot_at(3).is_none());
+    }
+
+    #[test]
+    fn cleared_fields_and_missing_fields_replay_correctly() {
+        let mut before = process(1, 0, 100);
+        before.group_name = Some("web".to_string());
+        let mut after = before.clone();
+        after.group_name = None;
+        after.cpu_percent = 3.5;
+
+        let record = ProcessRecord::diff(Some(&before), &after).unwrap();
+        let line = serde_json::to_string(&record).unwrap();
+        assert_eq!(line, r#"{"pid":1,"cpu_percent":3.5,"group_name":null}"#);
+        assert!(ProcessRecord::diff(Some(&after), &after).is_none());
+
+        let mut processes = HashMap::from([(1, before)]);
+        serde_json::from_str::<ProcessRecord>(&line)
+            .unwrap()
+            .apply(&mut processes);
+        assert_eq!(processes[&1], after);
+
+        // A record from a build with other fields and states still reads
+        let record: ProcessRecord =
+            serde_json::from_str(r#"{"pid":7,"status":"Parked","oom_score":3}"#).unwrap();
+        assert_eq!(record.status, Some(ProcessState::Unknown));
+    }
+
+    #[test]
+    fn version_1_frames_still_replay() {
+        let path = temp_path("recording_v1");
+        let full = serde_json::to_string(&process(1, 0, 100)).unwrap();
+        fs::write(
+            &path,
+            format!(
+                "{{\"format\":\"treetop-recording\",\"version\":1}}\n\
+                 {{\"time_ms\":1,\"keyframe\":true,\"cpu_usage_percent\":0.0,\
+                 \"memory_total\":0,\"memory_used\":0,\"swap_total\":0,\"swap_used\":0,\
+                 \"cpu_per_core\":[],\"load_average\":[0.0,0.0,0.0],\"changed\":[{full}]}}\n"
+            ),
+        )
+        .unwrap();
+        let recording = Recording::open(&path).unwrap();
+        let appending = Recorder::create(path.clone()).unwrap_err();
+        let _ = fs::remove_file(&path);
+        let snapshot = recording.snapshot_at(0).unwrap();
+        assert_eq!(snapshot.process_tree.processes[&1], process(1, 0, 100));
+        assert!(appending.to_string().contains("version 1"));
+    }
+
+    #[test]
+    fn damaged_lines_before_the_end_are_errors() {
+        let path = temp_path("recording_damaged");
+        let _ = fs::remove_file(&path);
+        let tick = snapshot(vec![process(1, 0, 100)]);
+        let mut recorder = Recorder::create(path.clone()).unwrap();
+        recorder.record(&tick, 1).unwrap();
+        recorder.record(&tick, 2).unwrap();
+        drop(recorder);
+        let contents = fs::read_to_string(&path).unwrap();
+        let mut lines: Vec<&str> = contents.lines().collect();
+        lines[1] = "{\"time_ms\":1,\"cpu_us";
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let err = Recording::open(&path).unwrap_err();
+        let _ = fs::remove_file(&path);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("line 2"), "{err}");
+    }
+
+    #[test]
+    fn appending_resumes_with_a_keyframe_after_a_torn_line() {
+        let path = temp_path("recording_append");
+        let _ = fs::remove_file(&path);
+        let tick = snapshot(vec![process(1, 0, 100)]);
+        let mut first = Recorder::create(path.clone()).unwrap();
+        first.record(&tick, 1).unwrap();
+        first.record(&tick, 2).unwrap();
+        drop(first);
+        // Killed halfway through a frame
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"time_ms\":3,\"cpu_us")
+            .unwrap();
+        Recorder::create(path.clone())
+            .unwrap()
+            .record(&tick, 4)
+            .unwrap();
+
+        let recording = Recording::open(&path).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(contents.matches("treetop-recording").count(), 1);
+        assert_eq!(contents.lines().count(), 4, "the torn frame is dropped");
+        let times: Vec<u64> = recording.frames.iter().map(|f| f.time_ms).collect();
+        assert_eq!(times, vec![1, 2, 4]);
+        assert!(recording.frames[2].keyframe);
+        assert_eq!(
+            recording
+                .snapshot_at(2)
+                .unwrap()
+                .process_tree
+                .processes
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn other_files_and_newer_versions_are_rejected() {
+        let path = temp_path("recording_invalid");
+        fs::write(&path, "{\"format\":\"treetop-recording\",\"version\":99}\n").unwrap();
+        let newer = Recording::open(&path).unwrap_err();
+        fs::write(&path, "pid,name\n").unwrap();
+        let foreign = Recorder::create(path.clone()).unwrap_err();
+        let _ = fs::remove_file(&path);
+        assert!(newer.to_string().contains("version 99"));
+        assert_eq!(foreign.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,252 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::system::recording::Recording;
+use crate::system::snapshot::SystemSnapshot;
+
+/// Playback speeds offered by faster/slower, as multiples of recorded time.
+const SPEEDS: [f64; 9] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0];
+const NORMAL_SPEED: usize = 2;
+
+/// Playback position and controls over a loaded [`Recording`].
+#[derive(Debug)]
+pub struct Replay {
+    path: PathBuf,
+    recording: Recording,
+    position: usize,
+    playing: bool,
+    speed: usize,
+    /// Recorded time played past the current frame, in milliseconds.
+    progress_ms: f64,
+}
+
+impl Replay {
+    /// Loads the recording at `path`; one without a complete frame is an
+    /// error.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let recording = Recording::open(path)?;
+        if recording.frames.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "recording has no frames",
+            ));
+        }
+        Ok(Self::new(path.to_path_buf(), recording))
+    }
+
+    /// Plays `recording`, read from `path`, from its first frame. It must
+    /// hold at least one frame.
+    pub fn new(path: PathBuf, recording: Recording) -> Self {
+        Self {
+            path,
+            recording,
+            position: 0,
+            playing: true,
+            speed: NORMAL_SPEED,
+            progress_ms: 0.0,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.recording.frames.len()
+    }
+
+    /// Index of the frame on screen.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn speed(&self) -> f64 {
+        SPEEDS[self.speed]
+    }
+
+    /// Recording time of the frame on screen, in milliseconds since the
+    /// Unix epoch.
+    pub fn time_ms(&self) -> u64 {
+        self.frame_time(self.position)
+    }
+
+    pub fn start_ms(&self) -> u64 {
+        self.frame_time(0)
+    }
+
+    pub fn end_ms(&self) -> u64 {
+        self.frame_time(self.frame_count() - 1)
+    }
+
+    fn frame_time(&self, index: usize) -> u64 {
+        self.recording.frames[index].time_ms
+    }
+
+    /// The frame on screen as a snapshot.
+    pub fn snapshot(&self) -> SystemSnapshot {
+        self.recording
+            .snapshot_at(self.position)
+            .expect("position is always a valid frame")
+    }
+
+    /// Pauses, or resumes from the start when the end was reached.
+    pub fn toggle_play(&mut self) {
+        if !self.playing && self.position + 1 == self.frame_count() {
+            self.position = 0;
+        }
+        self.playing = !self.playing;
+        self.progress_ms = 0.0;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+        self.progress_ms = 0.0;
+    }
+
+    /// Steps the speed up (`delta > 0`) or down through [`SPEEDS`].
+    pub fn change_speed(&mut self, delta: isize) {
+        self.speed = self
+            .speed
+            .saturating_add_signed(delta)
+            .min(SPEEDS.len() - 1);
+    }
+
+    /// Plays `elapsed` wall-clock time at the current speed. Returns whether
+    /// the frame changed; playback pauses on the last frame.
+    pub fn advance(&mut self, elapsed: Duration) -> bool {
+        if !self.playing {
+            return false;
+        }
+        self.progress_ms += elapsed.as_secs_f64() * 1000.0 * self.speed();
+        let start = self.position;
+        while self.position + 1 < self.frame_count() {
+            let gap = self
+                .frame_time(self.position + 1)
+                .saturating_sub(self.time_ms()) as f64;
+            if self.progress_ms < gap {
+                break;
+            }
+            self.progress_ms -= gap;
+            self.position += 1;
+        }
+        if self.position + 1 == self.frame_count() {
+            self.playing = false;
+            self.progress_ms = 0.0;
+        }
+        self.position != start
+    }
+
+    /// Moves `delta` frames and pauses. Returns whether the frame changed.
+    pub fn step(&mut self, delta: isize) -> bool {
+        self.playing = false;
+        let target = self.position.saturating_add_signed(delta);
+        self.seek(target)
+    }
+
+    /// Jumps to frame `index`, clamped to the recording. Returns whether the
+    /// frame changed.
+    pub fn seek(&mut self, index: usize) -> bool {
+        let index = index.min(self.frame_count() - 1);
+        let changed = index != self.position;
+        self.position = index;
+        self.progress_ms = 0.0;
+        changed
+    }
+
+    /// Jumps to the frame at `fraction` (0.0 to 1.0) of the recorded time.
+    pub fn seek_fraction(&mut self, fraction: f64) -> bool {
+        let span = self.end_ms().saturating_sub(self.start_ms()) as f64;
+        let target = self.start_ms() + (span * fraction.clamp(0.0, 1.0)) as u64;
+        let index = self
+            .recording
+            .frames
+            .partition_point(|f| f.time_ms < target)
+            .min(self.frame_count() - 1);
+        self.seek(index)
+    }
+
+    /// Where the frame on screen sits in the recorded time, 0.0 to 1.0.
+    pub fn fraction(&self) -> f64 {
+        let span = self.end_ms().saturating_sub(self.start_ms());
+        if span == 0 {
+            return 0.0;
+        }
+        self.time_ms().saturating_sub(self.start_ms()) as f64 / span as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::recording::Frame;
+
+    fn frame(time_ms: u64) -> Frame {
+        Frame {
+            time_ms,
+            keyframe: true,
+            cpu_usage_percent: 0.0,
+            memory_total: 0,
+            memory_used: time_ms,
+            swap_total: 0,
+            swap_used: 0,
+            cpu_per_core: Vec::new(),
+            load_average: [0.0; 3],
+            changed: Vec::new(),
+            exited: Vec::new(),
+        }
+    }
+
+    fn replay(times: &[u64]) -> Replay {
+        let frames = times.iter().copied().map(frame).collect();
+        Replay::new(PathBuf::from("test.jsonl"), Recording { frames })
+    }
+
+    #[test]
+    fn playback_follows_recorded_time_and_speed() {
+        let mut replay = replay(&[0, 1_000, 2_000, 10_000]);
+        assert!(replay.is_playing());
+        assert!(!replay.advance(Duration::from_millis(600)));
+        assert!(replay.advance(Duration::from_millis(600)));
+        assert_eq!(replay.position(), 1);
+
+        replay.change_speed(2);
+        assert_eq!(replay.speed(), 4.0);
+        // 1.6 s of recorded time: past frame 2, not yet at 10 s
+        assert!(replay.advance(Duration::from_millis(400)));
+        assert_eq!(replay.position(), 2);
+        assert_eq!(replay.snapshot().memory_used, 2_000);
+
+        assert!(replay.advance(Duration::from_secs(60)));
+        assert_eq!(replay.position(), 3);
+        assert!(!replay.is_playing(), "pauses on the last frame");
+        replay.toggle_play();
+        assert_eq!(replay.position(), 0, "restarts from the beginning");
+        assert!(replay.is_playing());
+
+        replay.change_speed(-100);
+        assert_eq!(replay.speed(), 0.25);
+    }
+
+    #[test]
+    fn stepping_and_seeking_pause_and_clamp() {
+        let mut replay = replay(&[0, 1_000, 2_000, 3_000, 4_000]);
+        assert!(replay.step(1));
+        assert!(!replay.is_playing());
+        assert!(replay.step(-5));
+        assert_eq!(replay.position(), 0);
+        assert!(!replay.step(-1));
+        assert!(replay.seek(99));
+        assert_eq!(replay.position(), 4);
+
+        assert!(replay.seek_fraction(0.5));
+        assert_eq!(replay.position(), 2);
+        assert_eq!(replay.fraction(), 0.5);
+        replay.seek_fraction(0.6);
+        assert_eq!(replay.time_ms(), 3_000, "lands on the next recorded frame");
+    }
+}
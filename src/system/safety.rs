@@ -0,0 +1,234 @@
+use sysinfo::{Pid, System, Uid, Users};
+
+use crate::system::audit::AuditLog;
+
+/// One entry of `[safety] protected`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Rule {
+    /// `pid:1`
+    Pid(u32),
+    /// `user:postgres` or `user:999`, resolved to a uid when loaded.
+    User { spec: String, uid: Option<Uid> },
+    /// A process name, where `*` matches any run of characters.
+    Name(String),
+}
+
+impl Rule {
+    fn parse(entry: &str, users: &mut Option<Users>) -> Self {
+        let entry = entry.trim();
+        if let Some(pid) = entry.strip_prefix("pid:").and_then(|p| p.parse().ok()) {
+            return Rule::Pid(pid);
+        }
+        if let Some(user) = entry.strip_prefix("user:") {
+            let users = users.get_or_insert_with(Users::new_with_refreshed_list);
+            let uid = users
+                .iter()
+                .find(|u| u.name() == user)
+                .map(|u| u.id().clone())
+                .or_else(|| user.parse().ok());
+            return Rule::User {
+                spec: entry.to_string(),
+                uid,
+            };
+        }
+        Rule::Name(entry.to_string())
+    }
+
+    fn matches(&self, pid: u32, name: &str, uid: Option<&Uid>) -> bool {
+        match self {
+            Rule::Pid(p) => *p == pid,
+            Rule::User { uid: rule_uid, .. } => rule_uid.is_some() && rule_uid.as_ref() == uid,
+            Rule::Name(pattern) => !name.is_empty() && glob_match(pattern, name),
+        }
+    }
+
+    fn spec(&self) -> String {
+        match self {
+            Rule::Pid(pid) => format!("pid:{pid}"),
+            Rule::User { spec, .. } => spec.clone(),
+            Rule::Name(pattern) => pattern.clone(),
+        }
+    }
+}
+
+/// What the UI is allowed to do to other processes: nothing at all in
+/// read-only mode, and never anything to a protected process. Every attempt
+/// is recorded in the audit log, when there is one.
+#[derive(Debug, Default)]
+pub struct SafetyPolicy {
+    pub read_only: bool,
+    rules: Vec<Rule>,
+    audit: Option<AuditLog>,
+}
+
+impl SafetyPolicy {
+    /// Builds the policy from the `[safety] protected` entries: `pid:N`,
+    /// `user:NAME` (or `user:UID`), or a process name where `*` is a wildcard.
+    pub fn new(read_only: bool, protected: &[String]) -> Self {
+        let mut users = None;
+        Self {
+            read_only,
+            rules: protected
+                .iter()
+                .filter(|entry| !entry.trim().is_empty())
+                .map(|entry| Rule::parse(entry, &mut users))
+                .collect(),
+            audit: None,
+        }
+    }
+
+    pub fn with_audit(mut self, log: AuditLog) -> Self {
+        self.audit = Some(log);
+        self
+    }
+
+    pub fn audit(&self) -> Option<&AuditLog> {
+        self.audit.as_ref()
+    }
+
+    /// `user:` entries naming no known user or uid, which protect nothing.
+    pub fn unresolved_users(&self) -> Vec<&str> {
+        self.rules
+            .iter()
+            .filter_map(|rule| match rule {
+                Rule::User { spec, uid: None } => Some(spec.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Why `pid` may not be changed, if it may not. `name` and `uid` are the
+    /// process's as last seen; an empty name is matched by PID rules only.
+    pub fn refusal(&self, pid: u32, name: &str, uid: Option<&Uid>) -> Option<String> {
+        if self.read_only {
+            return Some("read-only mode".to_string());
+        }
+        let rule = self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(pid, name, uid))?;
+        let who = if name.is_empty() {
+            format!("PID {pid}")
+        } else {
+            name.to_string()
+        };
+        Some(format!("{who} is protected by \"{}\"", rule.spec()))
+    }
+
+    /// [`SafetyPolicy::refusal`] for the process `sys` currently holds at
+    /// `pid`, or for the bare PID when `sys` does not know it.
+    pub fn refusal_in(&self, sys: &System, pid: u32) -> Option<String> {
+        match sys.process(Pid::from_u32(pid)) {
+            Some(process) => {
+                self.refusal(pid, &process.name().to_string_lossy(), process.user_id())
+            }
+            None => self.refusal(pid, "", None),
+        }
+    }
+}
+
+/// Matches `name` against `pattern`, where `*` matches any run of
+/// characters, including none.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*`: exact match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::collector::Collector;
+
+    fn policy(protected: &[&str]) -> SafetyPolicy {
+        let protected: Vec<String> = protected.iter().map(|s| s.to_string()).collect();
+        SafetyPolicy::new(false, &protected)
+    }
+
+    #[test]
+    fn glob_matches_stars_anywhere() {
+        assert!(glob_match("sshd", "sshd"));
+        assert!(!glob_match("sshd", "sshd-session"));
+        assert!(glob_match("postgres*", "postgres"));
+        assert!(glob_match("postgres*", "postgres: walwriter"));
+        assert!(glob_match("*sql*", "mysqld"));
+        assert!(glob_match("sys*d", "systemd"));
+        assert!(!glob_match("sys*d", "systemd-journal"));
+        assert!(!glob_match("ab*ba", "aba"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rules_match_pid_name_and_numeric_user() {
+        let policy = policy(&["pid:1", "sshd", "postgres*", "user:4242", ""]);
+        let uid: Uid = "4242".parse().unwrap();
+        let other: Uid = "1000".parse().unwrap();
+
+        assert_eq!(
+            policy.refusal(1, "systemd", Some(&other)).as_deref(),
+            Some("systemd is protected by \"pid:1\"")
+        );
+        assert!(policy.refusal(77, "sshd", Some(&other)).is_some());
+        assert!(policy.refusal(78, "postgres: checkpointer", None).is_some());
+        assert_eq!(
+            policy.refusal(79, "backup", Some(&uid)).as_deref(),
+            Some("backup is protected by \"user:4242\"")
+        );
+        assert_eq!(policy.refusal(80, "vim", Some(&other)), None);
+        assert_eq!(
+            policy.refusal(1, "", None).as_deref(),
+            Some("PID 1 is protected by \"pid:1\"")
+        );
+        assert_eq!(policy.refusal(81, "", None), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn user_rules_match_processes_looked_up_for_signalling() {
+        let pid = std::process::id();
+        let mut collector = Collector::new();
+        let sys = collector.system_for_pids(&[pid]);
+        let uid = sys
+            .process(Pid::from_u32(pid))
+            .and_then(|p| p.user_id())
+            .expect("owner of the test process");
+        let policy = policy(&[&format!("user:{}", **uid)]);
+        assert!(policy.refusal_in(sys, pid).is_some());
+    }
+
+    #[test]
+    fn user_rules_naming_nobody_are_reported() {
+        let policy = policy(&["user:no-such-user-for-treetop", "user:4242", "sshd"]);
+        assert_eq!(
+            policy.unresolved_users(),
+            vec!["user:no-such-user-for-treetop"]
+        );
+    }
+
+    #[test]
+    fn read_only_refuses_everything() {
+        let policy = SafetyPolicy::new(true, &["pid:1".to_string()]);
+        assert_eq!(
+            policy.refusal(80, "vim", None).as_deref(),
+            Some("read-only mode")
+        );
+        assert_eq!(
+            policy.refusal_in(&System::new(), u32::MAX).as_deref(),
+            Some("read-only mode")
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::AuditLogView;
+use crate::format::truncate_unicode;
+use crate::ui::help::centered_rect;
+use crate::ui::theme::Theme;
+
+/// Renders a centered, scrollable list of audit log entries, newest first.
+///
+/// Clamps `view.scroll` so the last page stays full.
+pub fn render(frame: &mut Frame, area: Rect, view: &mut AuditLogView, theme: &Theme) {
+    let width = 110u16.min(area.width.saturating_sub(4));
+    let height = (view.entries.len().max(1) as u16 + 2).min(area.height.saturating_sub(2)); // +2 for borders
+
+    let overlay = centered_rect(width, height, area);
+    frame.render_widget(Clear, overlay);
+
+    let title = format!(
+        " Audit log: {} — {} ",
+        view.path.display(),
+        view.entries.len()
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.overlay_border))
+        .title(Span::styled(
+            truncate_unicode(&title, width.saturating_sub(2) as usize),
+            Style::default()
+                .fg(theme.accent_mauve)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner = block.inner(overlay);
+
+    let visible = inner.height as usize;
+    view.scroll = view.scroll.min(view.entries.len().saturating_sub(visible));
+
+    // time (21) + user (11) + signal (10) + result (11) + pid (9) leave the
+    // rest for the process
+    let target_width = (inner.width as usize).saturating_sub(62);
+    let lines: Vec<Line> = if view.entries.is_empty() {
+        vec![Line::from(Span::styled(
+            " (no actions recorded yet)",
+            Style::default().fg(theme.text_secondary),
+        ))]
+    } else {
+        view.entries
+            .iter()
+            .skip(view.scroll)
+            .take(visible)
+            .map(|entry| {
+                let result_color = match entry.result.as_str() {
+                    "sent" | "applied" => theme.status_ok,
+                    "refused" | "failed" => theme.status_err,
+                    _ => theme.text_secondary,
+                };
+                // Signal name, or the nice/io setting for priority changes
+                let change = entry.setting.as_deref().unwrap_or(&entry.signal);
+                let target = match &entry.detail {
+                    Some(detail) => format!("{} — {detail}", entry.name),
+                    None if entry.command.is_empty() => entry.name.clone(),
+                    None => entry.command.clone(),
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:<20}", entry.timestamp),
+                        Style::default().fg(theme.text_secondary),
+                    ),
+                    Span::styled(
+                        format!(" {:<10}", truncate_unicode(&entry.user, 10)),
+                        Style::default().fg(theme.text_primary),
+                    ),
+                    Span::styled(
+                        format!(" {:<9}", truncate_unicode(change, 9)),
+                        Style::default()
+                            .fg(theme.accent_mauve)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!(" {:<10}", entry.result),
+                        Style::default().fg(result_color),
+                    ),
+                    Span::styled(
+                        format!(" {:>7} ", entry.pid),
+                        Style::default().fg(theme.text_primary),
+                    ),
+                    Span::styled(
+                        truncate_unicode(&target, target_width),
+                        Style::default().fg(theme.text_primary),
+                    ),
+                ])
+            })
+            .collect()
+    };
+
+    frame.render_widget(block, overlay);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().bg(theme.surface_bg)),
+        inner,
+    );
+}
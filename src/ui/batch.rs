@@ -0,0 +1,186 @@
+use ratatui::Frame;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+use crate::app::App;
+use crate::format::{format_bytes, truncate_unicode};
+use crate::ui::{colored_rects, header, treemap_widget};
+
+/// Draws one batch-mode page: the header, the treemap with nothing
+/// selected, and the `top` processes using the most memory.
+pub fn draw(frame: &mut Frame, app: &mut App, top: usize) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(1),
+            Constraint::Length(u16::try_from(top).map_or(u16::MAX, |n| n.saturating_add(1))),
+        ])
+        .split(frame.area());
+
+    let treemap_area = chunks[1];
+    app.treemap_area = Some(treemap_area);
+    app.compute_layout(treemap_area.width, treemap_area.height);
+    let marks = app.tile_marks();
+    let rects = app.display_rects();
+    let colored = colored_rects(app, &rects, &marks);
+    treemap_widget::render(
+        frame,
+        treemap_area,
+        &colored,
+        usize::MAX,
+        app.min_rect_width,
+        app.min_rect_height,
+        app.border_style,
+        &app.theme,
+        app.value_format(),
+        &marks,
+    );
+
+    let breadcrumbs = app.zoom_breadcrumbs();
+    header::render(
+        frame,
+        chunks[0],
+        &app.snapshot,
+        app.header_badge(),
+        app.color_mode,
+        &app.size_label(),
+        &app.theme,
+        app.border_style,
+        &breadcrumbs,
+        &app.cpu_history,
+    );
+    render_top(frame, chunks[2], app, top);
+}
+
+/// A table of the `top` processes by the active memory metric.
+fn render_top(frame: &mut Frame, area: Rect, app: &App, top: usize) {
+    let metric = app.memory_metric;
+    let mut processes: Vec<_> = app.snapshot.process_tree.processes.values().collect();
+    processes.sort_by_key(|p| (std::cmp::Reverse(p.memory_for(metric)), p.pid));
+
+    let name_width = (area.width as usize).saturating_sub(28);
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{:>7} {:>10} {:>6}  {}",
+            "PID",
+            metric.label(),
+            "CPU%",
+            "NAME"
+        ),
+        Style::default()
+            .fg(app.theme.text_secondary)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    lines.extend(processes.into_iter().take(top).map(|p| {
+        Line::from(Span::styled(
+            format!(
+                "{:>7} {:>10} {:>6.1}  {}",
+                p.pid,
+                format_bytes(p.memory_for(metric)),
+                p.cpu_percent,
+                truncate_unicode(&p.name, name_width)
+            ),
+            Style::default().fg(app.theme.text_primary),
+        ))
+    }));
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// The buffer as lines of text. Plain text drops styling and trailing
+/// blanks; with `ansi`, colors and bold are kept as SGR escape sequences.
+pub fn buffer_to_text(buf: &Buffer, ansi: bool) -> String {
+    let area = buf.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut current: Option<(Color, Color, bool)> = None;
+        for x in area.left()..area.right() {
+            let cell = &buf[(x, y)];
+            if ansi {
+                let style = (cell.fg, cell.bg, cell.modifier.contains(Modifier::BOLD));
+                if current != Some(style) {
+                    line.push_str(&sgr(style.0, style.1, style.2));
+                    current = Some(style);
+                }
+            }
+            line.push_str(cell.symbol());
+        }
+        if ansi {
+            line.push_str("\x1b[0m");
+        } else {
+            line.truncate(line.trim_end().len());
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Escape sequence switching to `fg` on `bg`, bold or not.
+fn sgr(fg: Color, bg: Color, bold: bool) -> String {
+    let mut codes = vec!["0".to_string()];
+    if bold {
+        codes.push("1".to_string());
+    }
+    codes.extend(color_code(fg, 30));
+    codes.extend(color_code(bg, 40));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// SGR parameters for `color`; `base` is 30 for foreground, 40 for
+/// background. `None` keeps the terminal default.
+fn color_code(color: Color, base: u8) -> Option<String> {
+    let named = |offset: u8| Some((base + offset).to_string());
+    match color {
+        Color::Reset => None,
+        Color::Black => named(0),
+        Color::Red => named(1),
+        Color::Green => named(2),
+        Color::Yellow => named(3),
+        Color::Blue => named(4),
+        Color::Magenta => named(5),
+        Color::Cyan => named(6),
+        Color::Gray => named(7),
+        Color::DarkGray => named(60),
+        Color::LightRed => named(61),
+        Color::LightGreen => named(62),
+        Color::LightYellow => named(63),
+        Color::LightBlue => named(64),
+        Color::LightMagenta => named(65),
+        Color::LightCyan => named(66),
+        Color::White => named(67),
+        Color::Indexed(i) => Some(format!("{};5;{i}", base + 8)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{r};{g};{b}", base + 8)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffer_text_is_plain_or_keeps_colors_as_escapes() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buf.set_string(0, 0, "ab", Style::default().fg(Color::Rgb(1, 2, 3)));
+        buf.set_string(
+            2,
+            0,
+            "c",
+            Style::default()
+                .bg(Color::Indexed(4))
+                .add_modifier(Modifier::BOLD),
+        );
+        buf.set_string(0, 1, "d", Style::default().fg(Color::LightRed));
+
+        assert_eq!(buffer_to_text(&buf, false), "abc\nd\n");
+        assert_eq!(
+            buffer_to_text(&buf, true),
+            "\x1b[0;38;2;1;2;3mab\x1b[0;1;48;5;4mc\x1b[0m   \x1b[0m\n\
+             \x1b[0;91md\x1b[0m     \x1b[0m\n"
+        );
+    }
+}
@@ -1,68 +1,82 @@
+use std::time::Duration;
+
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Sparkline};
 
-use crate::format::{format_bytes, truncate_unicode};
+use crate::app::DetailExtras;
+use crate::format::{
+    format_bytes, format_duration, format_rate, format_timestamp_utc, truncate_unicode,
+};
+use crate::system::cgroup::CgroupNode;
 use crate::system::history::ProcessHistory;
-use crate::system::process::ProcessInfo;
+use crate::system::net::ProcessSockets;
+use crate::system::process::{MemoryMetric, ProcessInfo};
 use crate::ui::theme::{BorderStyle, Theme};
 
+/// Listening sockets listed before the rest are folded into "+N more".
+const MAX_LISTEN_LINES: usize = 4;
+
+#[allow(clippy::too_many_arguments)]
 pub fn render(
     frame: &mut Frame,
     area: Rect,
     process: &ProcessInfo,
+    memory_metric: MemoryMetric,
     theme: &Theme,
     border_style: BorderStyle,
     history: Option<&ProcessHistory>,
+    extras: Option<&DetailExtras>,
+    now: u64,
 ) {
-    let borders = if border_style.has_border() {
-        Borders::ALL
-    } else {
-        Borders::NONE
-    };
-    let block = Block::default()
-        .borders(borders)
-        .border_type(border_style.border_type())
-        .border_style(Style::default().fg(theme.overlay_border))
-        .title(Span::styled(
-            " Process Detail ",
-            Style::default()
-                .fg(theme.accent_mauve)
-                .add_modifier(Modifier::BOLD),
-        ));
-
+    let block = panel_block(" Process Detail ", theme, border_style);
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let has_history = history.is_some_and(|h| h.memory.len() > 1);
-    let chunks = if has_history && inner.height > 14 {
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Length(13), Constraint::Min(4)])
-            .split(inner)
+    let mem_str = if process.falls_back_to_rss(memory_metric) {
+        format!(
+            "{} RSS ({} unreadable)",
+            format_bytes(process.memory_for(memory_metric)),
+            memory_metric.label()
+        )
     } else {
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Min(1), Constraint::Length(0)])
-            .split(inner)
+        format!(
+            "{} {}",
+            format_bytes(process.memory_for(memory_metric)),
+            memory_metric.label()
+        )
     };
-
-    let mem_str = format_bytes(process.memory_bytes);
+    let detail = &process.memory_detail;
+    let pss_uss = format!(
+        "{} / {}",
+        optional_bytes(detail.pss),
+        optional_bytes(detail.uss)
+    );
+    let swap_virt = format!(
+        "{} / {}",
+        optional_bytes(detail.swap),
+        optional_bytes(detail.virtual_bytes)
+    );
     let cmd_display = if process.command.is_empty() {
         "(none)".to_string()
     } else {
         truncate_unicode(&process.command, 60)
     };
 
-    let lines = vec![
+    let mut lines = vec![
         detail_line("PID", process.pid.to_string(), theme),
         detail_line("PPID", process.ppid.to_string(), theme),
         detail_line("Name", process.name.clone(), theme),
         detail_line("Cmd", cmd_display, theme),
         detail_line("Memory", mem_str, theme),
+        detail_line("PSS/USS", pss_uss, theme),
+        detail_line("Swap/VSZ", swap_virt, theme),
         detail_line("CPU", format!("{:.1}%", process.cpu_percent), theme),
+    ];
+    lines.extend(time_lines(process, now, theme));
+    lines.extend([
         detail_line(
             "User",
             process.user_id.as_deref().unwrap_or("N/A").to_string(),
@@ -78,6 +92,15 @@ pub fn render(
             process.group_name.as_deref().unwrap_or("N/A").to_string(),
             theme,
         ),
+        detail_line(
+            "Cgroup",
+            process
+                .container
+                .as_ref()
+                .and_then(|c| c.display_name())
+                .unwrap_or_else(|| "N/A".to_string()),
+            theme,
+        ),
         detail_line(
             "Priority",
             process
@@ -86,6 +109,7 @@ pub fn render(
                 .unwrap_or_else(|| "N/A".to_string()),
             theme,
         ),
+        sched_line(extras, theme),
         detail_line(
             "I/O",
             process
@@ -100,59 +124,309 @@ pub fn render(
                 .unwrap_or_else(|| "N/A".to_string()),
             theme,
         ),
+        detail_line(
+            "I/O rate",
+            process
+                .io_rate
+                .map(|rate| {
+                    format!(
+                        "R {} / W {}",
+                        format_rate(rate.read_per_sec),
+                        format_rate(rate.write_per_sec)
+                    )
+                })
+                .unwrap_or_else(|| "N/A".to_string()),
+            theme,
+        ),
+        fd_line(process, extras, history, theme),
         detail_line("Status", process.status.to_string(), theme),
         detail_line("Children", process.children.len().to_string(), theme),
-    ];
+    ]);
+    if let Some(sockets) = extras.and_then(|e| e.sockets.as_ref()) {
+        lines.extend(network_lines(sockets, theme));
+    }
+
+    let has_history = history.is_some_and(|h| h.memory.len() > 1);
+    let text_height = lines.len() as u16;
+    let chunks = if has_history && inner.height > text_height + 1 {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(text_height), Constraint::Min(6)])
+            .split(inner)
+    } else {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(0)])
+            .split(inner)
+    };
 
     let paragraph = Paragraph::new(lines);
     frame.render_widget(paragraph, chunks[0]);
 
     if let Some(hist) = history
         && hist.memory.len() > 1
-        && chunks[1].height >= 4
+        && chunks[1].height >= 6
     {
         let spark_chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+            .constraints([
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+            ])
             .split(chunks[1]);
 
         let mem_data: Vec<u64> = hist.memory.iter().copied().collect();
-        let mem_spark = Sparkline::default()
-            .block(
-                Block::default()
-                    .borders(Borders::TOP)
-                    .border_style(Style::default().fg(theme.overlay_border))
-                    .title(Span::styled(
-                        " Memory ",
-                        Style::default()
-                            .fg(theme.accent_mauve)
-                            .add_modifier(Modifier::BOLD),
-                    )),
-            )
-            .data(&mem_data)
+        let mem_spark = history_sparkline(" Memory ", &mem_data, theme)
             .style(Style::default().fg(theme.gauge_filled));
         frame.render_widget(mem_spark, spark_chunks[0]);
 
         let cpu_data: Vec<u64> = hist.cpu.iter().map(|&c| (c * 100.0) as u64).collect();
-        let cpu_spark = Sparkline::default()
-            .block(
-                Block::default()
-                    .borders(Borders::TOP)
-                    .border_style(Style::default().fg(theme.overlay_border))
-                    .title(Span::styled(
-                        " CPU ",
-                        Style::default()
-                            .fg(theme.accent_mauve)
-                            .add_modifier(Modifier::BOLD),
-                    )),
-            )
-            .data(&cpu_data)
+        let cpu_spark = history_sparkline(" CPU ", &cpu_data, theme)
             .max(10000)
             .style(Style::default().fg(theme.sparkline_color));
         frame.render_widget(cpu_spark, spark_chunks[1]);
+
+        let io_data: Vec<u64> = hist.io.iter().copied().collect();
+        let io_spark = history_sparkline(" I/O ", &io_data, theme)
+            .style(Style::default().fg(theme.sparkline_color));
+        frame.render_widget(io_spark, spark_chunks[2]);
     }
 }
 
+/// Start time, elapsed time as of `now` (seconds since the Unix epoch) and
+/// cumulative CPU time of the process and of its reaped children.
+fn time_lines(process: &ProcessInfo, now: u64, theme: &Theme) -> [Line<'static>; 5] {
+    let or_na = |value: Option<String>| value.unwrap_or_else(|| "N/A".to_string());
+    let split = |user: Duration, system: Duration| {
+        format!("{} / {}", format_duration(user), format_duration(system))
+    };
+    let times = process.cpu_time;
+    [
+        detail_line(
+            "Started",
+            or_na(process.start_time.map(format_timestamp_utc)),
+            theme,
+        ),
+        detail_line(
+            "Elapsed",
+            or_na(
+                process
+                    .start_time
+                    .map(|start| format_duration(Duration::from_secs(now.saturating_sub(start)))),
+            ),
+            theme,
+        ),
+        detail_line(
+            "CPU time",
+            or_na(times.map(|t| format_duration(t.total()))),
+            theme,
+        ),
+        detail_line(
+            "Usr/Sys",
+            or_na(times.map(|t| split(t.user, t.system))),
+            theme,
+        ),
+        detail_line(
+            "ChildCPU",
+            or_na(times.map(|t| split(t.children_user, t.children_system))),
+            theme,
+        ),
+    ]
+}
+
+/// Scheduling policy, nice value and I/O priority, e.g.
+/// "other, nice 0, io best-effort/4".
+fn sched_line(extras: Option<&DetailExtras>, theme: &Theme) -> Line<'static> {
+    let value = match extras {
+        None => "N/A".to_string(),
+        Some(extras) => {
+            let parts: Vec<String> = [
+                extras.sched_policy.map(|p| p.label()),
+                extras.nice.map(|n| format!("nice {n}")),
+                extras.io_priority.map(|io| format!("io {io}")),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            if parts.is_empty() {
+                "N/A".to_string()
+            } else {
+                parts.join(", ")
+            }
+        }
+    };
+    detail_line("Sched", value, theme)
+}
+
+/// Open descriptors against the soft `RLIMIT_NOFILE`, flagged when the
+/// count has been climbing steadily.
+fn fd_line(
+    process: &ProcessInfo,
+    extras: Option<&DetailExtras>,
+    history: Option<&ProcessHistory>,
+    theme: &Theme,
+) -> Line<'static> {
+    let value = match (process.fd_count, extras) {
+        (None, _) => "N/A".to_string(),
+        (Some(count), None) => count.to_string(),
+        (Some(count), Some(DetailExtras { fd_limit: None, .. })) => format!("{count} (no limit)"),
+        (
+            Some(count),
+            Some(DetailExtras {
+                fd_limit: Some(limit),
+                ..
+            }),
+        ) => format!(
+            "{count} / {limit} ({:.0}%)",
+            count as f64 / (*limit).max(1) as f64 * 100.0
+        ),
+    };
+    let mut line = detail_line("FDs", value, theme);
+    if history.is_some_and(|h| h.fd_leak_suspected()) {
+        line.spans.push(Span::styled(
+            " \u{25b2} leak?",
+            Style::default()
+                .fg(theme.status_err)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    line
+}
+
+/// "Network" section: listening sockets, then connection counts.
+fn network_lines(sockets: &ProcessSockets, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![section_line("Network", theme)];
+    let shown = sockets.listening.iter().take(MAX_LISTEN_LINES);
+    for (i, socket) in shown.enumerate() {
+        let label = if i == 0 { "Listen" } else { "" };
+        lines.push(detail_line(label, socket.to_string(), theme));
+    }
+    if sockets.listening.is_empty() {
+        lines.push(detail_line("Listen", "none".to_string(), theme));
+    } else if sockets.listening.len() > MAX_LISTEN_LINES {
+        let more = sockets.listening.len() - MAX_LISTEN_LINES;
+        lines.push(detail_line("", format!("+{more} more"), theme));
+    }
+    lines.push(detail_line(
+        "Conns",
+        format!("{} established, {} unix", sockets.established, sockets.unix),
+        theme,
+    ));
+    lines
+}
+
+fn section_line(title: &str, theme: &Theme) -> Line<'static> {
+    Line::from(Span::styled(
+        format!(" ── {title} ──"),
+        Style::default().fg(theme.overlay_border),
+    ))
+}
+
+fn history_sparkline<'a>(title: &'a str, data: &'a [u64], theme: &Theme) -> Sparkline<'a> {
+    Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_style(Style::default().fg(theme.overlay_border))
+                .title(Span::styled(
+                    title,
+                    Style::default()
+                        .fg(theme.accent_mauve)
+                        .add_modifier(Modifier::BOLD),
+                )),
+        )
+        .data(data)
+}
+
+/// Detail view for a cgroup in the cgroup hierarchy view.
+pub fn render_cgroup(
+    frame: &mut Frame,
+    area: Rect,
+    node: &CgroupNode,
+    theme: &Theme,
+    border_style: BorderStyle,
+) {
+    let block = panel_block(" Cgroup Detail ", theme, border_style);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let limit = |limit: Option<u64>| limit.map(format_bytes).unwrap_or_else(|| "max".to_string());
+    let current = match node.memory_max {
+        Some(max) if max > 0 => format!(
+            "{} ({:.0}% of max)",
+            format_bytes(node.memory_current),
+            node.memory_current as f64 / max as f64 * 100.0
+        ),
+        _ => format_bytes(node.memory_current),
+    };
+    let events = &node.memory_events;
+    let cpu = &node.cpu_stat;
+    let secs = |usec: u64| usec as f64 / 1_000_000.0;
+
+    let lines = vec![
+        detail_line("Path", truncate_unicode(&node.path, 60), theme),
+        detail_line("Current", current, theme),
+        detail_line("Max", limit(node.memory_max), theme),
+        detail_line("High", limit(node.memory_high), theme),
+        detail_line(
+            "OOM",
+            format!("{} ({} killed)", events.oom, events.oom_kill),
+            theme,
+        ),
+        detail_line(
+            "Events",
+            format!("high {} / max {}", events.high, events.max),
+            theme,
+        ),
+        detail_line(
+            "CPU",
+            format!(
+                "{:.1}s (usr {:.1}s sys {:.1}s)",
+                secs(cpu.usage_usec),
+                secs(cpu.user_usec),
+                secs(cpu.system_usec)
+            ),
+            theme,
+        ),
+        detail_line(
+            "Throttle",
+            format!("{}x, {:.1}s", cpu.nr_throttled, secs(cpu.throttled_usec)),
+            theme,
+        ),
+        detail_line("Procs", node.process_count.to_string(), theme),
+        detail_line(
+            "Children",
+            if node.has_children { "yes" } else { "no" }.to_string(),
+            theme,
+        ),
+    ];
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn panel_block(title: &'static str, theme: &Theme, border_style: BorderStyle) -> Block<'static> {
+    let borders = if border_style.has_border() {
+        Borders::ALL
+    } else {
+        Borders::NONE
+    };
+    Block::default()
+        .borders(borders)
+        .border_type(border_style.border_type())
+        .border_style(Style::default().fg(theme.overlay_border))
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(theme.accent_mauve)
+                .add_modifier(Modifier::BOLD),
+        ))
+}
+
+fn optional_bytes(bytes: Option<u64>) -> String {
+    bytes.map(format_bytes).unwrap_or_else(|| "N/A".to_string())
+}
+
 fn detail_line(label: &str, value: String, theme: &Theme) -> Line<'static> {
     Line::from(vec![
         Span::styled(
@@ -0,0 +1,101 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::{FrozenListView, FrozenProcess};
+use crate::format::truncate_unicode;
+use crate::ui::help::centered_rect;
+use crate::ui::theme::Theme;
+
+/// Renders the processes frozen from this session, with a reminder to thaw
+/// them when the list was opened by quitting.
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    frozen: &[FrozenProcess],
+    view: &FrozenListView,
+    theme: &Theme,
+) {
+    let width = 56u16.min(area.width.saturating_sub(4));
+    // Entries, a blank line and the hint, plus borders
+    let height = (frozen.len().max(1) as u16 + 4).min(area.height.saturating_sub(2));
+
+    let overlay = centered_rect(width, height, area);
+    frame.render_widget(Clear, overlay);
+
+    let (title, border) = if view.quitting {
+        (
+            format!(" Quit with {} processes still frozen? ", frozen.len()),
+            theme.status_err,
+        )
+    } else {
+        (
+            format!(" Frozen by treetop — {} ", frozen.len()),
+            theme.overlay_border,
+        )
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border))
+        .title(Span::styled(
+            truncate_unicode(&title, width.saturating_sub(2) as usize),
+            Style::default()
+                .fg(theme.accent_mauve)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner = block.inner(overlay);
+
+    // Room for the hint below the entries
+    let visible = (inner.height as usize).saturating_sub(2).max(1);
+    let skip = (view.cursor + 1).saturating_sub(visible);
+    let name_width = (inner.width as usize).saturating_sub(12);
+    let mut lines: Vec<Line> = if frozen.is_empty() {
+        vec![Line::from(Span::styled(
+            " (nothing frozen)",
+            Style::default().fg(theme.text_secondary),
+        ))]
+    } else {
+        frozen
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(visible)
+            .map(|(i, process)| {
+                let style = if i == view.cursor {
+                    Style::default()
+                        .fg(theme.pill_key_fg)
+                        .bg(theme.pill_key_bg)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text_primary)
+                };
+                Line::from(Span::styled(
+                    format!(
+                        " {:>8}  {:<name_width$}",
+                        process.identity.pid,
+                        truncate_unicode(&process.name, name_width)
+                    ),
+                    style,
+                ))
+            })
+            .collect()
+    };
+    lines.push(Line::default());
+    let hint = if view.quitting {
+        " a thaws all and quits, q quits leaving them frozen"
+    } else {
+        " Enter thaws the selected process, a thaws all"
+    };
+    lines.push(Line::from(Span::styled(
+        hint,
+        Style::default().fg(theme.text_secondary),
+    )));
+
+    frame.render_widget(block, overlay);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().bg(theme.surface_bg)),
+        inner,
+    );
+}
@@ -40,7 +40,9 @@ pub fn render(
     frame: &mut Frame,
     area: Rect,
     snapshot: &SystemSnapshot,
+    badge: &str,
     color_mode: ColorMode,
+    size_label: &str,
     theme: &Theme,
     border_style: BorderStyle,
     breadcrumbs: &[(u32, String)],
@@ -60,7 +62,9 @@ pub fn render(
         frame,
         chunks[0],
         snapshot,
+        badge,
         color_mode,
+        size_label,
         theme,
         border_style,
         breadcrumbs,
@@ -70,11 +74,14 @@ pub fn render(
     render_cpu_sparkline(frame, chunks[3], snapshot, theme, border_style, cpu_history);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_branding(
     frame: &mut Frame,
     area: Rect,
     snapshot: &SystemSnapshot,
+    badge: &str,
     color_mode: ColorMode,
+    size_label: &str,
     theme: &Theme,
     border_style: BorderStyle,
     breadcrumbs: &[(u32, String)],
@@ -85,7 +92,7 @@ fn render_branding(
     frame.render_widget(block, area);
 
     let mut spans = vec![Span::styled(
-        " live ",
+        format!(" {badge} "),
         Style::default()
             .fg(theme.header_accent_fg)
             .bg(theme.header_accent_bg)
@@ -112,6 +119,11 @@ fn render_branding(
             Style::default().fg(theme.text_secondary),
         ),
         Span::raw("  "),
+        Span::styled(
+            size_label.to_string(),
+            Style::default().fg(theme.text_secondary),
+        ),
+        Span::raw("  "),
         Span::styled(
             format!("Procs: {}", snapshot.process_tree.processes.len()),
             Style::default().fg(theme.text_secondary),
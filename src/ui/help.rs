@@ -51,7 +51,7 @@ pub fn render(frame: &mut Frame, area: Rect, entries: &[(String, &str)], theme:
     );
 }
 
-fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+pub(super) fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let [vert] = Layout::vertical([Constraint::Length(height)])
         .flex(Flex::Center)
         .areas(area);
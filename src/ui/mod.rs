@@ -1,8 +1,17 @@
+pub mod audit_log;
+pub mod batch;
 pub mod detail_panel;
+pub mod frozen_list;
 pub mod header;
 pub mod help;
+pub mod nice_entry;
+pub mod open_files;
+pub mod replay_bar;
 pub mod selection_bar;
+pub mod signal_confirm;
+pub mod signal_picker;
 pub mod statusbar;
+pub mod svg;
 pub mod theme;
 pub mod treemap_widget;
 
@@ -10,9 +19,12 @@ use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
 
 use crate::app::App;
-use crate::ui::theme::colorize_rects;
+use crate::treemap::node::TreemapRect;
+use crate::ui::theme::{ColoredTreemapRect, colorize_changes, colorize_rects};
+use crate::ui::treemap_widget::TileMarks;
 
 pub fn draw(frame: &mut Frame, app: &mut App) {
+    let replay_rows = if app.replay.is_some() { 1 } else { 0 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -20,10 +32,12 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
             Constraint::Min(1),
             Constraint::Length(1),
             Constraint::Length(1),
+            Constraint::Length(replay_rows),
         ])
         .split(frame.area());
 
     let content_area = chunks[1];
+    let marks = app.tile_marks();
 
     if app.show_detail_panel {
         let h_chunks = Layout::default()
@@ -38,14 +52,7 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         app.compute_layout(treemap_area.width, treemap_area.height);
 
         let rects = app.display_rects();
-        let colored = colorize_rects(
-            &rects,
-            &app.snapshot.process_tree,
-            app.snapshot.memory_total,
-            app.color_mode,
-            &app.theme,
-            app.color_support,
-        );
+        let colored = colored_rects(app, &rects, &marks);
         treemap_widget::render(
             frame,
             treemap_area,
@@ -55,31 +62,31 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
             app.min_rect_height,
             app.border_style,
             &app.theme,
+            app.value_format(),
+            &marks,
         );
 
-        if let Some(process) = app.selected_process() {
+        if let Some(node) = app.selected_cgroup() {
+            detail_panel::render_cgroup(frame, detail_area, node, &app.theme, app.border_style);
+        } else if let Some(process) = app.detail_process() {
             let history = app.history.get(process.pid);
             detail_panel::render(
                 frame,
                 detail_area,
                 process,
+                app.memory_metric,
                 &app.theme,
                 app.border_style,
                 history,
+                app.detail_extras(),
+                app.clock_secs(),
             );
         }
     } else {
         app.treemap_area = Some(content_area);
         app.compute_layout(content_area.width, content_area.height);
         let rects = app.display_rects();
-        let colored = colorize_rects(
-            &rects,
-            &app.snapshot.process_tree,
-            app.snapshot.memory_total,
-            app.color_mode,
-            &app.theme,
-            app.color_support,
-        );
+        let colored = colored_rects(app, &rects, &marks);
         treemap_widget::render(
             frame,
             content_area,
@@ -89,6 +96,8 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
             app.min_rect_height,
             app.border_style,
             &app.theme,
+            app.value_format(),
+            &marks,
         );
     }
 
@@ -97,7 +106,9 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         frame,
         chunks[0],
         &app.snapshot,
+        app.header_badge(),
         app.color_mode,
+        &app.size_label(),
         &app.theme,
         app.border_style,
         &breadcrumbs,
@@ -113,19 +124,98 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         app.is_zoomed(),
     );
 
-    let selected = app
-        .selected_process()
-        .map(|p| selection_bar::SelectionInfo {
-            pid: p.pid,
-            name: p.name.clone(),
-            memory_bytes: p.memory_bytes,
-        });
-    selection_bar::render(frame, chunks[2], selected, &app.theme);
+    let selected = selection_info(app);
+    let marked = app
+        .marked_totals()
+        .map(
+            |(count, memory_bytes, cpu_percent)| selection_bar::MarkedSummary {
+                count,
+                memory_bytes,
+                cpu_percent,
+            },
+        );
+    selection_bar::render(frame, chunks[2], selected, marked, &app.theme);
+    app.replay_bar_area = app
+        .replay
+        .as_ref()
+        .map(|replay| replay_bar::render(frame, chunks[4], replay, &app.theme));
 
     // Help overlay — rendered last to appear on top
     if app.show_help() {
         help::render(frame, frame.area(), &app.help_entries(), &app.theme);
     }
+    if let Some(view) = app.open_files.as_mut() {
+        open_files::render(frame, frame.area(), view, &app.theme);
+    }
+    if let Some(view) = app.audit_log.as_mut() {
+        audit_log::render(frame, frame.area(), view, &app.theme);
+    }
+    if let Some(picker) = &app.signal_picker {
+        signal_picker::render(frame, frame.area(), picker, &app.theme);
+    }
+    if let Some(view) = &app.frozen_list {
+        frozen_list::render(frame, frame.area(), &app.frozen, view, &app.theme);
+    }
+    if let Some(entry) = &app.nice_entry {
+        nice_entry::render(frame, frame.area(), entry, &app.theme);
+    }
+    if let Some(confirm) = &app.signal_confirm {
+        signal_confirm::render(frame, frame.area(), confirm, &app.theme);
+    }
+}
+
+/// Tile colors: which way memory changed in compare mode, the color mode
+/// otherwise.
+fn colored_rects(app: &App, rects: &[TreemapRect], marks: &TileMarks) -> Vec<ColoredTreemapRect> {
+    if app.comparing() {
+        return colorize_changes(rects, &marks.shrunk, &app.theme, app.color_support);
+    }
+    colorize_rects(
+        rects,
+        &app.snapshot.process_tree,
+        app.heat_total(),
+        app.color_mode,
+        &app.theme,
+        app.color_support,
+    )
+}
+
+/// What the selection bar describes for the selected tile, which may be a
+/// process, a container group, a memory-map region or a cgroup.
+fn selection_info(app: &App) -> Option<selection_bar::SelectionInfo> {
+    if let Some(node) = app.selected_cgroup() {
+        return Some(selection_bar::SelectionInfo {
+            pid: None,
+            name: node.path.clone(),
+            memory_bytes: node.memory_current,
+        });
+    }
+    if let Some(group) = app.selected_group() {
+        return Some(selection_bar::SelectionInfo {
+            pid: None,
+            name: format!("{} ({} procs)", group.label, group.process_count),
+            memory_bytes: group.value,
+        });
+    }
+    if let (Some(map), Some(region)) = (&app.memory_map, app.selected_region()) {
+        return Some(selection_bar::SelectionInfo {
+            pid: Some(map.pid),
+            name: format!(
+                "{} ({}, {} maps)",
+                region.label,
+                region.kind.label(),
+                region.mappings
+            ),
+            memory_bytes: region.value(app.memory_metric),
+        });
+    }
+    app.selected_process()
+        .or_else(|| app.selected_exited())
+        .map(|p| selection_bar::SelectionInfo {
+            pid: Some(p.pid),
+            name: p.name.clone(),
+            memory_bytes: p.memory_for(app.memory_metric),
+        })
 }
 
 #[cfg(test)]
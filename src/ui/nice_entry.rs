@@ -0,0 +1,65 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::NiceEntry;
+use crate::format::truncate_unicode;
+use crate::ui::help::centered_rect;
+use crate::ui::theme::Theme;
+
+/// Renders a small centered prompt for the nice value of one process.
+pub fn render(frame: &mut Frame, area: Rect, entry: &NiceEntry, theme: &Theme) {
+    let width = 44u16.min(area.width.saturating_sub(4));
+    let height = 5u16.min(area.height.saturating_sub(2));
+
+    let overlay = centered_rect(width, height, area);
+    frame.render_widget(Clear, overlay);
+
+    let title = if entry.marked > 0 {
+        format!(" Nice: {} ", entry.name)
+    } else {
+        format!(" Nice: {} ({}) ", entry.name, entry.pid)
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.overlay_border))
+        .title(Span::styled(
+            truncate_unicode(&title, width.saturating_sub(2) as usize),
+            Style::default()
+                .fg(theme.accent_mauve)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner = block.inner(overlay);
+
+    let current = entry
+        .current
+        .map_or_else(|| "N/A".to_string(), |n| n.to_string());
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(" Current  ", Style::default().fg(theme.text_secondary)),
+            Span::styled(current, Style::default().fg(theme.text_primary)),
+        ]),
+        Line::from(vec![
+            Span::styled(" New      ", Style::default().fg(theme.text_secondary)),
+            Span::styled(
+                entry.text.clone(),
+                Style::default()
+                    .fg(theme.text_primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("\u{2588}", Style::default().fg(theme.accent_mauve)),
+        ]),
+        Line::from(Span::styled(
+            " -20 runs first, 19 runs last",
+            Style::default().fg(theme.text_secondary),
+        )),
+    ];
+
+    frame.render_widget(block, overlay);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().bg(theme.surface_bg)),
+        inner,
+    );
+}
@@ -0,0 +1,80 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::OpenFilesView;
+use crate::format::truncate_unicode;
+use crate::ui::help::centered_rect;
+use crate::ui::theme::Theme;
+
+/// Renders a centered, scrollable list of a process's open descriptors.
+///
+/// Clamps `view.scroll` so the last page stays full.
+pub fn render(frame: &mut Frame, area: Rect, view: &mut OpenFilesView, theme: &Theme) {
+    let width = 72u16.min(area.width.saturating_sub(4));
+    let height = (view.files.len().max(1) as u16 + 2).min(area.height.saturating_sub(2)); // +2 for borders
+
+    let overlay = centered_rect(width, height, area);
+    frame.render_widget(Clear, overlay);
+
+    let title = format!(
+        " Open files: {} (PID {}) — {} ",
+        view.name,
+        view.pid,
+        view.files.len()
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.overlay_border))
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(theme.accent_mauve)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner = block.inner(overlay);
+
+    let visible = inner.height as usize;
+    view.scroll = view.scroll.min(view.files.len().saturating_sub(visible));
+
+    // fd (5) + kind (6) + separators leave the rest for the target
+    let target_width = (inner.width as usize).saturating_sub(14);
+    let lines: Vec<Line> = if view.files.is_empty() {
+        vec![Line::from(Span::styled(
+            " (no open files)",
+            Style::default().fg(theme.text_secondary),
+        ))]
+    } else {
+        view.files
+            .iter()
+            .skip(view.scroll)
+            .take(visible)
+            .map(|file| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:>5} ", file.fd),
+                        Style::default()
+                            .fg(theme.accent_mauve)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:<6} ", file.kind.label()),
+                        Style::default().fg(theme.text_secondary),
+                    ),
+                    Span::styled(
+                        truncate_unicode(&file.target, target_width),
+                        Style::default().fg(theme.text_primary),
+                    ),
+                ])
+            })
+            .collect()
+    };
+
+    frame.render_widget(block, overlay);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().bg(theme.surface_bg)),
+        inner,
+    );
+}
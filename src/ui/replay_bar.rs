@@ -0,0 +1,68 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use unicode_width::UnicodeWidthStr;
+
+use crate::format::format_timestamp_rfc3339;
+use crate::system::replay::Replay;
+use crate::ui::theme::Theme;
+
+/// Draws play state, speed, frame time, a seek track and the frame count.
+/// Returns the track's area, where a click seeks to that point.
+pub fn render(frame: &mut Frame, area: Rect, replay: &Replay, theme: &Theme) -> Rect {
+    let style = Style::default()
+        .bg(theme.statusbar_bg)
+        .fg(theme.text_primary);
+    let state = if replay.is_playing() {
+        "\u{25b6}"
+    } else {
+        "||"
+    };
+    let left = format!(
+        " {state} {}x  {} ",
+        replay.speed(),
+        format_timestamp_rfc3339(replay.time_ms() / 1000)
+    );
+    let right = format!(" {}/{} ", replay.position() + 1, replay.frame_count());
+    let track_width = (area.width as usize).saturating_sub(left.width() + right.width());
+    let played = ((track_width.saturating_sub(1)) as f64 * replay.fraction()).round() as usize;
+
+    let mut spans = vec![Span::styled(
+        left.clone(),
+        Style::default()
+            .fg(theme.pill_key_bg)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if track_width > 0 {
+        spans.extend([
+            Span::styled(
+                "\u{2501}".repeat(played),
+                Style::default().fg(theme.gauge_filled),
+            ),
+            Span::styled(
+                "\u{25cf}",
+                Style::default()
+                    .fg(theme.gauge_filled)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "\u{2500}".repeat(track_width - played - 1),
+                Style::default().fg(theme.text_secondary),
+            ),
+        ]);
+    }
+    spans.push(Span::styled(
+        right,
+        Style::default().fg(theme.text_secondary),
+    ));
+
+    frame.render_widget(Paragraph::new(Line::from(spans)).style(style), area);
+    Rect::new(
+        area.x + left.width() as u16,
+        area.y,
+        track_width as u16,
+        area.height.min(1),
+    )
+}
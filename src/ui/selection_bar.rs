@@ -10,19 +10,36 @@ use crate::ui::theme::Theme;
 
 #[derive(Debug, Clone)]
 pub struct SelectionInfo {
-    pub pid: u32,
+    /// `None` for tiles that aggregate several processes.
+    pub pid: Option<u32>,
     pub name: String,
     pub memory_bytes: u64,
 }
 
-pub fn render(frame: &mut Frame, area: Rect, selected: Option<SelectionInfo>, theme: &Theme) {
+/// Totals over the processes marked for batch actions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarkedSummary {
+    pub count: usize,
+    pub memory_bytes: u64,
+    pub cpu_percent: f32,
+}
+
+/// Shows the marked set when anything is marked, else the selected tile.
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    selected: Option<SelectionInfo>,
+    marked: Option<MarkedSummary>,
+    theme: &Theme,
+) {
     let style = Style::default()
         .bg(theme.statusbar_bg)
         .fg(theme.text_primary);
     let width = area.width as usize;
-    let line = match selected {
-        Some(selection) => format_selection_line(selection, width),
-        None => " ".repeat(width),
+    let line = match (marked, selected) {
+        (Some(marked), _) => format_marked_line(marked, width),
+        (None, Some(selection)) => format_selection_line(selection, width),
+        (None, None) => " ".repeat(width),
     };
 
     frame.render_widget(
@@ -31,12 +48,28 @@ pub fn render(frame: &mut Frame, area: Rect, selected: Option<SelectionInfo>, th
     );
 }
 
+fn format_marked_line(marked: MarkedSummary, width: usize) -> String {
+    let label = format!(
+        "\u{25cf} {} marked  CPU {:.1}%",
+        marked.count, marked.cpu_percent
+    );
+    let memory = format_bytes(marked.memory_bytes);
+    let gap = width.saturating_sub(label.width() + memory.width());
+    if gap == 0 {
+        return truncate_unicode(&format!("{label} {memory}"), width);
+    }
+    format!("{label}{}{memory}", " ".repeat(gap))
+}
+
 fn format_selection_line(selection: SelectionInfo, width: usize) -> String {
     if width == 0 {
         return String::new();
     }
 
-    let pid_prefix = format!("[{}] ", selection.pid);
+    let pid_prefix = selection
+        .pid
+        .map(|pid| format!("[{pid}] "))
+        .unwrap_or_default();
     let mut memory = format_bytes(selection.memory_bytes);
     if memory.width() > width {
         memory = truncate_unicode(&memory, width);
@@ -73,7 +106,7 @@ mod tests {
     fn keeps_memory_right_aligned() {
         let line = format_selection_line(
             SelectionInfo {
-                pid: 1234,
+                pid: Some(1234),
                 name: "Very Long Process Name".to_string(),
                 memory_bytes: 1_234_567_890,
             },
@@ -86,7 +119,7 @@ mod tests {
     fn pid_prefix_shown() {
         let line = format_selection_line(
             SelectionInfo {
-                pid: 42,
+                pid: Some(42),
                 name: "firefox".to_string(),
                 memory_bytes: 512_000_000,
             },
@@ -95,4 +128,32 @@ mod tests {
         assert!(line.starts_with("[42] firefox"));
         assert!(line.ends_with("488.3 MB"));
     }
+
+    #[test]
+    fn marked_line_shows_count_cpu_and_memory() {
+        let line = format_marked_line(
+            MarkedSummary {
+                count: 5,
+                memory_bytes: 512_000_000,
+                cpu_percent: 34.52,
+            },
+            40,
+        );
+        assert!(line.starts_with("\u{25cf} 5 marked  CPU 34.5%"));
+        assert!(line.ends_with("488.3 MB"));
+        assert_eq!(line.width(), 40);
+    }
+
+    #[test]
+    fn aggregate_tiles_have_no_pid_prefix() {
+        let line = format_selection_line(
+            SelectionInfo {
+                pid: None,
+                name: "docker 3f2a9c1e0b7d (4 procs)".to_string(),
+                memory_bytes: 512_000_000,
+            },
+            50,
+        );
+        assert!(line.starts_with("docker 3f2a9c1e0b7d"));
+    }
 }
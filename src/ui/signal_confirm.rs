@@ -0,0 +1,105 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::SignalConfirm;
+use crate::format::{format_bytes, truncate_unicode};
+use crate::system::kill::signal_name;
+use crate::ui::help::centered_rect;
+use crate::ui::theme::Theme;
+
+/// Renders a centered confirmation dialog describing what a held-back
+/// signal will hit.
+pub fn render(frame: &mut Frame, area: Rect, confirm: &SignalConfirm, theme: &Theme) {
+    let signal = signal_name(confirm.signal);
+    let width = 64u16.min(area.width.saturating_sub(4));
+    let height = 10u16.min(area.height.saturating_sub(2));
+
+    let overlay = centered_rect(width, height, area);
+    frame.render_widget(Clear, overlay);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.status_err))
+        .title(Span::styled(
+            format!(" Send {signal}? "),
+            Style::default()
+                .fg(theme.status_err)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner = block.inner(overlay);
+
+    // Label column (13) leaves the rest for the values
+    let value_width = (inner.width as usize).saturating_sub(13);
+    let descendants = match (confirm.descendants, confirm.tree) {
+        (0, _) => "none".to_string(),
+        (n, true) => format!("{n}, all signalled too"),
+        (n, false) => format!("{n}, left running as orphans"),
+    };
+    let memory = if confirm.tree {
+        format!("{} with descendants", format_bytes(confirm.memory))
+    } else {
+        format_bytes(confirm.memory)
+    };
+    let user = confirm.user.clone().unwrap_or_else(|| "N/A".into());
+    let rows = if !confirm.marked {
+        [
+            ("Name", confirm.name.clone()),
+            ("PID", confirm.pid.to_string()),
+            ("User", user),
+            ("Command", confirm.command.clone()),
+            ("Memory", memory),
+            ("Descendants", descendants),
+        ]
+    } else {
+        let pids: Vec<String> = confirm.targets.iter().map(|t| t.pid.to_string()).collect();
+        [
+            ("Marked", confirm.name.clone()),
+            ("PIDs", pids.join(" ")),
+            ("Users", user),
+            ("Names", confirm.command.clone()),
+            ("Memory", memory),
+            ("Descendants", "not signalled".to_string()),
+        ]
+    };
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(
+                    format!(" {label:<12}"),
+                    Style::default()
+                        .fg(theme.accent_mauve)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    truncate_unicode(&value, value_width),
+                    Style::default().fg(theme.pill_desc_fg),
+                ),
+            ])
+        })
+        .collect();
+    lines.push(Line::default());
+    lines.push(Line::from(vec![
+        Span::styled(" Press ", Style::default().fg(theme.text_secondary)),
+        Span::styled(
+            format!(" {} ", confirm.confirm_key),
+            Style::default()
+                .fg(theme.pill_key_fg)
+                .bg(theme.pill_key_bg)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" to send {signal}, Esc to cancel"),
+            Style::default().fg(theme.text_secondary),
+        ),
+    ]));
+
+    frame.render_widget(block, overlay);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().bg(theme.surface_bg)),
+        inner,
+    );
+}
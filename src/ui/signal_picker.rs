@@ -0,0 +1,80 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::SignalPicker;
+use crate::ui::help::centered_rect;
+use crate::ui::theme::Theme;
+
+/// Renders a centered list of signals with the selected one highlighted,
+/// scrolled so the selection stays in view.
+pub fn render(frame: &mut Frame, area: Rect, picker: &SignalPicker, theme: &Theme) {
+    let width = 50u16.min(area.width.saturating_sub(4));
+    let height = (picker.signals.len() as u16 + 2).min(area.height.saturating_sub(2)); // +2 for borders
+
+    let overlay = centered_rect(width, height, area);
+    frame.render_widget(Clear, overlay);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.overlay_border))
+        .title(Span::styled(
+            if picker.marked > 0 {
+                format!(" Signal {} ", picker.name)
+            } else {
+                format!(
+                    " Signal {} (PID {}){} ",
+                    picker.name,
+                    picker.pid,
+                    if picker.tree { " + descendants" } else { "" }
+                )
+            },
+            Style::default()
+                .fg(theme.accent_mauve)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner = block.inner(overlay);
+
+    let visible = (inner.height as usize).max(1);
+    let offset = picker
+        .selected
+        .saturating_sub(visible / 2)
+        .min(picker.signals.len().saturating_sub(visible));
+
+    let lines: Vec<Line> = picker
+        .signals
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, (info, number))| {
+            let number = number.map_or_else(|| "-".to_string(), |n| n.to_string());
+            let (key_style, desc_style) = if i == picker.selected {
+                let selected = Style::default()
+                    .fg(theme.pill_key_fg)
+                    .bg(theme.pill_key_bg)
+                    .add_modifier(Modifier::BOLD);
+                (selected, selected)
+            } else {
+                (
+                    Style::default()
+                        .fg(theme.accent_mauve)
+                        .add_modifier(Modifier::BOLD),
+                    Style::default().fg(theme.pill_desc_fg),
+                )
+            };
+            Line::from(vec![
+                Span::styled(format!(" {number:>2} {:<10}", info.name), key_style),
+                Span::styled(format!(" {:<33}", info.description), desc_style),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(block, overlay);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().bg(theme.surface_bg)),
+        inner,
+    );
+}
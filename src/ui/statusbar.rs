@@ -20,7 +20,19 @@ pub fn render(
 
     // Status message takes priority
     if let Some((msg, _)) = status_message {
-        let color = if msg.starts_with("Sent") || msg.starts_with("Killed") {
+        let color = if [
+            "Sent",
+            "Killed",
+            "Applied",
+            "Exported",
+            "Recording",
+            "Stopped",
+            "Comparing",
+            "Pinned",
+        ]
+        .iter()
+        .any(|ok| msg.starts_with(ok))
+        {
             theme.status_ok
         } else {
             theme.status_err
@@ -84,6 +96,42 @@ pub fn render(
             spans.extend(pill_spans("\u{2190}\u{2193}\u{2191}\u{2192}", "Nav", theme));
             Line::from(spans)
         }
+        InputMode::OpenFiles | InputMode::AuditLog => {
+            let mut spans = Vec::new();
+            spans.extend(pill_spans("\u{2191}\u{2193}", "Scroll", theme));
+            spans.extend(pill_spans("PgUp/PgDn", "Page", theme));
+            spans.extend(pill_spans("Esc", "Close", theme));
+            Line::from(spans)
+        }
+        InputMode::SignalPicker => {
+            let mut spans = Vec::new();
+            spans.extend(pill_spans("\u{2191}\u{2193}", "Select", theme));
+            spans.extend(pill_spans("0-9", "By number", theme));
+            spans.extend(pill_spans("Tab", "Process/Tree", theme));
+            spans.extend(pill_spans("Enter", "Send", theme));
+            spans.extend(pill_spans("Esc", "Cancel", theme));
+            Line::from(spans)
+        }
+        InputMode::FrozenList => {
+            let mut spans = Vec::new();
+            spans.extend(pill_spans("\u{2191}\u{2193}", "Select", theme));
+            spans.extend(pill_spans("Enter", "Thaw", theme));
+            spans.extend(pill_spans("a", "Thaw all", theme));
+            spans.extend(pill_spans("Esc", "Close", theme));
+            Line::from(spans)
+        }
+        InputMode::NiceEntry => {
+            let mut spans = Vec::new();
+            spans.extend(pill_spans("-20..19", "Nice value", theme));
+            spans.extend(pill_spans("Enter", "Apply", theme));
+            spans.extend(pill_spans("Esc", "Cancel", theme));
+            Line::from(spans)
+        }
+        InputMode::ConfirmSignal => {
+            let mut spans = Vec::new();
+            spans.extend(pill_spans("Esc", "Cancel", theme));
+            Line::from(spans)
+        }
         InputMode::Help => {
             let mut spans = Vec::new();
             spans.extend(pill_spans("?", "Close Help", theme));
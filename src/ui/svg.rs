@@ -0,0 +1,227 @@
+use std::fmt::Write as _;
+
+use ratatui::style::Color;
+
+use crate::app::App;
+use crate::format::{format_bytes, truncate_unicode};
+use crate::treemap::algorithm::squarify_sorted;
+use crate::treemap::node::{LayoutRect, TreemapItem};
+use crate::ui::colored_rects;
+use crate::ui::treemap_widget::{TileMarks, color_to_rgb, contrast_color};
+
+/// Image size when none is given, in pixels.
+pub const DEFAULT_WIDTH: u32 = 1600;
+pub const DEFAULT_HEIGHT: u32 = 1000;
+
+/// Tiles smaller than this, in pixels, get no label.
+const LABEL_MIN_WIDTH: f64 = 36.0;
+const LABEL_MIN_HEIGHT: f64 = 18.0;
+/// Height a tile needs for its value below the label.
+const VALUE_MIN_HEIGHT: f64 = 36.0;
+/// Longest command line shown in a tooltip, in columns.
+const TOOLTIP_COMMAND_WIDTH: usize = 200;
+
+/// The current treemap as a standalone SVG document of `width` by `height`
+/// pixels, each tile with a `<title>` tooltip.
+///
+/// The tiles of `layout_rects` are laid out again for the image's own
+/// proportions, so the result does not depend on the terminal's size or
+/// cell shape.
+pub fn svg(app: &App, width: u32, height: u32) -> String {
+    document(app, width, height, true)
+}
+
+/// The treemap SVG in an HTML page that shows each tile's PID, command,
+/// memory and CPU when hovered.
+pub fn html(app: &App, width: u32, height: u32) -> String {
+    let theme = &app.theme;
+    let page_bg = css_color(theme.statusbar_bg);
+    let text = css_color(theme.text_primary);
+    let caption = format!(
+        "{} \u{00b7} {} processes",
+        app.size_label(),
+        app.snapshot.process_tree.processes.len()
+    );
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(out, "<title>treetop: {}</title>", escape(&caption));
+    out.push_str("<style>\n");
+    let _ = writeln!(
+        out,
+        "body {{ margin: 16px; background: {page_bg}; color: {text}; font: 14px monospace; }}"
+    );
+    out.push_str("svg { display: block; max-width: 100%; height: auto; }\n");
+    let _ = writeln!(
+        out,
+        "#tip {{ position: absolute; display: none; pointer-events: none; white-space: pre; \
+         padding: 4px 8px; border-radius: 4px; background: {page_bg}; color: {text}; \
+         border: 1px solid {text}; }}"
+    );
+    out.push_str("</style>\n</head>\n<body>\n");
+    let _ = writeln!(out, "<p>{}</p>", escape(&caption));
+    out.push_str(&document(app, width, height, false));
+    out.push_str("<div id=\"tip\"></div>\n");
+    out.push_str(TOOLTIP_SCRIPT);
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+const TOOLTIP_SCRIPT: &str = r#"<script>
+const tip = document.getElementById("tip");
+for (const tile of document.querySelectorAll("[data-tip]")) {
+  tile.addEventListener("mousemove", (event) => {
+    tip.textContent = tile.dataset.tip;
+    tip.style.left = event.pageX + 12 + "px";
+    tip.style.top = event.pageY + 12 + "px";
+    tip.style.display = "block";
+  });
+  tile.addEventListener("mouseleave", () => {
+    tip.style.display = "none";
+  });
+}
+</script>
+"#;
+
+/// The SVG element; tooltips go in `<title>` children when `titles` is
+/// set, in `data-tip` attributes for the HTML script otherwise.
+fn document(app: &App, width: u32, height: u32, titles: bool) -> String {
+    let items: Vec<TreemapItem> = app
+        .layout_rects
+        .iter()
+        .map(|r| TreemapItem {
+            pid: r.pid,
+            label: r.label.clone(),
+            value: r.value,
+        })
+        .collect();
+    let bounds = LayoutRect::new(0.0, 0.0, f64::from(width), f64::from(height));
+    let rects = squarify_sorted(&items, &bounds);
+    let marks = app.tile_marks();
+    let tiles = colored_rects(app, &rects, &marks);
+    let format_value = app.value_format();
+    let seam = css_color(app.theme.surface_bg);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"13\">"
+    );
+    let _ = writeln!(
+        out,
+        "<rect width=\"{width}\" height=\"{height}\" fill=\"{seam}\"/>"
+    );
+    for tile in &tiles {
+        let LayoutRect {
+            x,
+            y,
+            width: w,
+            height: h,
+        } = tile.rect;
+        let tip = escape(&tooltip(app, &marks, tile.pid, &tile.label, tile.value));
+        if titles {
+            let _ = writeln!(out, "<g>\n<title>{tip}</title>");
+        } else {
+            let _ = writeln!(out, "<g data-tip=\"{tip}\">");
+        }
+        let _ = writeln!(
+            out,
+            "<rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{w:.1}\" height=\"{h:.1}\" fill=\"{}\" \
+             stroke=\"{seam}\"/>",
+            css_color(tile.color)
+        );
+        if w >= LABEL_MIN_WIDTH && h >= LABEL_MIN_HEIGHT {
+            // A nested viewport clips labels longer than the tile
+            let ink = css_color(contrast_color(tile.color));
+            let flag = marks.flag(tile.pid).unwrap_or("");
+            let _ = write!(
+                out,
+                "<svg x=\"{x:.1}\" y=\"{y:.1}\" width=\"{w:.1}\" height=\"{h:.1}\">\
+                 <text x=\"6\" y=\"15\" fill=\"{ink}\">{}</text>",
+                escape(&format!("{flag}{}", tile.label))
+            );
+            if h >= VALUE_MIN_HEIGHT {
+                let _ = write!(
+                    out,
+                    "<text x=\"6\" y=\"31\" fill=\"{ink}\">{}{}</text>",
+                    marks.sign(tile.pid),
+                    escape(&format_value(tile.value))
+                );
+            }
+            out.push_str("</svg>\n");
+        }
+        out.push_str("</g>\n");
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Tooltip lines for a tile: PID, command, memory and CPU for processes,
+/// including ones that exited since the compare baseline, and the label and
+/// value for groups, cgroups, regions and `Other`.
+fn tooltip(app: &App, marks: &TileMarks, pid: u32, label: &str, value: u64) -> String {
+    let exited = app.diff.as_ref().filter(|_| app.comparing());
+    let process = app
+        .snapshot
+        .process_tree
+        .processes
+        .get(&pid)
+        .or_else(|| exited.and_then(|d| d.exited.get(&pid)));
+    let Some(p) = process.filter(|_| pid != 0 && app.memory_map.is_none()) else {
+        return format!("{label}\n{}", (app.value_format())(value));
+    };
+    let command = if p.command.is_empty() {
+        &p.name
+    } else {
+        &p.command
+    };
+    // Arguments can run to pages and hold line breaks of their own
+    let command = truncate_unicode(
+        &command.split_whitespace().collect::<Vec<_>>().join(" "),
+        TOOLTIP_COMMAND_WIDTH,
+    );
+    let metric = app.memory_metric;
+    let mut tip = format!(
+        "PID {}\n{command}\nMemory: {} {}\nCPU: {:.1}%",
+        p.pid,
+        format_bytes(p.memory_for(metric)),
+        metric.label(),
+        p.cpu_percent
+    );
+    if app.comparing() {
+        let _ = write!(
+            tip,
+            "\nChange: {}{}",
+            marks.sign(pid),
+            (app.value_format())(value)
+        );
+    }
+    tip
+}
+
+/// `color` as a CSS hex color; the terminal default has none.
+fn css_color(color: Color) -> String {
+    color_to_rgb(color).map_or_else(
+        || "none".to_string(),
+        |(r, g, b)| format!("#{r:02x}{g:02x}{b:02x}"),
+    )
+}
+
+/// Escapes text for XML content and attribute values, keeping line breaks
+/// in attributes.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            '\n' => out.push_str("&#10;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
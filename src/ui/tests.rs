@@ -1,19 +1,42 @@
 use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
 
 use insta::assert_snapshot;
 use ratatui::Terminal;
 use ratatui::backend::TestBackend;
 use ratatui::layout::Rect;
 
-use crate::app::InputMode;
-use crate::system::history::HistoryStore;
-use crate::system::process::{ProcessInfo, ProcessState, ProcessTree};
+use crate::app::{
+    AuditLogView, DetailExtras, FrozenListView, FrozenProcess, InputMode, NiceEntry, OpenFilesView,
+    SignalConfirm, SignalPicker,
+};
+use crate::format::format_bytes;
+use crate::system::audit::AuditEntry;
+use crate::system::cgroup::{CgroupNode, CpuStat, MemoryEvents};
+use crate::system::fds::OpenFile;
+use crate::system::history::{HistorySample, HistoryStore};
+use crate::system::kill::SIGNALS;
+use crate::system::net::{ListeningSocket, ProcessSockets, SocketProtocol};
+use crate::system::priority::{IoClass, IoPriority, SchedPolicy};
+use crate::system::process::{
+    CpuTimes, MemoryMetric, ProcessIdentity, ProcessInfo, ProcessState, ProcessTree,
+};
+use crate::system::recording::{Frame, Recording};
+use crate::system::replay::Replay;
 use crate::system::snapshot::SystemSnapshot;
 use crate::treemap::node::LayoutRect;
 use crate::ui::theme::{
     BorderStyle, ColorMode, ColorSupport, ColoredTreemapRect, HeatOverrides, Theme,
 };
-use crate::ui::{detail_panel, header, selection_bar, statusbar, treemap_widget};
+use crate::ui::treemap_widget::TileMarks;
+use crate::ui::{
+    audit_log, detail_panel, frozen_list, header, nice_entry, open_files, replay_bar,
+    selection_bar, signal_confirm, signal_picker, statusbar, treemap_widget,
+};
+
+/// Wall-clock time detail panels are rendered at.
+const NOW: u64 = 1_740_233_600;
 
 fn buffer_to_string(buf: &ratatui::buffer::Buffer) -> String {
     let area = buf.area;
@@ -56,6 +79,13 @@ fn make_process(pid: u32, name: &str, memory: u64, cpu: f32) -> ProcessInfo {
         group_name: None,
         priority: None,
         io_stats: None,
+        memory_detail: Default::default(),
+        container: None,
+        io_rate: None,
+        fd_count: None,
+        start_time: None,
+        start_ticks: None,
+        cpu_time: None,
     }
 }
 
@@ -95,7 +125,9 @@ fn snapshot_header() {
             frame,
             Rect::new(0, 0, 80, 3),
             &snapshot,
+            "live",
             ColorMode::ByMemory,
+            "RSS",
             &make_theme(),
             BorderStyle::Rounded,
             &[(1, "alpha".to_string())],
@@ -126,7 +158,7 @@ fn snapshot_statusbar() {
 #[test]
 fn snapshot_selection_bar_empty() {
     let output = render_to_string(80, 1, |frame| {
-        selection_bar::render(frame, Rect::new(0, 0, 80, 1), None, &make_theme());
+        selection_bar::render(frame, Rect::new(0, 0, 80, 1), None, None, &make_theme());
     });
 
     assert_snapshot!("ui_selection_bar_empty", output);
@@ -139,10 +171,11 @@ fn snapshot_selection_bar_with_selection() {
             frame,
             Rect::new(0, 0, 80, 1),
             Some(selection_bar::SelectionInfo {
-                pid: 1,
+                pid: Some(1),
                 name: "Brave Browser Helper".to_string(),
                 memory_bytes: 571_400_000,
             }),
+            None,
             &make_theme(),
         );
     });
@@ -150,6 +183,30 @@ fn snapshot_selection_bar_with_selection() {
     assert_snapshot!("ui_selection_bar_with_selection", output);
 }
 
+#[test]
+fn snapshot_selection_bar_with_marked_processes() {
+    let output = render_to_string(80, 1, |frame| {
+        selection_bar::render(
+            frame,
+            Rect::new(0, 0, 80, 1),
+            Some(selection_bar::SelectionInfo {
+                pid: Some(1),
+                name: "Brave Browser Helper".to_string(),
+                memory_bytes: 571_400_000,
+            }),
+            Some(selection_bar::MarkedSummary {
+                count: 5,
+                memory_bytes: 2_400_000_000,
+                cpu_percent: 87.25,
+            }),
+            &make_theme(),
+        );
+    });
+
+    assert!(!output.contains("Brave"));
+    assert_snapshot!("ui_selection_bar_marked", output);
+}
+
 #[test]
 fn snapshot_selection_bar_truncation_right_memory_preserved() {
     let output = render_to_string(24, 1, |frame| {
@@ -157,10 +214,11 @@ fn snapshot_selection_bar_truncation_right_memory_preserved() {
             frame,
             Rect::new(0, 0, 24, 1),
             Some(selection_bar::SelectionInfo {
-                pid: 99,
+                pid: Some(99),
                 name: "Extremely Long Application Name With Suffix".to_string(),
                 memory_bytes: 1_234_567_890,
             }),
+            None,
             &make_theme(),
         );
     });
@@ -175,24 +233,316 @@ fn snapshot_detail_panel() {
     let process = snapshot.process_tree.processes.get(&1).unwrap();
     let mut store = HistoryStore::new(10);
     for i in 0..6 {
-        store.record(process.pid, 100_000_000 + i * 10_000_000, i as f32 * 5.0);
+        store.record(
+            process.pid,
+            HistorySample {
+                memory: 100_000_000 + i * 10_000_000,
+                cpu: i as f32 * 5.0,
+                io: i * 1_000_000,
+                fds: 12,
+                ..HistorySample::default()
+            },
+        );
     }
     let history = store.get(process.pid);
 
-    let output = render_to_string(40, 16, |frame| {
+    let output = render_to_string(40, 26, |frame| {
         detail_panel::render(
             frame,
-            Rect::new(0, 0, 40, 16),
+            Rect::new(0, 0, 40, 26),
             process,
+            MemoryMetric::Rss,
             &make_theme(),
             BorderStyle::Rounded,
             history,
+            None,
+            NOW,
         );
     });
 
     assert_snapshot!("ui_detail_panel", output);
 }
 
+#[test]
+fn snapshot_detail_panel_network_section() {
+    let snapshot = make_snapshot();
+    let mut process = snapshot.process_tree.processes.get(&1).unwrap().clone();
+    process.fd_count = Some(37);
+    process.cpu_time = Some(CpuTimes {
+        user: Duration::from_secs(3_492),
+        system: Duration::from_secs(408),
+        children_user: Duration::from_millis(4_000),
+        children_system: Duration::from_millis(1_250),
+    });
+    let sockets = ProcessSockets {
+        listening: vec![
+            ListeningSocket {
+                port: 443,
+                protocol: SocketProtocol::Tcp,
+                address: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            },
+            ListeningSocket {
+                port: 8080,
+                protocol: SocketProtocol::Tcp6,
+                address: IpAddr::V6(Ipv6Addr::LOCALHOST),
+            },
+        ],
+        established: 12,
+        unix: 3,
+    };
+    let extras = DetailExtras {
+        pid: process.pid,
+        sockets: Some(sockets),
+        fd_limit: Some(1024),
+        nice: Some(5),
+        sched_policy: Some(SchedPolicy::Other),
+        io_priority: Some(IoPriority {
+            class: IoClass::BestEffort,
+            level: 4,
+        }),
+    };
+
+    let output = render_to_string(44, 30, |frame| {
+        detail_panel::render(
+            frame,
+            Rect::new(0, 0, 44, 30),
+            &process,
+            MemoryMetric::Rss,
+            &make_theme(),
+            BorderStyle::Rounded,
+            None,
+            Some(&extras),
+            NOW,
+        );
+    });
+
+    assert!(output.contains("tcp6 [::1]:8080"));
+    assert!(output.contains("37 / 1024 (4%)"));
+    assert!(output.contains("1h 05m"));
+    assert_snapshot!("ui_detail_panel_network", output);
+}
+
+#[test]
+fn detail_panel_flags_climbing_fd_count() {
+    let snapshot = make_snapshot();
+    let mut process = snapshot.process_tree.processes.get(&1).unwrap().clone();
+    process.fd_count = Some(140);
+    let mut store = HistoryStore::new(20);
+    for i in 0..12 {
+        let sample = HistorySample {
+            fds: 100 + i * 4,
+            ..HistorySample::default()
+        };
+        store.record(process.pid, sample);
+    }
+
+    let output = render_to_string(40, 25, |frame| {
+        detail_panel::render(
+            frame,
+            Rect::new(0, 0, 40, 25),
+            &process,
+            MemoryMetric::Rss,
+            &make_theme(),
+            BorderStyle::Rounded,
+            store.get(process.pid),
+            None,
+            NOW,
+        );
+    });
+
+    assert!(output.contains("140 \u{25b2} leak?"));
+}
+
+#[test]
+fn snapshot_open_files_overlay() {
+    let mut view = OpenFilesView {
+        pid: 4242,
+        name: "nginx".to_string(),
+        files: [
+            (0, "/dev/null"),
+            (1, "pipe:[81235]"),
+            (2, "/var/log/nginx/error.log"),
+            (6, "socket:[81234]"),
+            (7, "anon_inode:[eventpoll]"),
+            (8, "/var/log/nginx/access.log"),
+        ]
+        .into_iter()
+        .map(|(fd, target)| OpenFile::new(fd, target.to_string()))
+        .collect(),
+        scroll: 5,
+    };
+
+    let output = render_to_string(50, 8, |frame| {
+        open_files::render(frame, Rect::new(0, 0, 50, 8), &mut view, &make_theme());
+    });
+
+    assert_eq!(view.scroll, 2, "scroll is clamped to the last full page");
+    assert_snapshot!("ui_open_files_overlay", output);
+}
+
+#[test]
+fn snapshot_signal_picker_keeps_selection_in_view() {
+    // Fixed Linux numbering so the snapshot is the same on every platform
+    let signals = SIGNALS
+        .iter()
+        .zip([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15])
+        .map(|(info, number)| (*info, Some(number)))
+        .collect();
+    let picker = SignalPicker::new(4242, "nginx".to_string(), signals, sysinfo::Signal::Term);
+
+    let output = render_to_string(54, 9, |frame| {
+        signal_picker::render(frame, Rect::new(0, 0, 54, 9), &picker, &make_theme());
+    });
+
+    assert!(output.contains("15 SIGTERM"));
+    assert_snapshot!("ui_signal_picker", output);
+}
+
+#[test]
+fn snapshot_audit_log_overlay() {
+    let entry = |pid: u32, signal: &str, result: &str, detail: Option<&str>| AuditEntry {
+        timestamp: format!("2025-02-22T14:0{}:20Z", pid % 10),
+        user: "alice".to_string(),
+        sudo_user: None,
+        action: "signal".to_string(),
+        pid,
+        start_time: Some(1_740_230_000),
+        name: "nginx".to_string(),
+        command: "nginx -g daemon off;".to_string(),
+        signal: signal.to_string(),
+        setting: None,
+        result: result.to_string(),
+        detail: detail.map(str::to_string),
+    };
+    let mut view = AuditLogView {
+        path: "/var/log/treetop/audit.jsonl".into(),
+        entries: vec![
+            entry(
+                4243,
+                "SIGKILL",
+                "refused",
+                Some("nginx is protected by \"nginx\""),
+            ),
+            entry(4242, "SIGTERM", "sent", None),
+            entry(4241, "SIGHUP", "not_found", None),
+        ],
+        scroll: 0,
+    };
+
+    let output = render_to_string(100, 7, |frame| {
+        audit_log::render(frame, Rect::new(0, 0, 100, 7), &mut view, &make_theme());
+    });
+
+    assert!(output.contains("refused"));
+    assert_snapshot!("ui_audit_log", output);
+}
+
+#[test]
+fn snapshot_frozen_list_before_quit() {
+    let frozen: Vec<FrozenProcess> = [(4242, "ffmpeg"), (4243, "ffmpeg-worker")]
+        .into_iter()
+        .map(|(pid, name)| FrozenProcess {
+            identity: ProcessIdentity {
+                pid,
+                start: Some(1_740_230_000),
+            },
+            name: name.to_string(),
+        })
+        .collect();
+    let view = FrozenListView {
+        cursor: 1,
+        quitting: true,
+    };
+
+    let output = render_to_string(60, 8, |frame| {
+        frozen_list::render(frame, Rect::new(0, 0, 60, 8), &frozen, &view, &make_theme());
+    });
+
+    assert!(output.contains("Quit with 2 processes still frozen?"));
+    assert_snapshot!("ui_frozen_list", output);
+}
+
+#[test]
+fn snapshot_nice_entry() {
+    let entry = NiceEntry {
+        pid: 4242,
+        name: "ffmpeg".to_string(),
+        marked: 0,
+        current: Some(0),
+        text: "-5".to_string(),
+    };
+
+    let output = render_to_string(50, 7, |frame| {
+        nice_entry::render(frame, Rect::new(0, 0, 50, 7), &entry, &make_theme());
+    });
+
+    assert!(output.contains("Nice: ffmpeg (4242)"));
+    assert_snapshot!("ui_nice_entry", output);
+}
+
+#[test]
+fn snapshot_signal_confirm_dialog() {
+    let confirm = SignalConfirm {
+        pid: 4242,
+        marked: false,
+        targets: Vec::new(),
+        signal: sysinfo::Signal::Kill,
+        tree: false,
+        name: "postgres".to_string(),
+        user: Some("postgres".to_string()),
+        command: "/usr/lib/postgresql/16/bin/postgres -D /var/lib/postgresql/16/main -c config_file=/etc/postgresql/16/main/postgresql.conf".to_string(),
+        memory: 512 * 1024 * 1024,
+        descendants: 7,
+        confirm_key: "y".to_string(),
+    };
+
+    let output = render_to_string(70, 12, |frame| {
+        signal_confirm::render(frame, Rect::new(0, 0, 70, 12), &confirm, &make_theme());
+    });
+
+    assert!(output.contains("Send SIGKILL?"));
+    assert!(output.contains("7, left running as orphans"));
+    assert_snapshot!("ui_signal_confirm", output);
+}
+
+#[test]
+fn snapshot_cgroup_detail_panel() {
+    let node = CgroupNode {
+        path: "/system.slice/nginx.service".to_string(),
+        name: "nginx.service".to_string(),
+        memory_current: 268_435_456,
+        memory_max: Some(536_870_912),
+        memory_high: None,
+        memory_events: MemoryEvents {
+            high: 41,
+            max: 5,
+            oom: 2,
+            oom_kill: 1,
+        },
+        cpu_stat: CpuStat {
+            usage_usec: 12_000_000,
+            user_usec: 9_000_000,
+            system_usec: 3_000_000,
+            nr_throttled: 12,
+            throttled_usec: 480_000,
+        },
+        process_count: 3,
+        has_children: false,
+    };
+
+    let output = render_to_string(44, 12, |frame| {
+        detail_panel::render_cgroup(
+            frame,
+            Rect::new(0, 0, 44, 12),
+            &node,
+            &make_theme(),
+            BorderStyle::Rounded,
+        );
+    });
+
+    assert_snapshot!("ui_cgroup_detail_panel", output);
+}
+
 #[test]
 fn snapshot_treemap_widget() {
     let rects = vec![
@@ -222,6 +572,8 @@ fn snapshot_treemap_widget() {
             2,
             BorderStyle::Rounded,
             &make_theme(),
+            format_bytes,
+            &TileMarks::default(),
         );
     });
 
@@ -257,6 +609,8 @@ fn snapshot_treemap_selected_warm_block() {
             2,
             BorderStyle::Rounded,
             &make_theme(),
+            format_bytes,
+            &TileMarks::default(),
         );
     });
 
@@ -292,6 +646,8 @@ fn snapshot_treemap_other_group_present() {
             2,
             BorderStyle::Rounded,
             &make_theme(),
+            format_bytes,
+            &TileMarks::default(),
         );
     });
 
@@ -341,6 +697,8 @@ fn snapshot_treemap_mixed_palette() {
             2,
             BorderStyle::Rounded,
             &make_theme(),
+            format_bytes,
+            &TileMarks::default(),
         );
     });
 
@@ -383,8 +741,43 @@ fn snapshot_treemap_flush_tiles() {
             2,
             BorderStyle::Rounded,
             &make_theme(),
+            format_bytes,
+            &TileMarks::default(),
         );
     });
 
     assert_snapshot!("ui_treemap_flush_tiles", output);
 }
+
+#[test]
+fn snapshot_replay_bar() {
+    let frames = [1_740_230_000_000, 1_740_230_002_000, 1_740_230_004_000]
+        .into_iter()
+        .map(|time_ms| Frame {
+            time_ms,
+            keyframe: true,
+            cpu_usage_percent: 0.0,
+            memory_total: 0,
+            memory_used: 0,
+            swap_total: 0,
+            swap_used: 0,
+            cpu_per_core: Vec::new(),
+            load_average: [0.0; 3],
+            changed: Vec::new(),
+            exited: Vec::new(),
+        })
+        .collect();
+    let mut replay = Replay::new("night.jsonl".into(), Recording { frames });
+    replay.step(1);
+    replay.change_speed(1);
+
+    let mut track = Rect::default();
+    let output = render_to_string(60, 1, |frame| {
+        track = replay_bar::render(frame, Rect::new(0, 0, 60, 1), &replay, &make_theme());
+    });
+
+    assert!(output.contains("2x  2025-02-22T13:13:22Z"));
+    assert!(output.ends_with(" 2/3 "));
+    assert_eq!(track.x + track.width, 60 - " 2/3 ".len() as u16);
+    assert_snapshot!("ui_replay_bar", output);
+}
@@ -1,5 +1,6 @@
 use ratatui::style::Color;
 use ratatui::widgets::BorderType;
+use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 
 use crate::config::ColorsConfig;
@@ -11,6 +12,8 @@ pub enum ColorMode {
     ByName,
     ByMemory,
     ByCpu,
+    /// Combined read and write throughput since the previous refresh.
+    ByIo,
     ByUser,
     ByGroup,
     Monochrome,
@@ -21,7 +24,8 @@ impl ColorMode {
         match self {
             ColorMode::ByName => ColorMode::ByMemory,
             ColorMode::ByMemory => ColorMode::ByCpu,
-            ColorMode::ByCpu => ColorMode::ByUser,
+            ColorMode::ByCpu => ColorMode::ByIo,
+            ColorMode::ByIo => ColorMode::ByUser,
             ColorMode::ByUser => ColorMode::ByGroup,
             ColorMode::ByGroup => ColorMode::Monochrome,
             ColorMode::Monochrome => ColorMode::ByName,
@@ -33,6 +37,7 @@ impl ColorMode {
             ColorMode::ByName => "Name",
             ColorMode::ByMemory => "Memory",
             ColorMode::ByCpu => "CPU",
+            ColorMode::ByIo => "I/O",
             ColorMode::ByUser => "User",
             ColorMode::ByGroup => "Group",
             ColorMode::Monochrome => "Mono",
@@ -43,6 +48,7 @@ impl ColorMode {
         match s.to_lowercase().as_str() {
             "name" | "process" => ColorMode::ByName,
             "cpu" => ColorMode::ByCpu,
+            "io" => ColorMode::ByIo,
             "user" => ColorMode::ByUser,
             "group" => ColorMode::ByGroup,
             "mono" | "monochrome" => ColorMode::Monochrome,
@@ -499,6 +505,7 @@ pub fn colorize_rects(
         ColorMode::ByName => apply_name_colors(&mut colored, process_tree, theme),
         ColorMode::ByMemory => apply_memory_heatmap(&mut colored, total_memory, theme),
         ColorMode::ByCpu => apply_cpu_heatmap(&mut colored, process_tree, theme),
+        ColorMode::ByIo => apply_io_heatmap(&mut colored, process_tree, theme),
         ColorMode::ByUser => apply_user_colors(&mut colored, process_tree, theme),
         ColorMode::ByGroup => apply_group_colors(&mut colored, process_tree, theme),
         ColorMode::Monochrome => apply_monochrome(&mut colored, total_memory, theme),
@@ -517,6 +524,39 @@ pub fn colorize_rects(
     colored
 }
 
+/// Colors compare-mode tiles by which way they changed: growth in the
+/// error color, shrinkage in the OK color. Mono terminals get the size
+/// ramp; the signed values still tell the two apart.
+pub fn colorize_changes(
+    rects: &[TreemapRect],
+    shrunk: &HashSet<u32>,
+    theme: &Theme,
+    support: ColorSupport,
+) -> Vec<ColoredTreemapRect> {
+    let mut colored: Vec<ColoredTreemapRect> = rects
+        .iter()
+        .map(|r| {
+            let color = if shrunk.contains(&r.pid) {
+                theme.status_ok
+            } else {
+                theme.status_err
+            };
+            ColoredTreemapRect::from_base(r, color)
+        })
+        .collect();
+    if support == ColorSupport::Mono {
+        let total = rects.iter().map(|r| r.value).sum();
+        apply_monochrome(&mut colored, total, theme);
+    }
+    for rect in colored.iter_mut() {
+        if rect.pid == 0 {
+            rect.color = theme.other_group_bg;
+        }
+        rect.color = adapt_color(rect.color, support);
+    }
+    colored
+}
+
 fn apply_name_colors(rects: &mut [ColoredTreemapRect], process_tree: &ProcessTree, theme: &Theme) {
     for rect in rects.iter_mut() {
         let process_name = process_tree
@@ -578,6 +618,33 @@ fn cpu_color(cpu_percent: f32, theme: &Theme) -> Color {
     }
 }
 
+fn apply_io_heatmap(rects: &mut [ColoredTreemapRect], process_tree: &ProcessTree, theme: &Theme) {
+    for rect in rects.iter_mut() {
+        let rate = process_tree
+            .processes
+            .get(&rect.pid)
+            .and_then(|p| p.io_rate)
+            .map(|r| r.total())
+            .unwrap_or(0);
+        rect.color = io_color(rate, theme);
+    }
+}
+
+fn io_color(bytes_per_sec: u64, theme: &Theme) -> Color {
+    const MB: u64 = 1024 * 1024;
+    if bytes_per_sec > 50 * MB {
+        theme.heat_colors[4]
+    } else if bytes_per_sec > 10 * MB {
+        theme.heat_colors[3]
+    } else if bytes_per_sec > MB {
+        theme.heat_colors[2]
+    } else if bytes_per_sec > 0 {
+        theme.heat_colors[1]
+    } else {
+        theme.heat_colors[0]
+    }
+}
+
 fn apply_user_colors(rects: &mut [ColoredTreemapRect], process_tree: &ProcessTree, theme: &Theme) {
     apply_hash_colors(rects, process_tree, theme, |p| {
         p.user_id.clone().unwrap_or_default()
@@ -738,7 +805,7 @@ fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::system::process::{ProcessInfo, ProcessTree};
+    use crate::system::process::{MemoryDetail, ProcessInfo, ProcessTree};
     use crate::treemap::node::LayoutRect;
 
     fn make_rect(pid: u32, value: u64) -> TreemapRect {
@@ -765,6 +832,13 @@ mod tests {
             group_name: None,
             priority: None,
             io_stats: None,
+            memory_detail: MemoryDetail::default(),
+            container: None,
+            io_rate: None,
+            fd_count: None,
+            start_time: None,
+            start_ticks: None,
+            cpu_time: None,
         }
     }
 
@@ -784,6 +858,8 @@ mod tests {
         mode = mode.next();
         assert_eq!(mode, ColorMode::ByCpu);
         mode = mode.next();
+        assert_eq!(mode, ColorMode::ByIo);
+        mode = mode.next();
         assert_eq!(mode, ColorMode::ByUser);
         mode = mode.next();
         assert_eq!(mode, ColorMode::ByGroup);
@@ -798,6 +874,7 @@ mod tests {
         assert_eq!(ColorMode::ByName.label(), "Name");
         assert_eq!(ColorMode::ByMemory.label(), "Memory");
         assert_eq!(ColorMode::ByCpu.label(), "CPU");
+        assert_eq!(ColorMode::ByIo.label(), "I/O");
         assert_eq!(ColorMode::ByUser.label(), "User");
         assert_eq!(ColorMode::ByGroup.label(), "Group");
         assert_eq!(ColorMode::Monochrome.label(), "Mono");
@@ -808,6 +885,7 @@ mod tests {
         assert_eq!(ColorMode::from_str_config("name"), ColorMode::ByName);
         assert_eq!(ColorMode::from_str_config("memory"), ColorMode::ByMemory);
         assert_eq!(ColorMode::from_str_config("cpu"), ColorMode::ByCpu);
+        assert_eq!(ColorMode::from_str_config("io"), ColorMode::ByIo);
         assert_eq!(ColorMode::from_str_config("user"), ColorMode::ByUser);
         assert_eq!(ColorMode::from_str_config("group"), ColorMode::ByGroup);
         assert_eq!(ColorMode::from_str_config("mono"), ColorMode::Monochrome);
@@ -922,6 +1000,34 @@ mod tests {
         assert_eq!(memory_color(51, 100, &theme), theme.heat_colors[4]);
     }
 
+    #[test]
+    fn io_heatmap_uses_process_rate() {
+        let heat = HeatOverrides {
+            low: "#475569".to_string(),
+            mid: "#f97316".to_string(),
+            high: "#ec4899".to_string(),
+        };
+        let theme = Theme::from_config("vivid", &heat, ColorSupport::Truecolor);
+        let mut busy = make_process(1, 100, 0.0);
+        busy.io_rate = Some(crate::system::process::IoRate {
+            read_per_sec: 40 * 1024 * 1024,
+            write_per_sec: 20 * 1024 * 1024,
+        });
+        let tree = make_tree(vec![busy, make_process(2, 100, 0.0)]);
+        let rects = vec![make_rect(1, 100), make_rect(2, 100)];
+        let colored = colorize_rects(
+            &rects,
+            &tree,
+            200,
+            ColorMode::ByIo,
+            &theme,
+            ColorSupport::Truecolor,
+        );
+        assert_eq!(colored[0].color, theme.heat_colors[4]);
+        assert_eq!(colored[1].color, theme.heat_colors[0]);
+        assert_eq!(io_color(1024 * 1024 + 1, &theme), theme.heat_colors[2]);
+    }
+
     #[test]
     fn other_group_is_always_neutral() {
         let heat = HeatOverrides {
@@ -937,6 +1043,7 @@ mod tests {
             ColorMode::ByName,
             ColorMode::ByMemory,
             ColorMode::ByCpu,
+            ColorMode::ByIo,
             ColorMode::ByUser,
             ColorMode::ByGroup,
             ColorMode::Monochrome,
@@ -2,9 +2,9 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::Widget;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::format::{format_bytes, truncate_unicode};
+use crate::format::truncate_unicode;
 use crate::ui::theme::{BorderStyle, ColoredTreemapRect, Theme};
 
 const LUMINANCE_BLACK_TEXT_THRESHOLD: f64 = 130.0;
@@ -16,6 +16,52 @@ pub struct TreemapWidget<'a> {
     min_label_height: u16,
     _border_style: BorderStyle,
     theme: &'a Theme,
+    /// Renders a tile's value, e.g. bytes or bytes per second.
+    value_format: fn(u64) -> String,
+    marks: &'a TileMarks,
+}
+
+/// Per-tile state drawn over the fill, keyed by tile PID.
+#[derive(Debug, Default)]
+pub struct TileMarks {
+    /// Stopped processes, whose tiles are hatched.
+    pub stopped: HashSet<u32>,
+    /// Processes marked for batch actions, flagged with a dot.
+    pub marked: HashSet<u32>,
+    /// Compare mode: tiles whose memory grew, their value signed `+`.
+    pub grown: HashSet<u32>,
+    /// Compare mode: tiles whose memory shrank, their value signed `-`.
+    pub shrunk: HashSet<u32>,
+    /// Compare mode: processes started since the baseline, flagged `+`.
+    pub new: HashSet<u32>,
+    /// Compare mode: processes gone since the baseline, flagged `×`.
+    pub exited: HashSet<u32>,
+}
+
+impl TileMarks {
+    /// The flag drawn before a tile's label, if any; marking wins.
+    pub(crate) fn flag(&self, pid: u32) -> Option<&'static str> {
+        if self.marked.contains(&pid) {
+            Some("\u{25cf}")
+        } else if self.new.contains(&pid) {
+            Some("+")
+        } else if self.exited.contains(&pid) {
+            Some("\u{00d7}")
+        } else {
+            None
+        }
+    }
+
+    /// The sign before a tile's value in compare mode.
+    pub(crate) fn sign(&self, pid: u32) -> &'static str {
+        if self.grown.contains(&pid) {
+            "+"
+        } else if self.shrunk.contains(&pid) {
+            "-"
+        } else {
+            ""
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -28,6 +74,8 @@ pub fn render(
     min_label_height: u16,
     border_style: BorderStyle,
     theme: &Theme,
+    value_format: fn(u64) -> String,
+    marks: &TileMarks,
 ) {
     let widget = TreemapWidget {
         rects,
@@ -36,6 +84,8 @@ pub fn render(
         min_label_height,
         _border_style: border_style,
         theme,
+        value_format,
+        marks,
     };
     frame.render_widget(widget, area);
 }
@@ -74,6 +124,9 @@ impl<'a> Widget for TreemapWidget<'a> {
                 continue;
             };
             fill_rect(buf, term_rect, Style::default().bg(trect.color));
+            if self.marks.stopped.contains(&trect.pid) {
+                hatch_rect(buf, term_rect, trect.color);
+            }
         }
 
         // Pass 2: draw shared plain seams for unselected tiles.
@@ -112,6 +165,16 @@ impl<'a> Widget for TreemapWidget<'a> {
                 term_rect.y
             };
 
+            if let Some(flag) = self.marks.flag(trect.pid)
+                && label_x > term_rect.x
+            {
+                let style = Style::default()
+                    .fg(fg_color)
+                    .bg(bg_color)
+                    .add_modifier(Modifier::BOLD);
+                buf.set_string(label_x - 1, label_y, flag, style);
+            }
+
             if term_rect.width >= self.min_label_width && term_rect.height >= self.min_label_height
             {
                 if label_max_w >= 5 {
@@ -125,7 +188,11 @@ impl<'a> Widget for TreemapWidget<'a> {
 
                 let value_y = label_y + 1;
                 if value_y < term_rect.y + term_rect.height && label_max_w >= 8 {
-                    let value_str = format_bytes(trect.value);
+                    let value_str = format!(
+                        "{}{}",
+                        self.marks.sign(trect.pid),
+                        (self.value_format)(trect.value)
+                    );
                     let value = truncate_unicode(&value_str, label_max_w as usize);
                     let style = Style::default().fg(fg_color).bg(bg_color);
                     buf.set_string(label_x, value_y, &value, style);
@@ -182,6 +249,20 @@ const MASK_E: u8 = 0b0010;
 const MASK_S: u8 = 0b0100;
 const MASK_W: u8 = 0b1000;
 
+/// Diagonal hatching over a tile's fill; labels are drawn on top.
+fn hatch_rect(buf: &mut Buffer, rect: Rect, bg: Color) {
+    let style = Style::default().fg(contrast_color(bg)).bg(bg);
+    for row in rect.y..rect.y + rect.height {
+        for col in rect.x..rect.x + rect.width {
+            if (col + row) % 3 == 0
+                && let Some(cell) = buf.cell_mut((col, row))
+            {
+                cell.set_char('\u{2571}').set_style(style);
+            }
+        }
+    }
+}
+
 fn draw_seam_grid(buf: &mut Buffer, area: Rect, rects: &[Rect], style: Style) {
     let mut seam_masks: HashMap<(u16, u16), u8> = HashMap::new();
 
@@ -300,7 +381,7 @@ fn seam_glyph(mask: u8) -> char {
     }
 }
 
-fn contrast_color(bg: Color) -> Color {
+pub(crate) fn contrast_color(bg: Color) -> Color {
     if let Some((r, g, b)) = color_to_rgb(bg) {
         let luminance = color_luminance(r, g, b);
         if luminance >= LUMINANCE_BLACK_TEXT_THRESHOLD {
@@ -317,7 +398,7 @@ fn color_luminance(r: u8, g: u8, b: u8) -> f64 {
     0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64
 }
 
-fn color_to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+pub(crate) fn color_to_rgb(color: Color) -> Option<(u8, u8, u8)> {
     match color {
         Color::Rgb(r, g, b) => Some((r, g, b)),
         Color::Indexed(index) => Some(ansi256_to_rgb(index)),
@@ -419,6 +500,7 @@ fn draw_heavy_border(buf: &mut Buffer, rect: Rect, style: Style) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::format::format_bytes;
     use crate::treemap::node::LayoutRect;
     use crate::ui::theme::Theme;
     use ratatui::buffer::Buffer;
@@ -460,6 +542,8 @@ mod tests {
             min_label_height,
             _border_style: BorderStyle::Thin,
             theme: &theme,
+            value_format: format_bytes,
+            marks: &TileMarks::default(),
         };
         widget.render(area, &mut buf);
         buf
@@ -559,4 +643,125 @@ mod tests {
         assert_eq!(buf.cell((1, 1)).unwrap().symbol(), " ");
         assert_eq!(buf.cell((2, 1)).unwrap().symbol(), "a");
     }
+
+    #[test]
+    fn stopped_tiles_are_hatched_under_their_label() {
+        let rects = vec![
+            ColoredTreemapRect {
+                rect: LayoutRect::new(0.0, 0.0, 10.0, 4.0),
+                pid: 1,
+                label: "alpha".into(),
+                value: 1_000_000,
+                color: Color::Rgb(96, 165, 250),
+            },
+            ColoredTreemapRect {
+                rect: LayoutRect::new(10.0, 0.0, 10.0, 4.0),
+                pid: 2,
+                label: "beta".into(),
+                value: 1_000_000,
+                color: Color::Rgb(96, 165, 250),
+            },
+        ];
+        let area = Rect::new(0, 0, 20, 4);
+        let mut buf = Buffer::empty(area);
+        let theme = Theme::dark();
+        TreemapWidget {
+            rects: &rects,
+            selected_index: usize::MAX,
+            min_label_width: 1,
+            min_label_height: 1,
+            _border_style: BorderStyle::Thin,
+            theme: &theme,
+            value_format: format_bytes,
+            marks: &TileMarks {
+                stopped: HashSet::from([2]),
+                ..TileMarks::default()
+            },
+        }
+        .render(area, &mut buf);
+
+        let hatched = |x0: u16| {
+            (x0..x0 + 10)
+                .flat_map(|x| (0..4).map(move |y| (x, y)))
+                .filter(|&pos| buf.cell(pos).unwrap().symbol() == "\u{2571}")
+                .count()
+        };
+        assert_eq!(hatched(0), 0);
+        assert!(hatched(10) > 0);
+        assert_eq!(buf.cell((12, 1)).unwrap().symbol(), "b");
+    }
+
+    #[test]
+    fn marked_tiles_get_a_dot_before_the_label() {
+        let rects = vec![ColoredTreemapRect {
+            rect: LayoutRect::new(0.0, 0.0, 10.0, 4.0),
+            pid: 7,
+            label: "alpha".into(),
+            value: 1_000_000,
+            color: Color::Rgb(96, 165, 250),
+        }];
+        let area = Rect::new(0, 0, 10, 4);
+        let mut buf = Buffer::empty(area);
+        let theme = Theme::dark();
+        TreemapWidget {
+            rects: &rects,
+            selected_index: usize::MAX,
+            min_label_width: 1,
+            min_label_height: 1,
+            _border_style: BorderStyle::Thin,
+            theme: &theme,
+            value_format: format_bytes,
+            marks: &TileMarks {
+                marked: HashSet::from([7]),
+                ..TileMarks::default()
+            },
+        }
+        .render(area, &mut buf);
+
+        assert_eq!(buf.cell((1, 1)).unwrap().symbol(), "\u{25cf}");
+        assert_eq!(buf.cell((2, 1)).unwrap().symbol(), "a");
+    }
+
+    #[test]
+    fn compare_tiles_show_signed_values_and_change_flags() {
+        let tile = |x: f64, pid: u32| ColoredTreemapRect {
+            rect: LayoutRect::new(x, 0.0, 12.0, 4.0),
+            pid,
+            label: "alpha".into(),
+            value: 2 * 1024 * 1024,
+            color: Color::Rgb(96, 165, 250),
+        };
+        let rects = vec![tile(0.0, 7), tile(12.0, 8)];
+        let area = Rect::new(0, 0, 24, 4);
+        let mut buf = Buffer::empty(area);
+        let theme = Theme::dark();
+        TreemapWidget {
+            rects: &rects,
+            selected_index: usize::MAX,
+            min_label_width: 1,
+            min_label_height: 1,
+            _border_style: BorderStyle::Thin,
+            theme: &theme,
+            value_format: format_bytes,
+            marks: &TileMarks {
+                grown: HashSet::from([7]),
+                shrunk: HashSet::from([8]),
+                new: HashSet::from([7]),
+                exited: HashSet::from([8]),
+                ..TileMarks::default()
+            },
+        }
+        .render(area, &mut buf);
+
+        let row = |y: u16| {
+            (0..24)
+                .map(|x| buf.cell((x, y)).unwrap().symbol())
+                .collect::<String>()
+        };
+        assert_eq!(
+            row(1),
+            "\u{2502}+alpha     \u{2502}\u{00d7}alpha    \u{2502}"
+        );
+        assert_eq!(row(2), "\u{2502} +2.0 MB   \u{2502} -2.0 MB  \u{2502}");
+    }
 }
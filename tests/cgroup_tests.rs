@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use treetop::system::cgroup::{read_children, read_node};
+
+fn fixture_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/cgroupfs")
+}
+
+#[test]
+fn top_level_children_sorted_by_memory_current() {
+    let nodes = read_children(&fixture_root(), "/");
+    let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(names, vec!["system.slice", "user.slice", "init.scope"]);
+    assert_eq!(nodes[0].path, "/system.slice");
+    assert!(nodes[0].has_children);
+    assert!(!nodes[2].has_children);
+    assert_eq!(nodes[2].process_count, 1);
+}
+
+#[test]
+fn service_limits_and_events() {
+    let nodes = read_children(&fixture_root(), "/system.slice");
+    let nginx = nodes.iter().find(|n| n.name == "nginx.service").unwrap();
+    assert_eq!(nginx.path, "/system.slice/nginx.service");
+    assert_eq!(nginx.memory_current, 268_435_456);
+    assert_eq!(nginx.memory_max, Some(536_870_912));
+    assert_eq!(nginx.memory_high, Some(402_653_184));
+    assert_eq!(nginx.memory_events.oom, 2);
+    assert_eq!(nginx.memory_events.oom_kill, 1);
+    assert_eq!(nginx.cpu_stat.nr_throttled, 12);
+    assert_eq!(nginx.process_count, 3);
+
+    let postgres = nodes
+        .iter()
+        .find(|n| n.name == "postgresql.service")
+        .unwrap();
+    assert_eq!(postgres.memory_max, None, "\"max\" means unlimited");
+}
+
+#[test]
+fn missing_interface_files_read_as_unset() {
+    let init = read_node(&fixture_root(), "/init.scope").unwrap();
+    assert_eq!(init.memory_current, 8_388_608);
+    assert_eq!(init.memory_high, None);
+    assert_eq!(init.cpu_stat.usage_usec, 0);
+}
+
+#[test]
+fn vanished_cgroup_reads_as_none() {
+    assert!(read_node(&fixture_root(), "/system.slice/gone.service").is_none());
+    assert!(read_children(&fixture_root(), "/system.slice/gone.service").is_empty());
+}
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use treetop::system::container::{ContainerInfo, ContainerRuntime, QosClass, parse_proc_cgroup};
+
+const CONTAINER_ID: &str = "3f2a9c1e0b7d4c5e8f9a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e";
+const POD_UID: &str = "8d4e7c2a-1b3f-4a5c-9d6e-0f1a2b3c4d5e";
+
+fn fixture(name: &str) -> ContainerInfo {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/cgroup")
+        .join(name);
+    let contents = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("missing fixture {}: {e}", path.display()));
+    parse_proc_cgroup(&contents).expect("fixture has a cgroup line")
+}
+
+#[test]
+fn docker_systemd_driver() {
+    let info = fixture("docker_systemd_v2.txt");
+    assert_eq!(info.runtime, Some(ContainerRuntime::Docker));
+    assert_eq!(info.container_id.as_deref(), Some(CONTAINER_ID));
+    assert_eq!(info.pod_uid, None);
+    assert_eq!(info.display_name().as_deref(), Some("docker 3f2a9c1e0b7d"));
+}
+
+#[test]
+fn docker_cgroupfs_driver_on_v1_uses_memory_hierarchy() {
+    let info = fixture("docker_cgroupfs_v1.txt");
+    assert_eq!(info.runtime, Some(ContainerRuntime::Docker));
+    assert_eq!(info.container_id.as_deref(), Some(CONTAINER_ID));
+    assert_eq!(info.cgroup_path, format!("/docker/{CONTAINER_ID}"));
+}
+
+#[test]
+fn rootless_podman() {
+    let info = fixture("podman_rootless_v2.txt");
+    assert_eq!(info.runtime, Some(ContainerRuntime::Podman));
+    assert_eq!(info.container_id.as_deref(), Some(CONTAINER_ID));
+    assert_eq!(
+        info.group_key(),
+        Some(format!("podman:{CONTAINER_ID}")),
+        "podman processes group by container"
+    );
+}
+
+#[test]
+fn kubernetes_burstable_pod_on_containerd() {
+    let info = fixture("k8s_containerd_burstable_v2.txt");
+    assert_eq!(info.runtime, Some(ContainerRuntime::Containerd));
+    assert_eq!(info.container_id.as_deref(), Some(CONTAINER_ID));
+    assert_eq!(info.pod_uid.as_deref(), Some(POD_UID));
+    assert_eq!(info.qos, Some(QosClass::Burstable));
+    assert_eq!(info.group_key(), Some(format!("pod:{POD_UID}")));
+    assert_eq!(
+        info.display_name().as_deref(),
+        Some("pod 8d4e7c2a (burstable)")
+    );
+}
+
+#[test]
+fn kubernetes_guaranteed_pod_on_crio() {
+    let info = fixture("k8s_crio_guaranteed_v2.txt");
+    assert_eq!(info.runtime, Some(ContainerRuntime::CriO));
+    assert_eq!(info.pod_uid.as_deref(), Some(POD_UID));
+    assert_eq!(info.qos, Some(QosClass::Guaranteed));
+}
+
+#[test]
+fn kubernetes_cgroupfs_besteffort_pod() {
+    let info = fixture("k8s_cgroupfs_besteffort_v1.txt");
+    assert_eq!(info.pod_uid.as_deref(), Some(POD_UID));
+    assert_eq!(info.qos, Some(QosClass::BestEffort));
+    assert_eq!(info.container_id.as_deref(), Some(CONTAINER_ID));
+    assert_eq!(info.runtime, None, "cgroupfs paths don't name the runtime");
+}
+
+#[test]
+fn lxc_container_keeps_inner_unit() {
+    let info = fixture("lxc_v2.txt");
+    assert_eq!(info.runtime, Some(ContainerRuntime::Lxc));
+    assert_eq!(info.container_id.as_deref(), Some("web01"));
+    assert_eq!(info.systemd_unit.as_deref(), Some("nginx.service"));
+    assert_eq!(info.display_name().as_deref(), Some("lxc web01"));
+}
+
+#[test]
+fn host_session_is_not_a_container() {
+    let info = fixture("host_session_v2.txt");
+    assert_eq!(info.runtime, None);
+    assert_eq!(info.container_id, None);
+    assert_eq!(info.group_key(), None);
+    assert_eq!(info.systemd_unit.as_deref(), Some("session-2.scope"));
+}
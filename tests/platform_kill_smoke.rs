@@ -3,7 +3,13 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
-use treetop::system::kill::{KillResult, kill_process};
+use treetop::system::audit::AuditLog;
+use treetop::system::collector::Collector;
+use treetop::system::kill::{KillResult, kill_process, kill_tree};
+use treetop::system::platform;
+use treetop::system::priority::{PriorityResult, renice};
+use treetop::system::process::ProcessInfo;
+use treetop::system::safety::SafetyPolicy;
 
 fn refresh_system(sys: &mut System) {
     sys.refresh_processes_specifics(
@@ -40,6 +46,16 @@ fn spawn_long_lived_child() -> Child {
         .expect("failed to spawn child process")
 }
 
+/// `pid` as the collector records it, the way the UI targets it.
+fn process_of(pid: u32) -> ProcessInfo {
+    Collector::new()
+        .refresh()
+        .process_tree
+        .processes
+        .remove(&pid)
+        .unwrap_or_else(|| panic!("PID {pid} is not in the snapshot"))
+}
+
 fn wait_for_pid(sys: &mut System, pid: u32, timeout: Duration) -> bool {
     let sys_pid = Pid::from_u32(pid);
     let deadline = Instant::now() + timeout;
@@ -65,7 +81,9 @@ fn kill_nonexistent_pid_returns_not_found() {
     let mut sys = System::new();
     refresh_system(&mut sys);
 
-    let result = kill_process(&sys, u32::MAX, Signal::Term);
+    let mut target = process_of(std::process::id());
+    target.pid = u32::MAX;
+    let result = kill_process(&sys, &target, Signal::Term, &SafetyPolicy::default());
     assert!(matches!(result, KillResult::NotFound(_)));
 }
 
@@ -85,11 +103,16 @@ fn kill_spawned_child_terminates() {
     } else {
         Signal::Term
     };
-    let mut result = kill_process(&sys, pid, signal);
-    if matches!(result, KillResult::NotFound(_) | KillResult::Failed(_)) {
+    let mut result = kill_process(&sys, &process_of(pid), signal, &SafetyPolicy::default());
+    if matches!(result, KillResult::NotFound(_) | KillResult::Failed { .. }) {
         thread::sleep(Duration::from_millis(100));
         refresh_system(&mut sys);
-        result = kill_process(&sys, pid, Signal::Kill);
+        result = kill_process(
+            &sys,
+            &process_of(pid),
+            Signal::Kill,
+            &SafetyPolicy::default(),
+        );
     }
 
     match result {
@@ -112,13 +135,232 @@ fn kill_spawned_child_terminates() {
                 }
             }
         }
-        KillResult::Failed(err) => {
+        KillResult::Failed { reason, .. } => {
             let _ = child.kill();
-            panic!("kill_process reported failure: {err}");
+            panic!("kill_process reported failure: {reason}");
         }
         KillResult::NotFound(_) => {
             let _ = child.kill();
             panic!("child process not found in sysinfo snapshot");
         }
+        KillResult::PidReused(_) => {
+            let _ = child.kill();
+            panic!("identity of a live child did not match itself");
+        }
+        KillResult::Refused { reason, .. } => {
+            let _ = child.kill();
+            panic!("an empty policy refused the signal: {reason}");
+        }
+    }
+}
+
+#[test]
+fn kill_refuses_stale_identity() {
+    let mut child = spawn_long_lived_child();
+    let pid = child.id();
+
+    let mut sys = System::new();
+    if !wait_for_pid(&mut sys, pid, Duration::from_secs(3)) {
+        let _ = child.kill();
+        panic!("child process PID {pid} was not observed by sysinfo");
+    }
+    let process = process_of(pid);
+    let Some(start) = process.identity().start else {
+        let _ = child.kill();
+        panic!("no start time for PID {pid}");
+    };
+
+    // An earlier process that held the same PID, one clock tick before
+    let mut earlier = process.clone();
+    earlier.start_ticks = Some(start.saturating_sub(1));
+    let result = kill_process(&sys, &earlier, Signal::Kill, &SafetyPolicy::default());
+    let still_running = matches!(child.try_wait(), Ok(None));
+    if !matches!(result, KillResult::PidReused(_)) || !still_running {
+        let _ = child.kill();
+        panic!("signal to a stale identity was not refused");
+    }
+
+    // Once reaped, the identity must never be signalled again, whether the
+    // PID is free or already handed to someone else.
+    let _ = child.kill();
+    child.wait().expect("failed to reap child");
+    refresh_system(&mut sys);
+    let result = kill_process(&sys, &process, Signal::Kill, &SafetyPolicy::default());
+    assert!(
+        matches!(result, KillResult::NotFound(_) | KillResult::PidReused(_)),
+        "reaped child was signalled"
+    );
+}
+
+#[test]
+fn kill_refuses_protected_process() {
+    let mut child = spawn_long_lived_child();
+    let pid = child.id();
+
+    let mut sys = System::new();
+    if !wait_for_pid(&mut sys, pid, Duration::from_secs(3)) {
+        let _ = child.kill();
+        panic!("child process PID {pid} was not observed by sysinfo");
+    }
+
+    let log_path = std::env::temp_dir().join(format!("treetop_smoke_audit_{pid}.jsonl"));
+    let policy = SafetyPolicy::new(false, &[format!("pid:{pid}")])
+        .with_audit(AuditLog::new(log_path.clone()));
+    let result = kill_process(&sys, &process_of(pid), Signal::Kill, &policy);
+    let still_running = matches!(child.try_wait(), Ok(None));
+    let _ = child.kill();
+    let _ = child.wait();
+    assert!(
+        matches!(result, KillResult::Refused { .. }) && still_running,
+        "protected process was signalled"
+    );
+
+    let entries = policy.audit().unwrap().read_recent(10).unwrap();
+    let _ = std::fs::remove_file(&log_path);
+    assert_eq!(entries.len(), 1, "one attempt, one audit entry");
+    assert_eq!(entries[0].pid, pid);
+    assert_eq!(entries[0].result, "refused");
+    assert!(entries[0].start_time.is_some());
+    assert!(!entries[0].name.is_empty());
+    assert!(!entries[0].command.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn renice_raises_nice_value_of_child() {
+    let mut child = spawn_long_lived_child();
+    let pid = child.id();
+
+    let mut sys = System::new();
+    if !wait_for_pid(&mut sys, pid, Duration::from_secs(3)) {
+        let _ = child.kill();
+        panic!("child process PID {pid} was not observed by sysinfo");
+    }
+
+    // Raising the nice value never needs privileges
+    let target = platform::process_nice(pid).unwrap_or(0).max(5);
+    let result = renice(&sys, &process_of(pid), target, &SafetyPolicy::default());
+    let nice = platform::process_nice(pid);
+    let _ = child.kill();
+    let _ = child.wait();
+    assert!(
+        matches!(result, PriorityResult::Success { .. }),
+        "renice failed: {result:?}"
+    );
+    assert_eq!(nice, Some(target));
+}
+
+#[cfg(unix)]
+#[test]
+fn kill_tree_signals_parent_and_children() {
+    let mut child = Command::new("sh")
+        .args(["-c", "sleep 30 & sleep 30 & wait"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("failed to spawn process tree");
+    let pid = child.id();
+
+    let mut collector = Collector::new();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let (pids, targets) = loop {
+        let snapshot = collector.refresh();
+        let tree = &snapshot.process_tree;
+        let pids = tree.subtree(pid);
+        if pids.len() >= 3 {
+            let targets: Vec<ProcessInfo> =
+                pids.iter().map(|p| tree.processes[p].clone()).collect();
+            break (pids, targets);
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            panic!("children of PID {pid} never appeared: {pids:?}");
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+    assert_eq!(pids[0], pid, "the root comes first");
+
+    let log_path = std::env::temp_dir().join(format!("treetop_smoke_tree_audit_{pid}.jsonl"));
+    let policy = SafetyPolicy::default().with_audit(AuditLog::new(log_path.clone()));
+    let results = kill_tree(
+        collector.system_for_pids(&pids),
+        &targets,
+        Signal::Term,
+        &policy,
+    );
+    if !results.iter().all(|r| matches!(r, KillResult::Success(..))) {
+        let _ = child.kill();
+        panic!("not every process in the tree was signalled: {results:?}");
     }
+
+    // Each process is stopped, signalled and resumed, each step logged as such
+    let entries = policy.audit().unwrap().read_recent(100).unwrap();
+    let _ = std::fs::remove_file(&log_path);
+    for (action, signal) in [
+        ("freeze", "SIGSTOP"),
+        ("signal", "SIGTERM"),
+        ("resume", "SIGCONT"),
+    ] {
+        let logged = entries
+            .iter()
+            .filter(|e| e.action == action && e.signal == signal)
+            .count();
+        assert_eq!(logged, pids.len(), "{action} entries");
+    }
+
+    // The root exits and gets reaped here; the sleeps are reparented and
+    // must be gone (or zombies awaiting their new parent) shortly after.
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while matches!(child.try_wait(), Ok(None)) {
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            panic!("root of the tree did not exit");
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    let mut sys = System::new();
+    loop {
+        refresh_system(&mut sys);
+        let alive: Vec<u32> = pids[1..]
+            .iter()
+            .copied()
+            .filter(|p| {
+                sys.process(Pid::from_u32(*p))
+                    .is_some_and(|proc_| !matches!(proc_.status(), sysinfo::ProcessStatus::Zombie))
+            })
+            .collect();
+        if alive.is_empty() {
+            break;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "descendants still running: {alive:?}"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn kill_tree_refuses_a_tree_holding_treetop() {
+    let mut child = spawn_long_lived_child();
+    let pid = child.id();
+
+    let mut sys = System::new();
+    if !wait_for_pid(&mut sys, pid, Duration::from_secs(3)) {
+        let _ = child.kill();
+        panic!("child process PID {pid} was not observed by sysinfo");
+    }
+    // This test process stands in for treetop, with the child below it
+    let targets = [process_of(std::process::id()), process_of(pid)];
+    let results = kill_tree(&sys, &targets, Signal::Kill, &SafetyPolicy::default());
+    let still_running = matches!(child.try_wait(), Ok(None));
+    let _ = child.kill();
+    assert!(
+        results
+            .iter()
+            .all(|r| matches!(r, KillResult::Refused { .. })),
+        "a tree holding the caller was signalled: {results:?}"
+    );
+    assert!(still_running, "the child was signalled");
 }
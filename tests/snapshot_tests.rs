@@ -1,6 +1,6 @@
 use insta::assert_debug_snapshot;
 use treetop::system::process::{
-    ProcessInfo, ProcessState, ProcessTree, build_process_tree_from_flat,
+    MemoryDetail, ProcessInfo, ProcessState, ProcessTree, build_process_tree_from_flat,
 };
 
 fn mock_process(pid: u32, ppid: u32, name: &str, memory_bytes: u64) -> ProcessInfo {
@@ -18,6 +18,13 @@ fn mock_process(pid: u32, ppid: u32, name: &str, memory_bytes: u64) -> ProcessIn
         group_name: None,
         priority: None,
         io_stats: None,
+        memory_detail: MemoryDetail::default(),
+        container: None,
+        io_rate: None,
+        fd_count: None,
+        start_time: None,
+        start_ticks: None,
+        cpu_time: None,
     }
 }
 